@@ -6,27 +6,39 @@ use relm4::{
 
 use adw::prelude::{AdwApplicationWindowExt, AdwDialogExt, AlertDialogExt, NavigationPageExt};
 use gtk::prelude::{
-    ActionableExt, ApplicationExt, ButtonExt, Cast, EditableExt, GtkApplicationExt, GtkWindowExt,
-    ObjectExt, OrientableExt, SettingsExt, ToggleButtonExt, WidgetExt,
+    ActionableExt, ApplicationExt, ButtonExt, Cast, EditableExt, FileExt, GtkApplicationExt,
+    GtkWindowExt, ObjectExt, OrientableExt, SettingsExt, ToggleButtonExt, WidgetExt,
 };
 use gtk::{gio, glib};
 use std::{fs, path::PathBuf, str::FromStr};
 
 use crate::config::{APP_ID, PROFILE};
-use crate::database::{SessionIndexer, load_session};
+use crate::database::{
+    SearchQuery, SessionIndexer, load_changed_files, load_messages_for_session, load_session,
+    load_session_note, load_tag_counts, set_message_bookmarked, set_session_note,
+    set_session_starred, set_session_tags, set_session_title,
+};
+use crate::dbus_api::{SessionsApi, emit_sessions_changed};
+use crate::export;
 use crate::models::session::Tool;
+use crate::models::{DateRange, Permalink, Session, parse_search_uri};
 use crate::session_sources::{SessionSources, select_db_filename};
+use crate::settings::{custom_redactor, exclusion_rules, index_redactor, resume_multiplexer};
 use crate::ui::modals::{
     about::AboutDialog,
     preferences::{PreferencesDialog, PreferencesOutput},
     shortcuts::ShortcutsDialog,
 };
 use crate::ui::{
+    bookmarks::{BookmarksMsg, BookmarksOutput, BookmarksView},
     detail_context_pane::{DetailContextPane, DetailContextPaneMsg, DetailContextPaneOutput},
-    session_detail::{SessionDetail, SessionDetailMsg},
+    session_detail::{SessionDetail, SessionDetailMsg, SessionDetailOutput},
     session_list::{SessionList, SessionListMsg, SessionListOutput},
-    sidebar::{Sidebar, SidebarOutput},
+    sidebar::{Sidebar, SidebarMsg, SidebarOutput},
+    statistics::{StatisticsMsg, StatisticsView},
 };
+use crate::utils::command_template::TemplateContext;
+use crate::utils::multiplexer::window_name;
 use crate::utils::terminal::{self, Terminal};
 
 /// Timeout in seconds for resume failure toast notifications
@@ -58,6 +70,8 @@ struct ActiveSessionRef {
 pub(super) struct App {
     search_visible: bool,
     detail_visible: bool,
+    statistics_visible: bool,
+    bookmarks_visible: bool,
     pane_open: bool,
     pane_mode: UtilityPaneMode,
     active_session: Option<ActiveSessionRef>,
@@ -67,13 +81,19 @@ pub(super) struct App {
     #[allow(dead_code)] // Controller must stay alive to keep the widget
     sidebar: Controller<Sidebar>,
     detail_context_pane: Controller<DetailContextPane>,
+    statistics: Controller<StatisticsView>,
+    bookmarks: Controller<BookmarksView>,
     preferences_dialog: Controller<PreferencesDialog>,
     nav_view: adw::NavigationView,
     detail_page: adw::NavigationPage,
+    statistics_page: adw::NavigationPage,
+    bookmarks_page: adw::NavigationPage,
     pane_stack: gtk::Stack,
     toast_overlay: adw::ToastOverlay,
     db_path: PathBuf,
     sources: SessionSources,
+    /// The app's session bus connection, where the D-Bus API is served.
+    dbus_connection: Option<gio::DBusConnection>,
 }
 
 #[derive(Debug)]
@@ -84,12 +104,31 @@ pub(super) enum AppMsg {
     PaneVisibilityChanged(bool),
     SearchQueryChanged(String),
     FiltersChanged(Vec<Tool>),
+    DateRangeChanged(DateRange),
+    ShowStatistics,
+    ShowBookmarks,
     SessionSelected(String),
+    /// Open a session scrolled to one of its messages.
+    OpenMessage(String, usize),
+    /// Open a `sessions-chronicle://` permalink.
+    OpenUri(String),
     NavigateBack,
+    PagePopped(String),
     ResumeSession(String, Tool),
     ResumeFromPane,
+    ExportFromPane,
+    StarredOnlyChanged(bool),
+    StarToggled(String, bool),
+    StarFromPane(bool),
+    BookmarkToggled(String, usize, bool),
+    RenameRequested(String, String),
+    SessionRenamed(String, String),
+    TagSelected(Option<String>),
+    TagsEdited(Vec<String>),
+    NoteEdited(String),
     ShowPreferences,
     ReindexRequested,
+    ExclusionsChanged,
 }
 
 relm4::new_action_group!(pub(super) WindowActionGroup, "win");
@@ -99,14 +138,22 @@ relm4::new_stateless_action!(AboutAction, WindowActionGroup, "about");
 relm4::new_stateless_action!(QuitAction, WindowActionGroup, "quit");
 relm4::new_stateless_action!(TogglePaneAction, WindowActionGroup, "toggle-pane");
 relm4::new_stateless_action!(ShowSearchAction, WindowActionGroup, "show-search");
-
+relm4::new_stateless_action!(StatisticsAction, WindowActionGroup, "statistics");
+relm4::new_stateless_action!(BookmarksAction, WindowActionGroup, "bookmarks");
+relm4::new_stateful_action!(
+    pub(super) OpenUriAction,
+    WindowActionGroup,
+    "open-uri",
+    String,
+    ()
+);
+
+/// Full-text part of the search box, used to highlight matches.
+///
+/// Field filters such as `branch:` select sessions but never match message text.
 fn active_search_query(query: &str) -> Option<String> {
-    let trimmed = query.trim();
-    if trimmed.is_empty() {
-        None
-    } else {
-        Some(trimmed.to_string())
-    }
+    let text = SearchQuery::parse(query).text;
+    if text.is_empty() { None } else { Some(text) }
 }
 
 fn search_query_update_messages(query: String) -> (SessionListMsg, SessionDetailMsg) {
@@ -127,6 +174,10 @@ impl SimpleComponent for App {
 
     menu! {
         primary_menu: {
+            section! {
+                "_Statistics" => StatisticsAction,
+                "_Bookmarked Messages" => BookmarksAction,
+            },
             section! {
                 "_Preferences" => PreferencesAction,
                 "_Keyboard" => ShortcutsAction,
@@ -160,7 +211,7 @@ impl SimpleComponent for App {
                             set_icon_name: "go-previous-symbolic",
                             set_tooltip_text: Some("Go back"),
                             #[watch]
-                            set_visible: model.detail_visible,
+                            set_visible: model.detail_visible || model.statistics_visible || model.bookmarks_visible,
                             connect_clicked => AppMsg::NavigateBack,
                         },
 
@@ -248,59 +299,25 @@ impl SimpleComponent for App {
             };
 
             if let Some(ref mut idx) = indexer {
-                match idx.index_claude_sessions(&sources.claude_dir) {
-                    Ok(count) => {
-                        tracing::info!(
-                            "Indexed {} Claude sessions from {}",
-                            count,
-                            sources.claude_dir.display()
-                        );
-                    }
-                    Err(err) => {
-                        tracing::error!("Failed to index Claude sessions: {}", err);
-                    }
-                }
-
-                match idx.index_opencode_sessions(&sources.opencode_storage_root) {
-                    Ok(count) => {
-                        tracing::info!(
-                            "Indexed {} OpenCode sessions from {}",
-                            count,
-                            sources.opencode_storage_root.display()
-                        );
-                    }
-                    Err(err) => {
-                        tracing::error!("Failed to index OpenCode sessions: {}", err);
-                    }
-                }
-
-                match idx.index_codex_sessions(&sources.codex_dir) {
-                    Ok(count) => {
-                        tracing::info!(
-                            "Indexed {} Codex sessions from {}",
-                            count,
-                            sources.codex_dir.display()
-                        );
-                    }
-                    Err(err) => {
-                        tracing::error!("Failed to index Codex sessions: {}", err);
-                    }
-                }
+                let settings = gio::Settings::new(APP_ID);
+                idx.set_redactor(index_redactor(&settings));
+                idx.set_exclusions(exclusion_rules(&settings));
 
-                match idx.index_vibe_sessions(&sources.vibe_dir) {
-                    Ok(count) => {
-                        tracing::info!(
-                            "Indexed {} Mistral Vibe sessions from {}",
-                            count,
-                            sources.vibe_dir.display()
-                        );
-                    }
-                    Err(err) => {
-                        tracing::error!("Failed to index Mistral Vibe sessions: {}", err);
-                    }
-                }
+                idx.index_sources(&sources);
+            }
+        }
+        // Serve the D-Bus API on the app's own bus name
+        let dbus_connection = main_application().dbus_connection();
+        if let Some(connection) = &dbus_connection {
+            let resume_sender = sender.input_sender().clone();
+            let api = SessionsApi::new(db_path.clone(), move |session: &Session| {
+                resume_sender.emit(AppMsg::ResumeSession(session.id.clone(), session.tool));
+            });
+            if let Err(err) = api.register(connection) {
+                tracing::error!("{:#}", err);
             }
         }
+
         // Initialize child components
         let session_list =
             SessionList::builder()
@@ -308,25 +325,55 @@ impl SimpleComponent for App {
                 .forward(sender.input_sender(), |msg| match msg {
                     SessionListOutput::SessionSelected(id) => AppMsg::SessionSelected(id),
                     SessionListOutput::ResumeRequested(id, tool) => AppMsg::ResumeSession(id, tool),
+                    SessionListOutput::StarToggled(id, starred) => AppMsg::StarToggled(id, starred),
+                    SessionListOutput::RenameRequested(id, title) => {
+                        AppMsg::RenameRequested(id, title)
+                    }
                 });
-        let session_detail = SessionDetail::builder().launch(db_path.clone()).detach();
+        let session_detail = SessionDetail::builder().launch(db_path.clone()).forward(
+            sender.input_sender(),
+            |output| match output {
+                SessionDetailOutput::OpenSession(id) => AppMsg::SessionSelected(id),
+                SessionDetailOutput::BookmarkToggled(id, index, bookmarked) => {
+                    AppMsg::BookmarkToggled(id, index, bookmarked)
+                }
+            },
+        );
         let sidebar = Sidebar::builder()
             .launch(())
             .forward(sender.input_sender(), |output| match output {
                 SidebarOutput::FiltersChanged(tools) => AppMsg::FiltersChanged(tools),
+                SidebarOutput::DateRangeChanged(range) => AppMsg::DateRangeChanged(range),
+                SidebarOutput::ShowStarredOnly(starred_only) => {
+                    AppMsg::StarredOnlyChanged(starred_only)
+                }
+                SidebarOutput::TagSelected(tag) => AppMsg::TagSelected(tag),
             });
         let detail_context_pane =
             DetailContextPane::builder()
                 .launch(())
                 .forward(sender.input_sender(), |output| match output {
                     DetailContextPaneOutput::ResumeClicked => AppMsg::ResumeFromPane,
+                    DetailContextPaneOutput::ExportClicked => AppMsg::ExportFromPane,
+                    DetailContextPaneOutput::StarToggled(starred) => AppMsg::StarFromPane(starred),
+                    DetailContextPaneOutput::TagsChanged(tags) => AppMsg::TagsEdited(tags),
+                    DetailContextPaneOutput::NoteChanged(note) => AppMsg::NoteEdited(note),
                 });
+        let statistics = StatisticsView::builder().launch(db_path.clone()).detach();
+        let bookmarks = BookmarksView::builder().launch(db_path.clone()).forward(
+            sender.input_sender(),
+            |output| match output {
+                BookmarksOutput::OpenMessage(id, index) => AppMsg::OpenMessage(id, index),
+                BookmarksOutput::Unbookmark(id, index) => AppMsg::BookmarkToggled(id, index, false),
+            },
+        );
 
         // Create preferences dialog once, with forwarded outputs
         let preferences_dialog = PreferencesDialog::builder().launch(()).forward(
             sender.input_sender(),
             |msg| match msg {
                 PreferencesOutput::ReindexRequested => AppMsg::ReindexRequested,
+                PreferencesOutput::ExclusionsChanged => AppMsg::ExclusionsChanged,
             },
         );
 
@@ -347,11 +394,25 @@ impl SimpleComponent for App {
             .child(session_detail.widget())
             .build();
 
-        // Connect popped signal to reset detail_visible when user navigates back
+        // Create statistics page (pushed from the primary menu)
+        let statistics_page = adw::NavigationPage::builder()
+            .title("Statistics")
+            .tag("statistics")
+            .child(statistics.widget())
+            .build();
+
+        // Create bookmarks page (pushed from the primary menu)
+        let bookmarks_page = adw::NavigationPage::builder()
+            .title("Bookmarked Messages")
+            .tag("bookmarks")
+            .child(bookmarks.widget())
+            .build();
+
+        // Connect popped signal to reset visibility state when user navigates back
         let popped_sender = sender.input_sender().clone();
         nav_view.connect_popped(move |_, page| {
-            if page.tag().as_deref() == Some("detail") {
-                popped_sender.send(AppMsg::NavigateBack).ok();
+            if let Some(tag) = page.tag() {
+                popped_sender.send(AppMsg::PagePopped(tag.to_string())).ok();
             }
         });
 
@@ -366,6 +427,8 @@ impl SimpleComponent for App {
         let mut model = Self {
             search_visible: false,
             detail_visible: false,
+            statistics_visible: false,
+            bookmarks_visible: false,
             pane_open: true,
             pane_mode: UtilityPaneMode::Filters,
             active_session: None,
@@ -374,16 +437,23 @@ impl SimpleComponent for App {
             session_detail,
             sidebar,
             detail_context_pane,
+            statistics,
+            bookmarks,
             preferences_dialog,
             nav_view: nav_view.clone(),
             detail_page: detail_page.clone(),
+            statistics_page,
+            bookmarks_page,
             pane_stack,
             toast_overlay: adw::ToastOverlay::new(),
             db_path,
             sources,
+            dbus_connection,
         };
 
         let widgets = view_output!();
+        model.refresh_tags();
+        model.notify_sessions_changed();
 
         // Get the actual ToastOverlay from the root window's content
         model.toast_overlay = root
@@ -484,6 +554,38 @@ impl SimpleComponent for App {
             })
         };
 
+        let statistics_action = {
+            let sender = sender.clone();
+            RelmAction::<StatisticsAction>::new_stateless(move |_| {
+                sender.input(AppMsg::ShowStatistics);
+            })
+        };
+
+        let bookmarks_action = {
+            let sender = sender.clone();
+            RelmAction::<BookmarksAction>::new_stateless(move |_| {
+                sender.input(AppMsg::ShowBookmarks);
+            })
+        };
+
+        // Activated by `main` for URIs passed on the command line or by the
+        // desktop file's scheme handler. Search links fill the search bar.
+        let open_uri_action = {
+            let sender = sender.clone();
+            let search_bar = widgets.search_bar.clone();
+            let search_entry = widgets.search_entry.clone();
+            RelmAction::<OpenUriAction>::new_with_target_value(move |_, uri: String| {
+                match parse_search_uri(&uri) {
+                    Some(query) => {
+                        search_bar.set_search_mode(true);
+                        search_entry.set_text(&query);
+                        search_entry.set_position(-1);
+                    }
+                    None => sender.input(AppMsg::OpenUri(uri)),
+                }
+            })
+        };
+
         let quit_action = {
             RelmAction::<QuitAction>::new_stateless(move |_| {
                 sender.input(AppMsg::Quit);
@@ -496,12 +598,17 @@ impl SimpleComponent for App {
         app.set_accelerators_for_action::<ShowSearchAction>(&["<Control>f"]);
         app.set_accelerators_for_action::<ShortcutsAction>(&["<Control>question"]);
         app.set_accelerators_for_action::<PreferencesAction>(&["<Control>comma"]);
+        app.set_accelerators_for_action::<StatisticsAction>(&["<Control>i"]);
+        app.set_accelerators_for_action::<BookmarksAction>(&["<Control>b"]);
 
         actions.add_action(preferences_action);
         actions.add_action(shortcuts_action);
         actions.add_action(about_action);
         actions.add_action(show_search_action);
         actions.add_action(toggle_pane_action);
+        actions.add_action(statistics_action);
+        actions.add_action(bookmarks_action);
+        actions.add_action(open_uri_action);
         actions.add_action(quit_action);
         actions.register_for_widget(&widgets.main_window);
 
@@ -539,87 +646,55 @@ impl SimpleComponent for App {
                 self.session_detail.emit(detail_msg);
             }
             AppMsg::FiltersChanged(tools) => {
+                self.statistics.emit(StatisticsMsg::SetTools(tools.clone()));
                 self.session_list.emit(SessionListMsg::SetTools(tools));
             }
-            AppMsg::SessionSelected(id) => {
-                tracing::debug!("Session selected: {}", id);
-
-                let search_query = active_search_query(&self.search_query);
-
-                // Load session once, shared by context pane and detail view
-                match load_session(&self.db_path, &id) {
-                    Ok(Some(session)) => {
-                        let project_name = session
-                            .project_path
-                            .as_deref()
-                            .and_then(|p| std::path::Path::new(p).file_name())
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("Unknown project")
-                            .to_string();
-
-                        self.active_session = Some(ActiveSessionRef {
-                            id: session.id.clone(),
-                            tool: session.tool,
-                            project_name: project_name.clone(),
-                        });
-
-                        self.detail_context_pane
-                            .emit(DetailContextPaneMsg::SetSession {
-                                project_name,
-                                tool: session.tool,
-                            });
-
-                        self.session_detail.emit(SessionDetailMsg::SetSession {
-                            session,
-                            search_query,
-                        });
-                    }
-                    Ok(None) => {
-                        tracing::warn!("Session not found: {}", id);
-                        self.active_session = None;
-                        self.detail_context_pane
-                            .emit(DetailContextPaneMsg::ClearSession);
-                        self.session_detail.emit(SessionDetailMsg::Clear);
-                    }
-                    Err(err) => {
-                        tracing::error!("Failed to load session: {}", err);
-                        self.active_session = None;
-                        self.detail_context_pane
-                            .emit(DetailContextPaneMsg::ClearSession);
-                        self.session_detail.emit(SessionDetailMsg::Clear);
-                    }
-                }
-
-                // Push the detail page onto the navigation stack
-                if !self.detail_visible {
-                    self.nav_view.push(&self.detail_page);
-                    self.detail_visible = true;
-                }
-
-                // Switch to session context pane mode (open by default)
-                transition_to_detail(&mut self.pane_mode, &mut self.pane_open);
-                self.apply_pane_stack_switch();
+            AppMsg::DateRangeChanged(range) => {
+                self.statistics.emit(StatisticsMsg::SetDateRange(range));
+                self.session_list.emit(SessionListMsg::SetDateRange(range));
+            }
+            AppMsg::ShowStatistics => {
+                self.statistics.emit(StatisticsMsg::Refresh);
+                self.show_page(&self.statistics_page, self.statistics_visible);
+                self.statistics_visible = true;
+            }
+            AppMsg::ShowBookmarks => {
+                self.bookmarks.emit(BookmarksMsg::Refresh);
+                self.show_page(&self.bookmarks_page, self.bookmarks_visible);
+                self.bookmarks_visible = true;
+            }
+            AppMsg::SessionSelected(id) => self.open_session(&id, None),
+            AppMsg::OpenMessage(id, message_index) => {
+                self.open_session(&id, Some(message_index));
             }
+            AppMsg::OpenUri(uri) => match Permalink::parse(&uri) {
+                Some(link) => self.open_session(&link.session_id, link.message_index),
+                None => {
+                    tracing::warn!("Unsupported URI: {}", uri);
+                    self.toast_overlay.add_toast(
+                        adw::Toast::builder()
+                            .title("Unsupported link")
+                            .timeout(3)
+                            .build(),
+                    );
+                }
+            },
             AppMsg::NavigateBack => {
-                if self.detail_visible {
+                self.nav_view.pop();
+            }
+            AppMsg::PagePopped(tag) => match tag.as_str() {
+                "statistics" => self.statistics_visible = false,
+                "bookmarks" => self.bookmarks_visible = false,
+                "detail" => {
                     self.detail_visible = false;
-                    // Only pop if we're currently showing detail (avoid double-pop from signal)
-                    if self
-                        .nav_view
-                        .visible_page()
-                        .and_then(|p| p.tag())
-                        .as_deref()
-                        == Some("detail")
-                    {
-                        self.nav_view.pop();
-                    }
 
                     // Return to filter pane mode
                     self.active_session = None;
                     transition_to_list(&mut self.pane_mode);
                     self.apply_pane_stack_switch();
                 }
-            }
+                _ => {}
+            },
             AppMsg::ShowPreferences => {
                 let dialog_widget = self.preferences_dialog.widget();
                 dialog_widget.present(Some(&main_application().windows()[0]));
@@ -628,6 +703,10 @@ impl SimpleComponent for App {
                 tracing::info!("Reindex requested — clearing and rebuilding index");
                 match SessionIndexer::new(&self.db_path) {
                     Ok(mut indexer) => {
+                        let settings = gio::Settings::new(APP_ID);
+                        indexer.set_redactor(index_redactor(&settings));
+                        indexer.set_exclusions(exclusion_rules(&settings));
+
                         if let Err(err) = indexer.clear_all_sessions() {
                             tracing::error!("Failed to clear sessions: {}", err);
                             self.toast_overlay.add_toast(
@@ -639,26 +718,12 @@ impl SimpleComponent for App {
                             return;
                         }
 
-                        let mut total = 0usize;
-                        match indexer.index_claude_sessions(&self.sources.claude_dir) {
-                            Ok(n) => total += n,
-                            Err(err) => tracing::warn!("Claude sessions: {}", err),
-                        }
-                        match indexer.index_opencode_sessions(&self.sources.opencode_storage_root) {
-                            Ok(n) => total += n,
-                            Err(err) => tracing::warn!("OpenCode sessions: {}", err),
-                        }
-                        match indexer.index_codex_sessions(&self.sources.codex_dir) {
-                            Ok(n) => total += n,
-                            Err(err) => tracing::warn!("Codex sessions: {}", err),
-                        }
-                        match indexer.index_vibe_sessions(&self.sources.vibe_dir) {
-                            Ok(n) => total += n,
-                            Err(err) => tracing::warn!("Vibe sessions: {}", err),
-                        }
-
+                        let total = indexer.index_sources(&self.sources);
                         tracing::info!("Reindex complete: {} sessions indexed", total);
+                        self.notify_sessions_changed();
                         self.session_list.emit(SessionListMsg::Reload);
+                        self.statistics.emit(StatisticsMsg::Refresh);
+                        self.refresh_tags();
                         self.toast_overlay.add_toast(
                             adw::Toast::builder()
                                 .title(format!("Index rebuilt — {} sessions", total))
@@ -677,6 +742,38 @@ impl SimpleComponent for App {
                     }
                 }
             }
+            AppMsg::ExclusionsChanged => {
+                let purged = SessionIndexer::new(&self.db_path).and_then(|mut indexer| {
+                    indexer.set_exclusions(exclusion_rules(&gio::Settings::new(APP_ID)));
+                    indexer.purge_excluded_sessions()
+                });
+
+                match purged {
+                    Ok(0) => {}
+                    Ok(count) => {
+                        tracing::info!("Purged {} excluded sessions", count);
+                        self.notify_sessions_changed();
+                        self.session_list.emit(SessionListMsg::Reload);
+                        self.statistics.emit(StatisticsMsg::Refresh);
+                        self.refresh_tags();
+                        self.toast_overlay.add_toast(
+                            adw::Toast::builder()
+                                .title(format!("Removed {} excluded sessions", count))
+                                .timeout(3)
+                                .build(),
+                        );
+                    }
+                    Err(err) => {
+                        tracing::error!("Failed to purge excluded sessions: {}", err);
+                        self.toast_overlay.add_toast(
+                            adw::Toast::builder()
+                                .title("Failed to remove excluded sessions")
+                                .timeout(3)
+                                .build(),
+                        );
+                    }
+                }
+            }
             AppMsg::ResumeSession(session_id, tool) => {
                 tracing::debug!("Resume session requested: {}", session_id);
 
@@ -701,24 +798,15 @@ impl SimpleComponent for App {
                     }
                 };
 
-                // Determine workdir
-                let workdir = if let Some(project_path) = &session.project_path {
-                    PathBuf::from(project_path)
-                } else {
-                    // Use the directory containing the session file
-                    match PathBuf::from(&session.file_path).parent() {
-                        Some(dir) => dir.to_path_buf(),
-                        None => {
-                            tracing::error!(
-                                "Cannot determine workdir for session: no project_path and no valid parent directory"
-                            );
-                            self.show_error_dialog(
-                                "Invalid Session",
-                                "The session has no valid working directory.",
-                            );
-                            return;
-                        }
-                    }
+                let Some(workdir) = terminal::resume_workdir(&session) else {
+                    tracing::error!(
+                        "Cannot determine workdir for session: no project_path and no valid parent directory"
+                    );
+                    self.show_error_dialog(
+                        "Invalid Session",
+                        "The session has no valid working directory.",
+                    );
+                    return;
                 };
 
                 // Get terminal preference
@@ -738,22 +826,56 @@ impl SimpleComponent for App {
 
                 // Build and spawn resume command
                 match terminal::build_resume_command(tool, &session_id, &workdir) {
-                    Ok(args) => match terminal::spawn_terminal(terminal, &args) {
-                        Ok(_) => {
-                            tracing::info!(
-                                "Successfully launched terminal for session: {}",
-                                session_id
-                            );
-                        }
-                        Err(err) => {
-                            tracing::error!(
-                                "Failed to spawn terminal for session {}: {}",
+                    Ok(args) => {
+                        let multiplexer = resume_multiplexer(&settings);
+                        match multiplexer.open(&window_name(&session), &workdir, &args) {
+                            Ok(Some(used)) => {
+                                tracing::info!(
+                                    "Resumed session {} in {}",
+                                    session_id,
+                                    used.display_name()
+                                );
+                                self.toast_overlay.add_toast(adw::Toast::new(&format!(
+                                    "Session resumed in {}",
+                                    used.display_name()
+                                )));
+                                return;
+                            }
+                            Ok(None) => {}
+                            Err(err) => tracing::warn!(
+                                "Failed to resume session {} in {}, opening a terminal instead: {:#}",
                                 session_id,
+                                multiplexer.display_name(),
                                 err
-                            );
-                            self.show_resume_failure_toast(&err);
+                            ),
                         }
-                    },
+
+                        match spawn_resume_terminal(
+                            &settings,
+                            terminal,
+                            &TemplateContext {
+                                workdir: &workdir,
+                                session_id: &session_id,
+                                tool,
+                                resume_args: &args,
+                            },
+                        ) {
+                            Ok(_) => {
+                                tracing::info!(
+                                    "Successfully launched terminal for session: {}",
+                                    session_id
+                                );
+                            }
+                            Err(err) => {
+                                tracing::error!(
+                                    "Failed to spawn terminal for session {}: {}",
+                                    session_id,
+                                    err
+                                );
+                                self.show_resume_failure_toast(&err);
+                            }
+                        }
+                    }
                     Err(err) => {
                         tracing::error!(
                             "Failed to build resume command for session {}: {}",
@@ -774,6 +896,138 @@ impl SimpleComponent for App {
                     tracing::warn!("ResumeFromPane ignored — no active session");
                 }
             }
+            AppMsg::ExportFromPane => {
+                if let Some(ref session) = self.active_session {
+                    self.export_session(&session.id.clone());
+                } else {
+                    tracing::warn!("ExportFromPane ignored — no active session");
+                }
+            }
+            AppMsg::StarredOnlyChanged(starred_only) => {
+                self.session_list
+                    .emit(SessionListMsg::SetStarredOnly(starred_only));
+            }
+            AppMsg::StarToggled(id, starred) => {
+                // On failure, put the toggles back the way they were.
+                let starred = match set_session_starred(&self.db_path, &id, starred) {
+                    Ok(()) => starred,
+                    Err(err) => {
+                        tracing::error!("Failed to star session {}: {}", id, err);
+                        self.toast_overlay.add_toast(
+                            adw::Toast::builder()
+                                .title("Failed to save star")
+                                .timeout(3)
+                                .build(),
+                        );
+                        !starred
+                    }
+                };
+                if self
+                    .active_session
+                    .as_ref()
+                    .is_some_and(|session| session.id == id)
+                {
+                    self.detail_context_pane
+                        .emit(DetailContextPaneMsg::SetStarred(starred));
+                }
+                self.session_list
+                    .emit(SessionListMsg::SetStarred(id, starred));
+            }
+            AppMsg::BookmarkToggled(id, message_index, bookmarked) => {
+                // On failure, put the toggle back the way it was.
+                let bookmarked =
+                    match set_message_bookmarked(&self.db_path, &id, message_index, bookmarked) {
+                        Ok(()) => bookmarked,
+                        Err(err) => {
+                            tracing::error!(
+                                "Failed to bookmark message {}: {}",
+                                message_index,
+                                err
+                            );
+                            self.toast_overlay.add_toast(
+                                adw::Toast::builder()
+                                    .title("Failed to save bookmark")
+                                    .timeout(3)
+                                    .build(),
+                            );
+                            !bookmarked
+                        }
+                    };
+                if self
+                    .active_session
+                    .as_ref()
+                    .is_some_and(|session| session.id == id)
+                {
+                    self.session_detail.emit(SessionDetailMsg::SetBookmarked {
+                        message_index,
+                        bookmarked,
+                    });
+                }
+                self.bookmarks.emit(BookmarksMsg::Refresh);
+            }
+            AppMsg::StarFromPane(starred) => {
+                if let Some(ref session) = self.active_session {
+                    _sender.input(AppMsg::StarToggled(session.id.clone(), starred));
+                } else {
+                    tracing::warn!("StarFromPane ignored — no active session");
+                }
+            }
+            AppMsg::RenameRequested(id, title) => {
+                self.show_rename_dialog(&_sender, id, &title);
+            }
+            AppMsg::SessionRenamed(id, title) => {
+                match set_session_title(&self.db_path, &id, &title) {
+                    Ok(()) => self.session_list.emit(SessionListMsg::Reload),
+                    Err(err) => {
+                        tracing::error!("Failed to rename session {}: {}", id, err);
+                        self.toast_overlay.add_toast(
+                            adw::Toast::builder()
+                                .title("Failed to save title")
+                                .timeout(3)
+                                .build(),
+                        );
+                    }
+                }
+            }
+            AppMsg::TagSelected(tag) => {
+                self.session_list.emit(SessionListMsg::SetTagFilter(tag));
+            }
+            AppMsg::TagsEdited(tags) => {
+                let Some(ref session) = self.active_session else {
+                    tracing::warn!("TagsEdited ignored — no active session");
+                    return;
+                };
+                match set_session_tags(&self.db_path, &session.id, &tags) {
+                    Ok(()) => {
+                        self.refresh_tags();
+                        self.session_list.emit(SessionListMsg::Reload);
+                    }
+                    Err(err) => {
+                        tracing::error!("Failed to save tags: {}", err);
+                        self.toast_overlay.add_toast(
+                            adw::Toast::builder()
+                                .title("Failed to save tags")
+                                .timeout(3)
+                                .build(),
+                        );
+                    }
+                }
+            }
+            AppMsg::NoteEdited(note) => {
+                let Some(ref session) = self.active_session else {
+                    tracing::warn!("NoteEdited ignored — no active session");
+                    return;
+                };
+                let title = match set_session_note(&self.db_path, &session.id, &note) {
+                    Ok(()) => "Note saved",
+                    Err(err) => {
+                        tracing::error!("Failed to save note: {}", err);
+                        "Failed to save note"
+                    }
+                };
+                self.toast_overlay
+                    .add_toast(adw::Toast::builder().title(title).timeout(3).build());
+            }
         }
     }
 
@@ -782,6 +1036,21 @@ impl SimpleComponent for App {
     }
 }
 
+/// Open `terminal` on the resume command, or run the user's command
+/// template for a custom terminal.
+fn spawn_resume_terminal(
+    settings: &gio::Settings,
+    terminal: Terminal,
+    context: &TemplateContext,
+) -> Result<(), terminal::TerminalSpawnError> {
+    if terminal == Terminal::Custom {
+        let template = settings.string("resume-terminal-command");
+        terminal::spawn_custom_terminal(&template, context)
+    } else {
+        terminal::spawn_terminal(terminal, context.resume_args)
+    }
+}
+
 /// Pure transition: switch to detail mode (session context pane, open).
 fn transition_to_detail(pane_mode: &mut UtilityPaneMode, pane_open: &mut bool) {
     *pane_mode = UtilityPaneMode::SessionContext;
@@ -794,6 +1063,23 @@ fn transition_to_list(pane_mode: &mut UtilityPaneMode) {
 }
 
 impl App {
+    /// Emit the D-Bus API's `SessionsChanged` signal after indexing.
+    fn notify_sessions_changed(&self) {
+        if let Some(connection) = &self.dbus_connection
+            && let Err(err) = emit_sessions_changed(connection)
+        {
+            tracing::error!("{:#}", err);
+        }
+    }
+
+    /// Reload the sidebar's tag cloud.
+    fn refresh_tags(&self) {
+        match load_tag_counts(&self.db_path) {
+            Ok(tags) => self.sidebar.emit(SidebarMsg::SetTags(tags)),
+            Err(err) => tracing::error!("Failed to load tags: {}", err),
+        }
+    }
+
     /// Apply the current `pane_mode` to the Stack widget, with verification.
     fn apply_pane_stack_switch(&self) {
         let target = self.pane_mode.stack_child_name();
@@ -810,6 +1096,89 @@ impl App {
         }
     }
 
+    /// Load a session into the detail view and the context pane, scrolled
+    /// to `focus_message` when given.
+    fn open_session(&mut self, id: &str, focus_message: Option<usize>) {
+        tracing::debug!("Session selected: {}", id);
+
+        let search_query = active_search_query(&self.search_query);
+
+        // Load session once, shared by context pane and detail view
+        match load_session(&self.db_path, id) {
+            Ok(Some(session)) => {
+                let project_name = session
+                    .project_path
+                    .as_deref()
+                    .and_then(|p| std::path::Path::new(p).file_name())
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("Unknown project")
+                    .to_string();
+
+                self.active_session = Some(ActiveSessionRef {
+                    id: session.id.clone(),
+                    tool: session.tool,
+                    project_name: project_name.clone(),
+                });
+
+                let changed_files =
+                    load_changed_files(&self.db_path, &session.id).unwrap_or_else(|err| {
+                        tracing::error!("Failed to load changed files: {}", err);
+                        Vec::new()
+                    });
+
+                let note = load_session_note(&self.db_path, &session.id).unwrap_or_else(|err| {
+                    tracing::error!("Failed to load note: {}", err);
+                    None
+                });
+
+                self.detail_context_pane
+                    .emit(DetailContextPaneMsg::SetSession {
+                        project_name,
+                        session: Box::new(session.clone()),
+                        changed_files,
+                        note,
+                    });
+
+                self.session_detail.emit(SessionDetailMsg::SetSession {
+                    session: Box::new(session),
+                    search_query,
+                    focus_message,
+                });
+            }
+            Ok(None) => {
+                tracing::warn!("Session not found: {}", id);
+                self.active_session = None;
+                self.detail_context_pane
+                    .emit(DetailContextPaneMsg::ClearSession);
+                self.session_detail.emit(SessionDetailMsg::Clear);
+            }
+            Err(err) => {
+                tracing::error!("Failed to load session: {}", err);
+                self.active_session = None;
+                self.detail_context_pane
+                    .emit(DetailContextPaneMsg::ClearSession);
+                self.session_detail.emit(SessionDetailMsg::Clear);
+            }
+        }
+
+        // Show the detail page, back from any page pushed over it
+        self.show_page(&self.detail_page, self.detail_visible);
+        self.detail_visible = true;
+
+        // Switch to session context pane mode (open by default)
+        transition_to_detail(&mut self.pane_mode, &mut self.pane_open);
+        self.apply_pane_stack_switch();
+    }
+
+    /// Push `page`, or go back to it when it is already in the stack.
+    fn show_page(&self, page: &adw::NavigationPage, in_stack: bool) {
+        if in_stack {
+            self.nav_view.pop_to_page(page);
+        } else {
+            self.nav_view.push(page);
+        }
+    }
+
     fn show_error_dialog(&self, title: &str, message: &str) {
         let dialog = adw::AlertDialog::builder()
             .heading(title)
@@ -822,6 +1191,100 @@ impl App {
         dialog.present(Some(&relm4::main_application().windows()[0]));
     }
 
+    /// Ask for a new title; an empty one restores the tool's or derived title.
+    fn show_rename_dialog(&self, sender: &ComponentSender<Self>, id: String, title: &str) {
+        let entry = gtk::Entry::builder()
+            .text(title)
+            .placeholder_text("Session title")
+            .activates_default(true)
+            .build();
+
+        let dialog = adw::AlertDialog::builder()
+            .heading("Rename Session")
+            .body("Leave empty to restore the original title.")
+            .extra_child(&entry)
+            .build();
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("rename", "Rename");
+        dialog.set_response_appearance("rename", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("rename"));
+        dialog.set_close_response("cancel");
+
+        let input_sender = sender.input_sender().clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "rename" {
+                input_sender
+                    .send(AppMsg::SessionRenamed(id.clone(), entry.text().to_string()))
+                    .ok();
+            }
+        });
+
+        dialog.present(Some(&relm4::main_application().windows()[0]));
+    }
+
+    /// Ask for a destination and write a redacted Markdown transcript there.
+    fn export_session(&self, session_id: &str) {
+        let session = match load_session(&self.db_path, session_id) {
+            Ok(Some(session)) => session,
+            Ok(None) => {
+                tracing::error!("Session not found: {}", session_id);
+                self.show_error_dialog(
+                    "Session Not Found",
+                    "The requested session could not be found in the database.",
+                );
+                return;
+            }
+            Err(err) => {
+                tracing::error!("Failed to load session {}: {}", session_id, err);
+                self.show_error_dialog(
+                    "Failed to Load Session",
+                    &format!("An error occurred while loading the session: {}", err),
+                );
+                return;
+            }
+        };
+
+        let messages = match load_messages_for_session(&self.db_path, session_id) {
+            Ok(messages) => messages,
+            Err(err) => {
+                tracing::error!("Failed to load messages for {}: {}", session_id, err);
+                self.show_error_dialog(
+                    "Failed to Load Session",
+                    &format!("An error occurred while loading the messages: {}", err),
+                );
+                return;
+            }
+        };
+
+        let redactor = custom_redactor(&gio::Settings::new(APP_ID));
+        let markdown = export::session_to_markdown(&session, &messages, &redactor);
+
+        let dialog = gtk::FileDialog::builder()
+            .title("Export Session")
+            .initial_name(export::export_file_name(&session))
+            .build();
+
+        let toast_overlay = self.toast_overlay.clone();
+        let window = main_application().windows().into_iter().next();
+        dialog.save(window.as_ref(), gio::Cancellable::NONE, move |result| {
+            let Some(path) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+
+            let title = match fs::write(&path, markdown) {
+                Ok(()) => {
+                    tracing::info!("Exported session to {}", path.display());
+                    "Session exported".to_string()
+                }
+                Err(err) => {
+                    tracing::error!("Failed to export session to {}: {}", path.display(), err);
+                    format!("Export failed: {}", err)
+                }
+            };
+            toast_overlay.add_toast(adw::Toast::builder().title(title).timeout(3).build());
+        });
+    }
+
     fn show_resume_failure_toast(&self, error: &terminal::TerminalSpawnError) {
         let toast = adw::Toast::builder()
             .title(error.to_string())
@@ -898,6 +1361,11 @@ mod tests {
             active_search_query("  needle  "),
             Some("needle".to_string())
         );
+        assert_eq!(active_search_query("branch:feature-x"), None);
+        assert_eq!(
+            active_search_query("needle branch:main"),
+            Some("needle".to_string())
+        );
     }
 
     #[test]
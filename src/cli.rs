@@ -0,0 +1,130 @@
+//! Command-line queries against the session index, for editor integrations
+//! and scripts, the terminal interface, and the search provider service.
+//! They use the index the app maintains and never start GTK.
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use std::path::{Path, PathBuf};
+
+use crate::config::APP_ID;
+use crate::database::{load_session, sessions_touching_file};
+use crate::models::FileActivity;
+use crate::models::file_access::lookup_path;
+use crate::session_sources::SessionSources;
+use crate::settings::terminal_resume_multiplexer;
+use crate::utils::terminal::{resume_from_terminal, run_in_current_terminal};
+use crate::{mcp, search_provider, tui};
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// List sessions whose tool calls touched FILE, most recent first
+    Touching {
+        /// Path of the file; a path that does not exist below the working
+        /// directory matches indexed paths ending with it
+        file: PathBuf,
+
+        /// Print one JSON object per line instead of tab-separated fields
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Serve the Model Context Protocol on stdin and stdout, giving coding
+    /// agents read access to the session history
+    Mcp,
+
+    /// Browse, search and resume sessions in the terminal, for machines
+    /// where the app cannot run
+    Tui,
+
+    /// Resume a session in a new tmux window or zellij tab when a server is
+    /// running, else in this terminal. With the app's settings installed,
+    /// only the multiplexer chosen in its preferences is used, and none by
+    /// default
+    Resume {
+        /// Id of the session, as printed by `touching`
+        session_id: String,
+    },
+
+    /// Serve the GNOME Shell search provider on the session bus; started by
+    /// D-Bus activation
+    #[command(hide = true)]
+    SearchProvider,
+}
+
+pub fn run(command: Command, db_path: &Path, sessions_dir: Option<&Path>) -> Result<()> {
+    match command {
+        Command::Touching { file, json } => {
+            let path = lookup_path(&file);
+            for activity in sessions_touching_file(db_path, &path)? {
+                if json {
+                    println!("{}", activity_json(&activity));
+                } else {
+                    println!("{}", activity_line(&activity));
+                }
+            }
+            Ok(())
+        }
+        Command::Mcp => mcp::serve(db_path, std::io::stdin().lock(), std::io::stdout().lock()),
+        Command::Tui => tui::run(db_path, &SessionSources::resolve(sessions_dir)),
+        Command::Resume { session_id } => {
+            let session = load_session(db_path, &session_id)?
+                .with_context(|| format!("No session with id {}", session_id))?;
+            if let Some(multiplexer) = resume_from_terminal(
+                &session,
+                terminal_resume_multiplexer(),
+                run_in_current_terminal,
+            )? {
+                println!("Resumed in a new {} window", multiplexer.display_name());
+            }
+            Ok(())
+        }
+        Command::SearchProvider => {
+            let bus_name = format!("{}.SearchProvider", APP_ID);
+            search_provider::serve(&bus_name, db_path.to_path_buf())
+        }
+    }
+}
+
+fn operations(activity: &FileActivity) -> Vec<&'static str> {
+    activity
+        .operations
+        .iter()
+        .map(|operation| operation.to_storage())
+        .collect()
+}
+
+/// `time  tool  session id  operations  path  session file  first prompt`,
+/// tab-separated.
+fn activity_line(activity: &FileActivity) -> String {
+    let session = &activity.session;
+    let prompt = session
+        .first_prompt
+        .as_deref()
+        .and_then(|prompt| prompt.lines().next())
+        .unwrap_or_default();
+    [
+        activity.last_touched.to_rfc3339(),
+        session.tool.to_storage().to_string(),
+        session.id.clone(),
+        operations(activity).join(","),
+        activity.path.clone(),
+        session.file_path.clone(),
+        prompt.replace('\t', " "),
+    ]
+    .join("\t")
+}
+
+fn activity_json(activity: &FileActivity) -> serde_json::Value {
+    let session = &activity.session;
+    serde_json::json!({
+        "session_id": session.id,
+        "tool": session.tool.to_storage(),
+        "project_path": session.project_path,
+        "session_file": session.file_path,
+        "title": session.display_title(),
+        "first_prompt": session.first_prompt,
+        "path": activity.path,
+        "operations": operations(activity),
+        "last_touched": activity.last_touched.to_rfc3339(),
+    })
+}
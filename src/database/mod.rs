@@ -1,15 +1,36 @@
 pub mod indexer;
+pub mod query;
 pub mod schema;
+pub mod stats;
 
 use anyhow::{Context, Result};
 use chrono::{TimeZone, Utc};
-use rusqlite::{Connection, Row, ToSql};
+use rusqlite::{Connection, OptionalExtension, Row, ToSql};
 use std::collections::HashSet;
 use std::path::Path;
 
-use crate::models::{MessagePreview, Role, Session, Tool};
+use crate::models::file_change::changed_files;
+use crate::models::{
+    ChangedFile, Continuation, ConversationBranch, FileActivity, FileOperation, GitInfo, Message,
+    MessageBookmark, MessagePreview, Role, Session, SessionUsage, TokenUsage, Tool, ToolCall,
+};
 
 pub use indexer::SessionIndexer;
+pub use query::SearchQuery;
+
+/// Columns read by `session_from_row`, in order.
+const SESSION_COLUMNS: &str =
+    "id, tool, project_path, start_time, message_count, file_path, last_updated, first_prompt,
+     models, input_tokens, output_tokens, cache_read_tokens, cache_write_tokens, cost_usd,
+     cost_is_estimate, git_branch, git_commit, git_remote, parent_session_id, leaf_uuid,
+     previous_session_id, title,
+     EXISTS (SELECT 1 FROM starred_sessions WHERE starred_sessions.session_id = id),
+     (SELECT group_concat(tag, ' ') FROM session_tags WHERE session_tags.session_id = id),
+     (SELECT title FROM session_titles WHERE session_titles.session_id = id)";
+
+/// Number of columns in `SESSION_COLUMNS`; queries that select extra columns
+/// after them read those from this index on.
+const SESSION_COLUMN_COUNT: usize = 25;
 
 fn session_from_row(row: &Row) -> rusqlite::Result<Session> {
     let tool_value: String = row.get(1)?;
@@ -33,6 +54,58 @@ fn session_from_row(row: &Row) -> rusqlite::Result<Session> {
             .single()
             .unwrap_or_else(Utc::now),
         first_prompt: row.get(7)?,
+        usage: usage_from_row(row)?,
+        git: GitInfo {
+            branch: row.get(15)?,
+            commit: row.get(16)?,
+            remote: row.get(17)?,
+        },
+        parent_session_id: row.get(18)?,
+        continuation: Continuation {
+            leaf_uuid: row.get(19)?,
+            continued_leaves: Vec::new(),
+            previous_session_id: row.get(20)?,
+        },
+        starred: row.get(22)?,
+        tags: {
+            let tags: Option<String> = row.get(23)?;
+            let mut tags: Vec<String> = tags
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(str::to_string)
+                .collect();
+            tags.sort();
+            tags
+        },
+        title: row.get(21)?,
+        custom_title: row.get(24)?,
+    })
+}
+
+fn usage_from_row(row: &Row) -> rusqlite::Result<SessionUsage> {
+    let models: Option<String> = row.get(8)?;
+    let count = |idx: usize| -> rusqlite::Result<u64> { Ok(row.get::<_, i64>(idx)?.max(0) as u64) };
+
+    Ok(SessionUsage {
+        models: models
+            .as_deref()
+            .map(|models| {
+                models
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|model| !model.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        tokens: TokenUsage {
+            input: count(9)?,
+            output: count(10)?,
+            cache_read: count(11)?,
+            cache_write: count(12)?,
+        },
+        cost_usd: row.get(13)?,
+        cost_is_estimate: row.get(14)?,
     })
 }
 
@@ -59,6 +132,48 @@ fn sanitize_search_query(raw: &str) -> Option<String> {
     }
 }
 
+/// `WHERE` conditions and parameters restricting sessions to `tools` and the
+/// filters of `query`. Subagent sessions are listed inside their parent,
+/// never on their own.
+fn session_conditions(tools: &[Tool], query: &SearchQuery) -> (Vec<String>, Vec<String>) {
+    let mut conditions = vec!["parent_session_id IS NULL".to_string()];
+    let mut params = Vec::new();
+
+    if tools.len() != Tool::ALL.len() {
+        let placeholders: Vec<&str> = tools.iter().map(|_| "?").collect();
+        conditions.push(format!("tool IN ({})", placeholders.join(",")));
+        params.extend(tools.iter().map(|t| t.to_storage()));
+    }
+
+    if let Some(branch) = &query.branch {
+        conditions.push(query::filter_condition("git_branch", branch));
+        params.push(branch.clone());
+    }
+
+    if let Some(tag) = &query.tag {
+        conditions.push(format!(
+            "id IN (SELECT session_id FROM session_tags WHERE {})",
+            query::filter_condition("tag", tag)
+        ));
+        params.push(tag.clone());
+    }
+
+    // Files touched by a subagent count for its parent.
+    if let Some(file) = &query.file {
+        let (condition, file_params) = query::path_condition("f.path", file);
+        conditions.push(format!(
+            "id IN (SELECT COALESCE(c.parent_session_id, c.id)
+                    FROM session_files f
+                    JOIN sessions c ON c.id = f.session_id
+                    WHERE {})",
+            condition
+        ));
+        params.extend(file_params);
+    }
+
+    (conditions, params)
+}
+
 pub fn search_sessions(db_path: &Path, tools: &[Tool], query: &str) -> Result<Vec<Session>> {
     if !db_path.exists() {
         return Ok(Vec::new());
@@ -68,24 +183,28 @@ pub fn search_sessions(db_path: &Path, tools: &[Tool], query: &str) -> Result<Ve
         return Ok(Vec::new());
     }
 
-    let query = query.trim();
-    if query.is_empty() {
-        return load_sessions(db_path, tools);
-    }
-
+    let query = SearchQuery::parse(query);
     let db = Connection::open(db_path).context("Failed to open database")?;
 
-    match search_sessions_with_query(&db, tools, query) {
+    if query.text.is_empty() {
+        return load_sessions_with_query(&db, tools, &query);
+    }
+
+    match search_sessions_with_query(&db, tools, &query) {
         Ok(sessions) => Ok(sessions),
         Err(err) => {
-            let sanitized = sanitize_search_query(query);
+            let sanitized = sanitize_search_query(&query.text);
             if let Some(sanitized) = sanitized {
                 tracing::warn!(
                     "Search query failed, retrying with sanitized query '{}': {}",
                     sanitized,
                     err
                 );
-                match search_sessions_with_query(&db, tools, &sanitized) {
+                let sanitized_query = SearchQuery {
+                    text: sanitized.clone(),
+                    ..query
+                };
+                match search_sessions_with_query(&db, tools, &sanitized_query) {
                     Ok(sessions) => Ok(sessions),
                     Err(retry_err) => {
                         tracing::warn!(
@@ -107,42 +226,48 @@ pub fn search_sessions(db_path: &Path, tools: &[Tool], query: &str) -> Result<Ve
 fn search_sessions_with_query(
     db: &Connection,
     tools: &[Tool],
-    query: &str,
+    query: &SearchQuery,
 ) -> Result<Vec<Session>> {
-    let (query_sql, tool_strings): (String, Vec<String>) = if tools.len() == Tool::ALL.len() {
-        (
-            "SELECT s.id, s.tool, s.project_path, s.start_time, s.message_count, s.file_path, s.last_updated, s.first_prompt,
-                    bm25(messages) AS rank
+    let (conditions, filter_params) = session_conditions(tools, query);
+    // Reasoning and tool traffic are searched only on request, so they do not
+    // outrank the conversation itself.
+    let mut roles = vec!["'user'", "'assistant'"];
+    if query.include_thinking {
+        roles.push("'thinking'");
+    }
+    if query.include_tools {
+        roles.extend(["'toolcall'", "'toolresult'"]);
+    }
+    let roles = format!("role IN ({})", roles.join(", "));
+
+    // The user's notes are searched along with the messages. A match in a
+    // subagent's messages finds its parent session.
+    let query_sql = format!(
+        "SELECT {SESSION_COLUMNS},
+                hits.rank
+         FROM (
+             SELECT session_id, bm25(messages) AS rank
              FROM messages
-             JOIN sessions s ON s.id = messages.session_id
-             WHERE messages MATCH ?1
-             ORDER BY rank ASC, s.last_updated DESC"
-                .to_string(),
-            vec![],
-        )
-    } else {
-        let placeholders: Vec<String> = tools.iter().map(|_| "?".to_string()).collect();
-        let tool_strings: Vec<String> = tools.iter().map(|t| t.to_storage()).collect::<Vec<_>>();
-        (
-            format!(
-                "SELECT s.id, s.tool, s.project_path, s.start_time, s.message_count, s.file_path, s.last_updated, s.first_prompt,
-                        bm25(messages) AS rank
-                 FROM messages
-                 JOIN sessions s ON s.id = messages.session_id
-                 WHERE messages MATCH ?1
-                   AND s.tool IN ({})
-                 ORDER BY rank ASC, s.last_updated DESC",
-                placeholders.join(",")
-            ),
-            tool_strings,
-        )
-    };
+             WHERE messages MATCH ?1 AND {roles}
+             UNION ALL
+             SELECT session_id, bm25(session_notes) AS rank
+             FROM session_notes
+             WHERE session_notes MATCH ?1
+         ) hits
+         JOIN sessions s ON s.id = (
+             SELECT COALESCE(parent_session_id, id) FROM sessions WHERE id = hits.session_id
+         )
+         WHERE {}
+         ORDER BY hits.rank ASC, s.last_updated DESC",
+        conditions.join(" AND ")
+    );
 
     let mut stmt = db.prepare(&query_sql)?;
-    let mut params: Vec<&dyn ToSql> = Vec::with_capacity(1 + tool_strings.len());
-    params.push(&query);
-    for tool in &tool_strings {
-        params.push(tool as &dyn ToSql);
+    // `?1` is the search text; the filters' `?` placeholders follow it.
+    let mut params: Vec<&dyn ToSql> = Vec::with_capacity(1 + filter_params.len());
+    params.push(&query.text);
+    for param in &filter_params {
+        params.push(param as &dyn ToSql);
     }
 
     let mut rows = stmt
@@ -171,36 +296,35 @@ pub fn load_sessions(db_path: &Path, tools: &[Tool]) -> Result<Vec<Session>> {
     }
 
     let db = Connection::open(db_path).context("Failed to open database")?;
+    load_sessions_with_query(&db, tools, &SearchQuery::default())
+}
 
-    let (query, tool_strings): (String, Vec<String>) = if tools.len() == Tool::ALL.len() {
-        (
-            "SELECT id, tool, project_path, start_time, message_count, file_path, last_updated, first_prompt
-             FROM sessions
-             ORDER BY last_updated DESC"
-                .to_string(),
-            vec![],
-        )
+/// Sessions matching `tools` and the filters of `query`, ignoring its text.
+fn load_sessions_with_query(
+    db: &Connection,
+    tools: &[Tool],
+    query: &SearchQuery,
+) -> Result<Vec<Session>> {
+    let (conditions, params) = session_conditions(tools, query);
+    let where_clause = if conditions.is_empty() {
+        String::new()
     } else {
-        let placeholders: Vec<String> = tools.iter().map(|_| "?".to_string()).collect();
-        let tool_strings: Vec<String> = tools.iter().map(|t| t.to_storage()).collect::<Vec<_>>();
-        (
-            format!(
-                "SELECT id, tool, project_path, start_time, message_count, file_path, last_updated, first_prompt
-                 FROM sessions
-                 WHERE tool IN ({})
-                 ORDER BY last_updated DESC",
-                placeholders.join(",")
-            ),
-            tool_strings,
-        )
+        format!("WHERE {}", conditions.join(" AND "))
     };
 
+    let query = format!(
+        "SELECT {SESSION_COLUMNS}
+         FROM sessions
+         {where_clause}
+         ORDER BY last_updated DESC"
+    );
+
     let mut stmt = db.prepare(&query)?;
 
-    let tool_refs: Vec<&dyn ToSql> = tool_strings.iter().map(|s| s as &dyn ToSql).collect();
+    let param_refs: Vec<&dyn ToSql> = params.iter().map(|s| s as &dyn ToSql).collect();
 
     let sessions = stmt
-        .query_map(tool_refs.as_slice(), session_from_row)
+        .query_map(param_refs.as_slice(), session_from_row)
         .context("Failed to query sessions")?
         .collect::<Result<Vec<_>, _>>()
         .context("Failed to load sessions")?;
@@ -217,11 +341,11 @@ pub fn load_session(db_path: &Path, session_id: &str) -> Result<Option<Session>>
 
     let db = Connection::open(db_path).context("Failed to open database")?;
 
-    let mut stmt = db.prepare(
-        "SELECT id, tool, project_path, start_time, message_count, file_path, last_updated, first_prompt
+    let mut stmt = db.prepare(&format!(
+        "SELECT {SESSION_COLUMNS}
          FROM sessions
-         WHERE id = ?1",
-    )?;
+         WHERE id = ?1"
+    ))?;
 
     let mut rows = stmt
         .query([session_id])
@@ -237,7 +361,290 @@ pub fn load_session(db_path: &Path, session_id: &str) -> Result<Option<Session>>
     result
 }
 
+/// Star or unstar a session.
+pub fn set_session_starred(db_path: &Path, session_id: &str, starred: bool) -> Result<()> {
+    let db = Connection::open(db_path).context("Failed to open database")?;
+    if starred {
+        db.execute(
+            "INSERT OR IGNORE INTO starred_sessions (session_id, starred_at) VALUES (?1, ?2)",
+            rusqlite::params![session_id, Utc::now().timestamp()],
+        )
+    } else {
+        db.execute(
+            "DELETE FROM starred_sessions WHERE session_id = ?1",
+            [session_id],
+        )
+    }
+    .context("Failed to update starred sessions")?;
+    Ok(())
+}
+
+/// Replace the user tags of a session.
+pub fn set_session_tags(db_path: &Path, session_id: &str, tags: &[String]) -> Result<()> {
+    let mut db = Connection::open(db_path).context("Failed to open database")?;
+    let tx = db.transaction()?;
+    tx.execute(
+        "DELETE FROM session_tags WHERE session_id = ?1",
+        [session_id],
+    )?;
+    for tag in tags {
+        tx.execute(
+            "INSERT OR IGNORE INTO session_tags (session_id, tag) VALUES (?1, ?2)",
+            [session_id, tag],
+        )?;
+    }
+    tx.commit().context("Failed to save tags")?;
+    Ok(())
+}
+
+/// Every tag with the number of indexed sessions carrying it, most used
+/// first.
+pub fn load_tag_counts(db_path: &Path) -> Result<Vec<(String, usize)>> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+    let mut stmt = db.prepare(
+        "SELECT t.tag, COUNT(*) AS uses
+         FROM session_tags t
+         JOIN sessions s ON s.id = t.session_id
+         GROUP BY t.tag
+         ORDER BY uses DESC, t.tag ASC",
+    )?;
+    let counts = stmt
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get::<_, i64>(1)?.max(0) as usize))
+        })
+        .context("Failed to query tags")?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to load tags")?;
+    Ok(counts)
+}
+
+/// The user's markdown note on a session, if any.
+pub fn load_session_note(db_path: &Path, session_id: &str) -> Result<Option<String>> {
+    if !db_path.exists() {
+        return Ok(None);
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+    db.query_row(
+        "SELECT note FROM session_notes WHERE session_id = ?1",
+        [session_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .context("Failed to load note")
+}
+
+/// Save the note on a session; a blank note removes it.
+pub fn set_session_note(db_path: &Path, session_id: &str, note: &str) -> Result<()> {
+    let mut db = Connection::open(db_path).context("Failed to open database")?;
+    let tx = db.transaction()?;
+    tx.execute(
+        "DELETE FROM session_notes WHERE session_id = ?1",
+        [session_id],
+    )?;
+    if !note.trim().is_empty() {
+        tx.execute(
+            "INSERT INTO session_notes (session_id, note, updated_at) VALUES (?1, ?2, ?3)",
+            rusqlite::params![session_id, note, Utc::now().timestamp()],
+        )?;
+    }
+    tx.commit().context("Failed to save note")?;
+    Ok(())
+}
+
+/// Rename a session; a blank title restores the tool's or derived one.
+pub fn set_session_title(db_path: &Path, session_id: &str, title: &str) -> Result<()> {
+    let db = Connection::open(db_path).context("Failed to open database")?;
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    if title.is_empty() {
+        db.execute(
+            "DELETE FROM session_titles WHERE session_id = ?1",
+            [session_id],
+        )
+    } else {
+        db.execute(
+            "INSERT OR REPLACE INTO session_titles (session_id, title, updated_at)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![session_id, title, Utc::now().timestamp()],
+        )
+    }
+    .context("Failed to save title")?;
+    Ok(())
+}
+
+/// Bookmark or unbookmark one message.
+pub fn set_message_bookmarked(
+    db_path: &Path,
+    session_id: &str,
+    message_index: usize,
+    bookmarked: bool,
+) -> Result<()> {
+    let db = Connection::open(db_path).context("Failed to open database")?;
+    if bookmarked {
+        db.execute(
+            "INSERT OR IGNORE INTO message_bookmarks (session_id, message_index, created_at)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![session_id, message_index as i64, Utc::now().timestamp()],
+        )
+    } else {
+        db.execute(
+            "DELETE FROM message_bookmarks WHERE session_id = ?1 AND message_index = ?2",
+            rusqlite::params![session_id, message_index as i64],
+        )
+    }
+    .context("Failed to update bookmarks")?;
+    Ok(())
+}
+
+/// Indexes of the bookmarked messages of a session.
+pub fn load_bookmarked_indexes(db_path: &Path, session_id: &str) -> Result<HashSet<usize>> {
+    if !db_path.exists() {
+        return Ok(HashSet::new());
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+    let mut stmt =
+        db.prepare("SELECT message_index FROM message_bookmarks WHERE session_id = ?1")?;
+    let indexes = stmt
+        .query_map(
+            [session_id],
+            |row| Ok(row.get::<_, i64>(0)?.max(0) as usize),
+        )
+        .context("Failed to query bookmarks")?
+        .collect::<Result<HashSet<_>, _>>()
+        .context("Failed to load bookmarks")?;
+    Ok(indexes)
+}
+
+/// Bookmarked messages of indexed sessions, most recently bookmarked first,
+/// with their content cut to `preview_len` characters.
+pub fn load_bookmarks(db_path: &Path, preview_len: usize) -> Result<Vec<MessageBookmark>> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+    let mut stmt = db.prepare(&format!(
+        "SELECT {SESSION_COLUMNS},
+                b.message_index, m.role, substr(m.content, 1, ?1), length(m.content),
+                m.timestamp, b.created_at
+         FROM message_bookmarks b
+         JOIN sessions s ON s.id = b.session_id
+         JOIN messages m ON m.session_id = b.session_id
+              AND CAST(m.message_index AS INTEGER) = b.message_index
+         ORDER BY b.created_at DESC, b.message_index ASC"
+    ))?;
+
+    let bookmarks = stmt
+        .query_map([preview_len as i64], |row| {
+            let role: String = row.get(SESSION_COLUMN_COUNT + 1)?;
+            let timestamp: i64 = row.get(SESSION_COLUMN_COUNT + 4)?;
+            let bookmarked_at: i64 = row.get(SESSION_COLUMN_COUNT + 5)?;
+            Ok(MessageBookmark {
+                session: session_from_row(row)?,
+                message: MessagePreview {
+                    index: row.get::<_, i64>(SESSION_COLUMN_COUNT)?.max(0) as usize,
+                    role: Role::from_storage(&role).unwrap_or(Role::User),
+                    content_preview: row.get(SESSION_COLUMN_COUNT + 2)?,
+                    content_len: row.get::<_, i64>(SESSION_COLUMN_COUNT + 3)?.max(0) as usize,
+                    timestamp: Utc
+                        .timestamp_opt(timestamp, 0)
+                        .single()
+                        .unwrap_or_else(Utc::now),
+                },
+                bookmarked_at: Utc
+                    .timestamp_opt(bookmarked_at, 0)
+                    .single()
+                    .unwrap_or_else(Utc::now),
+            })
+        })
+        .context("Failed to query bookmarks")?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to load bookmarks")?;
+
+    Ok(bookmarks)
+}
+
+/// Subagent sessions spawned by `session_id`, oldest first.
+pub fn load_child_sessions(db_path: &Path, session_id: &str) -> Result<Vec<Session>> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+
+    let mut stmt = db.prepare(&format!(
+        "SELECT {SESSION_COLUMNS}
+         FROM sessions
+         WHERE parent_session_id = ?1
+         ORDER BY start_time ASC"
+    ))?;
+
+    let sessions = stmt
+        .query_map([session_id], session_from_row)
+        .context("Failed to query child sessions")?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to load child sessions")?;
+
+    Ok(sessions)
+}
+
+/// Every part of the continued conversation `session_id` belongs to, first
+/// part first. A session that was never continued is its own only part.
+pub fn load_continuation_chain(db_path: &Path, session_id: &str) -> Result<Vec<Session>> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+    let load = |condition: &str, id: &str| -> Result<Option<Session>> {
+        db.query_row(
+            &format!(
+                "SELECT {SESSION_COLUMNS}
+                 FROM sessions
+                 WHERE {condition}
+                 ORDER BY start_time ASC
+                 LIMIT 1"
+            ),
+            [id],
+            session_from_row,
+        )
+        .optional()
+        .context("Failed to load continuation")
+    };
+
+    let Some(session) = load("id = ?1", session_id)? else {
+        return Ok(Vec::new());
+    };
+    // Links are resolved from file contents, so guard against cycles.
+    let mut seen = HashSet::from([session.id.clone()]);
+    let mut chain = vec![session];
+
+    while let Some(previous_id) = chain[0].continuation.previous_session_id.clone()
+        && let Some(previous) = load("id = ?1", &previous_id)?
+        && seen.insert(previous.id.clone())
+    {
+        chain.insert(0, previous);
+    }
+    while let Some(next) = load("previous_session_id = ?1", &chain[chain.len() - 1].id)?
+        && seen.insert(next.id.clone())
+    {
+        chain.push(next);
+    }
+
+    Ok(chain)
+}
+
 /// Load message previews for a session with pagination and truncation.
+///
+/// A long tool call with a JSON object input keeps its header and every
+/// input field, as compact JSON with long values cut to the preview length,
+/// so its card summary still names the command or file. The whole input is
+/// loaded when the card is expanded.
 pub fn load_message_previews_for_session(
     db_path: &Path,
     session_id: &str,
@@ -255,11 +662,30 @@ pub fn load_message_previews_for_session(
     let mut stmt = db.prepare(
         "SELECT
           role,
-          substr(content, 1, ?2) AS content_preview,
+          CASE
+            WHEN role = 'toolcall' AND length(content) > ?2 AND json_valid(input)
+                 AND json_type(input) = 'object'
+              THEN substr(content, 1, instr(content, char(10))) || (
+                SELECT json_group_object(
+                  key,
+                  CASE
+                    WHEN length(CAST(value AS TEXT)) > ?2 THEN substr(value, 1, ?2)
+                    WHEN type IN ('array', 'object') THEN json(value)
+                    ELSE value
+                  END
+                )
+                FROM json_each(input)
+              )
+            ELSE substr(content, 1, ?2)
+          END AS content_preview,
           length(content) AS content_len,
-          timestamp
-        FROM messages
-        WHERE session_id = ?1
+          timestamp,
+          message_index
+        FROM (
+          SELECT *, substr(content, instr(content, char(10)) + 1) AS input
+          FROM messages
+          WHERE session_id = ?1
+        )
         ORDER BY CAST(message_index AS INTEGER) ASC
         LIMIT ?3 OFFSET ?4",
     )?;
@@ -280,6 +706,7 @@ pub fn load_message_previews_for_session(
         let timestamp: i64 = row.get(3)?;
 
         previews.push(MessagePreview {
+            index: row.get::<_, i64>(4)?.max(0) as usize,
             role,
             content_preview: row.get(1)?,
             content_len: row.get::<_, i64>(2)? as usize,
@@ -298,3 +725,191 @@ pub fn load_message_previews_for_session(
 
     Ok(previews)
 }
+
+/// Load the full content of one message, for expanding a truncated preview.
+pub fn load_message_content(
+    db_path: &Path,
+    session_id: &str,
+    message_index: usize,
+) -> Result<Option<String>> {
+    if !db_path.exists() {
+        return Ok(None);
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+
+    let content = db
+        .query_row(
+            "SELECT content
+            FROM messages
+            WHERE session_id = ?1 AND CAST(message_index AS INTEGER) = ?2",
+            rusqlite::params![session_id, message_index as i64],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("Failed to load message content")?;
+
+    Ok(content)
+}
+
+/// Files changed by a session's tool calls, in order of first change.
+pub fn load_changed_files(db_path: &Path, session_id: &str) -> Result<Vec<ChangedFile>> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+
+    let mut stmt = db.prepare(
+        "SELECT content
+        FROM messages
+        WHERE session_id = ?1 AND role = 'toolcall'
+        ORDER BY CAST(message_index AS INTEGER) ASC",
+    )?;
+
+    let calls: Vec<ToolCall> = stmt
+        .query_map([session_id], |row| row.get::<_, String>(0))
+        .context("Failed to query tool calls")?
+        .filter_map(|content| ToolCall::from_content(&content.ok()?))
+        .collect();
+
+    Ok(changed_files(&calls))
+}
+
+/// Sessions whose tool calls touched `path`, most recently touched first.
+///
+/// `path` matches an indexed path exactly or as its trailing components, so
+/// both `/home/me/app/src/lib.rs` and `src/lib.rs` find the same sessions.
+pub fn sessions_touching_file(db_path: &Path, path: &str) -> Result<Vec<FileActivity>> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+
+    let (condition, params) = query::path_condition("f.path", path);
+    let mut stmt = db.prepare(&format!(
+        "SELECT {SESSION_COLUMNS},
+                f.path, group_concat(f.operation), max(f.timestamp) AS touched
+         FROM session_files f
+         JOIN sessions s ON s.id = f.session_id
+         WHERE {condition}
+         GROUP BY s.id, f.path
+         ORDER BY touched DESC"
+    ))?;
+
+    let activity = stmt
+        .query_map(rusqlite::params_from_iter(&params), |row| {
+            let operations: String = row.get(SESSION_COLUMN_COUNT + 1)?;
+            let touched: i64 = row.get(SESSION_COLUMN_COUNT + 2)?;
+            Ok(FileActivity {
+                session: session_from_row(row)?,
+                path: row.get(SESSION_COLUMN_COUNT)?,
+                operations: operations
+                    .split(',')
+                    .filter_map(FileOperation::from_storage)
+                    .collect(),
+                last_touched: Utc
+                    .timestamp_opt(touched, 0)
+                    .single()
+                    .unwrap_or_else(Utc::now),
+            })
+        })
+        .context("Failed to query file activity")?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to load file activity")?;
+
+    Ok(activity)
+}
+
+/// Load every message of a session with its full content, in index order.
+pub fn load_messages_for_session(db_path: &Path, session_id: &str) -> Result<Vec<Message>> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+
+    let mut stmt = db.prepare(
+        "SELECT message_index, role, content, timestamp
+        FROM messages
+        WHERE session_id = ?1
+        ORDER BY CAST(message_index AS INTEGER) ASC",
+    )?;
+
+    let messages = stmt
+        .query_map([session_id], |row| {
+            let role_str: String = row.get(1)?;
+            let timestamp: i64 = row.get(3)?;
+            Ok(Message {
+                session_id: session_id.to_string(),
+                index: row.get::<_, i64>(0)?.max(0) as usize,
+                role: Role::from_storage(&role_str).unwrap_or(Role::User),
+                content: row.get(2)?,
+                timestamp: Utc
+                    .timestamp_opt(timestamp, 0)
+                    .single()
+                    .unwrap_or_else(Utc::now),
+            })
+        })
+        .context("Failed to query messages")?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to load messages")?;
+
+    Ok(messages)
+}
+
+/// Load the branches of a session abandoned by rewinding or editing a
+/// prompt, as redacted at index time, in the order they were recorded.
+pub fn load_branches(db_path: &Path, session_id: &str) -> Result<Vec<ConversationBranch>> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+
+    let mut stmt = db.prepare(
+        "SELECT branch_index, fork_index, message_index, role, content, timestamp
+        FROM branch_messages
+        WHERE session_id = ?1
+        ORDER BY branch_index ASC, message_index ASC",
+    )?;
+
+    let rows = stmt
+        .query_map([session_id], |row| {
+            let role_str: String = row.get(3)?;
+            let timestamp: i64 = row.get(5)?;
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?.max(0) as usize,
+                Message {
+                    session_id: session_id.to_string(),
+                    index: row.get::<_, i64>(2)?.max(0) as usize,
+                    role: Role::from_storage(&role_str).unwrap_or(Role::User),
+                    content: row.get(4)?,
+                    timestamp: Utc
+                        .timestamp_opt(timestamp, 0)
+                        .single()
+                        .unwrap_or_else(Utc::now),
+                },
+            ))
+        })
+        .context("Failed to query branches")?;
+
+    let mut branches: Vec<(i64, ConversationBranch)> = Vec::new();
+    for row in rows {
+        let (branch_index, fork_index, message) = row.context("Failed to load branches")?;
+        match branches.last_mut() {
+            Some((last, branch)) if *last == branch_index => branch.messages.push(message),
+            _ => branches.push((
+                branch_index,
+                ConversationBranch {
+                    fork_index,
+                    messages: vec![message],
+                },
+            )),
+        }
+    }
+
+    Ok(branches.into_iter().map(|(_, branch)| branch).collect())
+}
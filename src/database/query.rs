@@ -0,0 +1,170 @@
+use crate::models::tag::normalize_tag;
+use crate::utils::exclusions::expand_home;
+
+/// A search box query split into full-text terms and field filters.
+///
+/// Filters are `key:value` tokens (`branch:feature-x`, `file:src/app.rs`);
+/// everything else is passed to FTS5 as-is. Filter values may be quoted and
+/// may use `*` as a wildcard. `thinking:include` also searches the model's
+/// reasoning, and `tools:include` tool calls and their output; both are left
+/// out by default. `tag:design` (or `tag:#design`) selects sessions the user
+/// tagged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Full-text part of the query, trimmed.
+    pub text: String,
+    pub branch: Option<String>,
+    /// Path, or trailing part of a path, of a file touched by a tool call.
+    pub file: Option<String>,
+    /// Match text in reasoning messages as well as the conversation.
+    pub include_thinking: bool,
+    /// Match text in tool calls and their output as well.
+    pub include_tools: bool,
+    /// User tag, normalized.
+    pub tag: Option<String>,
+}
+
+impl SearchQuery {
+    pub fn parse(raw: &str) -> Self {
+        let mut query = SearchQuery::default();
+        let mut terms = Vec::new();
+
+        for token in raw.split_whitespace() {
+            match token.split_once(':') {
+                Some(("branch", value)) if !value.is_empty() => {
+                    query.branch = Some(value.trim_matches('"').to_string());
+                }
+                Some(("file", value)) if !value.is_empty() => {
+                    query.file = Some(value.trim_matches('"').to_string());
+                }
+                Some(("tag", value)) if normalize_tag(value.trim_matches('"')).is_some() => {
+                    query.tag = normalize_tag(value.trim_matches('"'));
+                }
+                Some(("thinking", "include")) => query.include_thinking = true,
+                Some(("thinking", "exclude")) => query.include_thinking = false,
+                Some(("tools", "include")) => query.include_tools = true,
+                Some(("tools", "exclude")) => query.include_tools = false,
+                _ => terms.push(token),
+            }
+        }
+
+        query.text = terms.join(" ");
+        query
+    }
+}
+
+/// SQL condition matching `column` against a filter value, using `GLOB` when
+/// the value contains a wildcard.
+pub(crate) fn filter_condition(column: &str, value: &str) -> String {
+    if value.contains('*') {
+        format!("{} GLOB ?", column)
+    } else {
+        format!("{} = ?", column)
+    }
+}
+
+/// SQL condition and parameters matching an indexed file `path` column
+/// against `value`, either exactly or as a trailing run of path components
+/// (`indexer.rs` matches `/src/database/indexer.rs`). A leading `~` is
+/// expanded, as it is in indexed paths.
+pub(crate) fn path_condition(column: &str, value: &str) -> (String, Vec<String>) {
+    let value = &expand_home(value);
+    let suffix = if value.contains('*') {
+        format!("*/{}", value)
+    } else {
+        format!("*/{}", glob_escape(value))
+    };
+    (
+        format!("({} OR {} GLOB ?)", filter_condition(column, value), column),
+        vec![value.to_string(), suffix],
+    )
+}
+
+/// Escape `GLOB` metacharacters so `value` only matches itself.
+fn glob_escape(value: &str) -> String {
+    value
+        .chars()
+        .map(|ch| match ch {
+            '*' | '?' | '[' => format!("[{}]", ch),
+            _ => ch.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extracts_branch_filter() {
+        let query = SearchQuery::parse("  refactor branch:feature-x parser ");
+        assert_eq!(query.text, "refactor parser");
+        assert_eq!(query.branch.as_deref(), Some("feature-x"));
+    }
+
+    #[test]
+    fn parse_extracts_file_filter() {
+        let query = SearchQuery::parse("file:\"src/database/indexer.rs\" branch:main");
+        assert_eq!(query.text, "");
+        assert_eq!(query.file.as_deref(), Some("src/database/indexer.rs"));
+        assert_eq!(query.branch.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn parse_reads_thinking_switch() {
+        let query = SearchQuery::parse("plan thinking:include");
+        assert_eq!(query.text, "plan");
+        assert!(query.include_thinking);
+        assert!(!SearchQuery::parse("plan thinking:exclude").include_thinking);
+        assert_eq!(SearchQuery::parse("thinking:maybe").text, "thinking:maybe");
+    }
+
+    #[test]
+    fn parse_reads_tools_switch() {
+        let query = SearchQuery::parse("cargo tools:include");
+        assert_eq!(query.text, "cargo");
+        assert!(query.include_tools);
+        assert!(!SearchQuery::parse("cargo tools:exclude").include_tools);
+        assert!(!SearchQuery::parse("cargo").include_tools);
+    }
+
+    #[test]
+    fn parse_extracts_tag_filter() {
+        let query = SearchQuery::parse("tag:#Incident-42 outage");
+        assert_eq!(query.text, "outage");
+        assert_eq!(query.tag.as_deref(), Some("incident-42"));
+        assert_eq!(SearchQuery::parse("tag:#").text, "tag:#");
+    }
+
+    #[test]
+    fn path_condition_matches_exact_or_trailing_components() {
+        let (sql, params) = path_condition("path", "a[1].rs");
+        assert_eq!(sql, "(path = ? OR path GLOB ?)");
+        assert_eq!(params, ["a[1].rs", "*/a[[]1].rs"]);
+
+        let (sql, params) = path_condition("path", "src/*.rs");
+        assert_eq!(sql, "(path GLOB ? OR path GLOB ?)");
+        assert_eq!(params, ["src/*.rs", "*/src/*.rs"]);
+
+        let home = std::env::var("HOME").unwrap();
+        let (_, params) = path_condition("path", "~/.bashrc");
+        assert_eq!(params[0], format!("{}/.bashrc", home.trim_end_matches('/')));
+    }
+
+    #[test]
+    fn parse_keeps_unknown_and_empty_filters_as_text() {
+        let query = SearchQuery::parse("http://example.com branch:");
+        assert_eq!(query.text, "http://example.com branch:");
+        assert_eq!(query.branch, None);
+        assert_eq!(SearchQuery::parse("   "), SearchQuery::default());
+    }
+
+    #[test]
+    fn filter_condition_uses_glob_for_wildcards() {
+        assert_eq!(filter_condition("git_branch", "main"), "git_branch = ?");
+        assert_eq!(
+            filter_condition("git_branch", "feat/*"),
+            "git_branch GLOB ?"
+        );
+    }
+}
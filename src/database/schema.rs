@@ -1,7 +1,27 @@
 use anyhow::Result;
 use rusqlite::Connection;
 
+/// Version of the index tables layout, stored in `PRAGMA user_version`.
+///
+/// The index is rebuilt from the session files on every start, so bumping
+/// this simply drops the old index tables and lets the indexer refill them.
+/// Tables holding user data (`starred_sessions`, `session_tags`,
+/// `session_notes`, `session_titles`, `message_bookmarks`) are never dropped.
+pub const SCHEMA_VERSION: i32 = 9;
+
 pub fn initialize_database(conn: &Connection) -> Result<()> {
+    let version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version < SCHEMA_VERSION {
+        conn.execute_batch(
+            "DROP TABLE IF EXISTS messages;
+             DROP TABLE IF EXISTS session_files;
+             DROP TABLE IF EXISTS session_continuations;
+             DROP TABLE IF EXISTS branch_messages;
+             DROP TABLE IF EXISTS sessions;",
+        )?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    }
+
     // Create sessions table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS sessions (
@@ -12,7 +32,21 @@ pub fn initialize_database(conn: &Connection) -> Result<()> {
             message_count INTEGER NOT NULL,
             file_path TEXT NOT NULL,
             last_updated INTEGER NOT NULL,
-            first_prompt TEXT
+            first_prompt TEXT,
+            models TEXT,
+            input_tokens INTEGER NOT NULL DEFAULT 0,
+            output_tokens INTEGER NOT NULL DEFAULT 0,
+            cache_read_tokens INTEGER NOT NULL DEFAULT 0,
+            cache_write_tokens INTEGER NOT NULL DEFAULT 0,
+            cost_usd REAL,
+            cost_is_estimate INTEGER NOT NULL DEFAULT 0,
+            git_branch TEXT,
+            git_commit TEXT,
+            git_remote TEXT,
+            parent_session_id TEXT,
+            leaf_uuid TEXT,
+            previous_session_id TEXT,
+            title TEXT
         )",
         [],
     )?;
@@ -25,11 +59,31 @@ pub fn initialize_database(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_branch ON sessions(git_branch)",
+        [],
+    )?;
+
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_time ON sessions(start_time DESC)",
         [],
     )?;
 
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_parent ON sessions(parent_session_id)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_leaf ON sessions(leaf_uuid)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_previous ON sessions(previous_session_id)",
+        [],
+    )?;
+
     // Create FTS5 messages table
     conn.execute(
         "CREATE VIRTUAL TABLE IF NOT EXISTS messages USING fts5(
@@ -42,5 +96,189 @@ pub fn initialize_database(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    // Files named by tool calls, one row per session, path and operation
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_files (
+            session_id TEXT NOT NULL,
+            path TEXT NOT NULL,
+            operation TEXT NOT NULL,
+            message_index INTEGER NOT NULL,
+            timestamp INTEGER NOT NULL,
+            PRIMARY KEY (session_id, path, operation)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_file_path ON session_files(path)",
+        [],
+    )?;
+
+    // Messages of other sessions named by a session's summary events; the
+    // indexer resolves them into `sessions.previous_session_id`
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_continuations (
+            session_id TEXT NOT NULL,
+            leaf_uuid TEXT NOT NULL,
+            PRIMARY KEY (session_id, leaf_uuid)
+        )",
+        [],
+    )?;
+
+    // Messages of branches abandoned by rewinding or editing a prompt. They
+    // are shown in the detail view but not searched.
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS branch_messages (
+            session_id TEXT NOT NULL,
+            fork_index INTEGER NOT NULL,
+            branch_index INTEGER NOT NULL,
+            message_index INTEGER NOT NULL,
+            role TEXT NOT NULL,
+            content TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            PRIMARY KEY (session_id, branch_index, message_index)
+        )",
+        [],
+    )?;
+
+    // User data, keyed by session id so it survives reindexing
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS starred_sessions (
+            session_id TEXT PRIMARY KEY,
+            starred_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_tags (
+            session_id TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (session_id, tag)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_tag ON session_tags(tag)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_titles (
+            session_id TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS message_bookmarks (
+            session_id TEXT NOT NULL,
+            message_index INTEGER NOT NULL,
+            created_at INTEGER NOT NULL,
+            PRIMARY KEY (session_id, message_index)
+        )",
+        [],
+    )?;
+
+    // Markdown notes, searchable with the messages
+    conn.execute(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS session_notes USING fts5(
+            session_id UNINDEXED,
+            note,
+            updated_at UNINDEXED
+        )",
+        [],
+    )?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outdated_index_tables_are_rebuilt() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sessions (id TEXT PRIMARY KEY, tool TEXT NOT NULL);
+             INSERT INTO sessions (id, tool) VALUES ('old', 'claude_code');",
+        )
+        .unwrap();
+
+        initialize_database(&conn).unwrap();
+
+        let version: i32 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+        conn.prepare(
+            "SELECT cost_usd, git_branch, parent_session_id, leaf_uuid, title FROM sessions",
+        )
+        .unwrap();
+        conn.prepare("SELECT session_id, leaf_uuid FROM session_continuations")
+            .unwrap();
+        conn.prepare("SELECT path, operation FROM session_files")
+            .unwrap();
+        conn.prepare("SELECT fork_index, content FROM branch_messages")
+            .unwrap();
+    }
+
+    #[test]
+    fn user_data_survives_a_rebuild() {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO starred_sessions (session_id, starred_at) VALUES ('kept', 0);
+             INSERT INTO session_tags (session_id, tag) VALUES ('kept', 'design');
+             INSERT INTO session_notes (session_id, note, updated_at) VALUES ('kept', 'n', 0);
+             INSERT INTO session_titles (session_id, title, updated_at) VALUES ('kept', 't', 0);
+             INSERT INTO message_bookmarks (session_id, message_index, created_at)
+             VALUES ('kept', 3, 0);",
+        )
+        .unwrap();
+        conn.pragma_update(None, "user_version", 0).unwrap();
+
+        initialize_database(&conn).unwrap();
+
+        let count: i64 = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM starred_sessions)
+                      + (SELECT COUNT(*) FROM session_tags)
+                      + (SELECT COUNT(*) FROM session_notes)
+                      + (SELECT COUNT(*) FROM session_titles)
+                      + (SELECT COUNT(*) FROM message_bookmarks)",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 5);
+    }
+
+    #[test]
+    fn current_schema_keeps_existing_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO sessions (id, tool, start_time, message_count, file_path, last_updated)
+             VALUES ('kept', 'codex', 0, 0, '/tmp/a', 0)",
+            [],
+        )
+        .unwrap();
+
+        initialize_database(&conn).unwrap();
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+}
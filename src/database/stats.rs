@@ -0,0 +1,415 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use rusqlite::{Connection, ToSql};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::models::{TokenUsage, Tool};
+
+/// Number of projects listed in `Statistics::busiest_projects`.
+const BUSIEST_PROJECTS_LIMIT: usize = 10;
+
+/// Heatmap intensity levels above zero.
+pub const HEATMAP_LEVELS: u8 = 4;
+
+/// Which sessions the statistics are computed over.
+#[derive(Debug, Clone)]
+pub struct StatsFilter {
+    pub tools: Vec<Tool>,
+    /// Only sessions updated at or after this instant.
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Sessions started by one tool during one day or week.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCount {
+    /// The day, or the Monday of the week.
+    pub bucket: NaiveDate,
+    pub tool: Tool,
+    pub sessions: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectActivity {
+    pub project_path: String,
+    pub sessions: usize,
+    pub messages: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsTotals {
+    pub sessions: usize,
+    pub messages: usize,
+    pub avg_duration_secs: f64,
+    pub avg_messages: f64,
+    pub tokens: TokenUsage,
+    /// Sum of known session costs; `None` when no session has one.
+    pub cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Statistics {
+    pub per_day: Vec<ToolCount>,
+    pub per_week: Vec<ToolCount>,
+    pub busiest_projects: Vec<ProjectActivity>,
+    pub totals: StatsTotals,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeatmapCell {
+    pub date: NaiveDate,
+    pub sessions: usize,
+    /// 0 for no activity, up to `HEATMAP_LEVELS` for the busiest days.
+    pub level: u8,
+}
+
+/// Condition selecting top-level sessions. Subagent sessions are not
+/// counted as sessions of their own, but their tokens and cost are.
+const TOP_LEVEL: &str = "parent_session_id IS NULL";
+
+/// Build the `WHERE` clause and parameters shared by every aggregate.
+fn filter_clause(filter: &StatsFilter) -> (String, Vec<Box<dyn ToSql>>) {
+    let mut conditions = Vec::new();
+    let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+
+    if filter.tools.len() != Tool::ALL.len() {
+        let placeholders: Vec<&str> = filter.tools.iter().map(|_| "?").collect();
+        conditions.push(format!("tool IN ({})", placeholders.join(",")));
+        for tool in &filter.tools {
+            params.push(Box::new(tool.to_storage()));
+        }
+    }
+
+    if let Some(since) = filter.since {
+        conditions.push("last_updated >= ?".to_string());
+        params.push(Box::new(since.timestamp()));
+    }
+
+    if conditions.is_empty() {
+        (String::new(), params)
+    } else {
+        (format!("WHERE {}", conditions.join(" AND ")), params)
+    }
+}
+
+/// Compute the statistics dashboard aggregates from the index.
+pub fn load_statistics(db_path: &Path, filter: &StatsFilter) -> Result<Statistics> {
+    if !db_path.exists() || filter.tools.is_empty() {
+        return Ok(Statistics::default());
+    }
+
+    let db = Connection::open(db_path).context("Failed to open database")?;
+    let (where_clause, params) = filter_clause(filter);
+    let params: Vec<&dyn ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    let top_level_clause = if where_clause.is_empty() {
+        format!("WHERE {}", TOP_LEVEL)
+    } else {
+        format!("{} AND {}", where_clause, TOP_LEVEL)
+    };
+
+    let per_day = tool_counts(
+        &db,
+        "date(start_time, 'unixepoch')",
+        &top_level_clause,
+        &params,
+    )?;
+    // 'weekday 0' moves to the next Sunday (or stays), '-6 days' back to Monday.
+    let per_week = tool_counts(
+        &db,
+        "date(start_time, 'unixepoch', 'weekday 0', '-6 days')",
+        &top_level_clause,
+        &params,
+    )?;
+
+    let busiest_projects = {
+        let project_clause = format!("{} AND project_path IS NOT NULL", top_level_clause);
+        let mut stmt = db.prepare(&format!(
+            "SELECT project_path, COUNT(*) AS sessions, SUM(message_count) AS messages
+             FROM sessions
+             {}
+             GROUP BY project_path
+             ORDER BY sessions DESC, messages DESC, project_path ASC
+             LIMIT {}",
+            project_clause, BUSIEST_PROJECTS_LIMIT
+        ))?;
+        stmt.query_map(params.as_slice(), |row| {
+            Ok(ProjectActivity {
+                project_path: row.get(0)?,
+                sessions: row.get::<_, i64>(1)?.max(0) as usize,
+                messages: row.get::<_, i64>(2)?.max(0) as usize,
+            })
+        })
+        .context("Failed to query busiest projects")?
+        .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let totals = db
+        .query_row(
+            &format!(
+                "SELECT COALESCE(SUM({top}), 0),
+                        COALESCE(SUM(CASE WHEN {top} THEN message_count END), 0),
+                        COALESCE(AVG(CASE WHEN {top} THEN last_updated - start_time END), 0),
+                        COALESCE(AVG(CASE WHEN {top} THEN message_count END), 0),
+                        COALESCE(SUM(input_tokens), 0),
+                        COALESCE(SUM(output_tokens), 0),
+                        COALESCE(SUM(cache_read_tokens), 0),
+                        COALESCE(SUM(cache_write_tokens), 0),
+                        SUM(cost_usd)
+                 FROM sessions
+                 {where_clause}",
+                top = TOP_LEVEL,
+            ),
+            params.as_slice(),
+            |row| {
+                let count = |idx: usize| -> rusqlite::Result<u64> {
+                    Ok(row.get::<_, i64>(idx)?.max(0) as u64)
+                };
+                Ok(StatsTotals {
+                    sessions: count(0)? as usize,
+                    messages: count(1)? as usize,
+                    avg_duration_secs: row.get(2)?,
+                    avg_messages: row.get(3)?,
+                    tokens: TokenUsage {
+                        input: count(4)?,
+                        output: count(5)?,
+                        cache_read: count(6)?,
+                        cache_write: count(7)?,
+                    },
+                    cost_usd: row.get(8)?,
+                })
+            },
+        )
+        .context("Failed to query totals")?;
+
+    Ok(Statistics {
+        per_day,
+        per_week,
+        busiest_projects,
+        totals,
+    })
+}
+
+fn tool_counts(
+    db: &Connection,
+    bucket_expr: &str,
+    where_clause: &str,
+    params: &[&dyn ToSql],
+) -> Result<Vec<ToolCount>> {
+    let mut stmt = db.prepare(&format!(
+        "SELECT {} AS bucket, tool, COUNT(*)
+         FROM sessions
+         {}
+         GROUP BY bucket, tool
+         ORDER BY bucket ASC, tool ASC",
+        bucket_expr, where_clause
+    ))?;
+
+    let mut rows = stmt
+        .query(params)
+        .context("Failed to query session counts")?;
+    let mut counts = Vec::new();
+    while let Some(row) = rows.next()? {
+        let bucket: String = row.get(0)?;
+        let tool: String = row.get(1)?;
+        let (Ok(bucket), Some(tool)) = (
+            NaiveDate::parse_from_str(&bucket, "%Y-%m-%d"),
+            Tool::from_storage(&tool),
+        ) else {
+            continue;
+        };
+        counts.push(ToolCount {
+            bucket,
+            tool,
+            sessions: row.get::<_, i64>(2)?.max(0) as usize,
+        });
+    }
+
+    Ok(counts)
+}
+
+/// Sum per-tool counts into one total per bucket.
+pub fn totals_by_bucket(counts: &[ToolCount]) -> BTreeMap<NaiveDate, usize> {
+    let mut totals = BTreeMap::new();
+    for count in counts {
+        *totals.entry(count.bucket).or_insert(0) += count.sessions;
+    }
+    totals
+}
+
+/// Lay out daily totals as a GitHub-style grid: `weeks` columns of seven
+/// days (Monday first), the last column containing `end`.
+///
+/// Days after `end` are omitted, so the last column may be shorter.
+pub fn heatmap(
+    daily: &BTreeMap<NaiveDate, usize>,
+    end: NaiveDate,
+    weeks: usize,
+) -> Vec<Vec<HeatmapCell>> {
+    if weeks == 0 {
+        return Vec::new();
+    }
+
+    let last_monday = end - Duration::days(end.weekday().num_days_from_monday() as i64);
+    let first_monday = last_monday - Duration::weeks(weeks as i64 - 1);
+
+    let max = daily
+        .range(first_monday..=end)
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0);
+
+    (0..weeks)
+        .map(|week| {
+            let monday = first_monday + Duration::weeks(week as i64);
+            (0..7)
+                .map(|day| monday + Duration::days(day))
+                .take_while(|date| *date <= end)
+                .map(|date| {
+                    let sessions = daily.get(&date).copied().unwrap_or(0);
+                    HeatmapCell {
+                        date,
+                        sessions,
+                        level: heatmap_level(sessions, max),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Consecutive chart buckets ending with the one containing `today`, each
+/// with its per-tool session counts in `Tool::ALL` order.
+///
+/// The range starts at `since` (or the earliest bucket with data) and is
+/// capped at `max_buckets`.
+pub fn chart_buckets(
+    counts: &[ToolCount],
+    weekly: bool,
+    since: Option<NaiveDate>,
+    today: NaiveDate,
+    max_buckets: usize,
+) -> Vec<(NaiveDate, Vec<(Tool, usize)>)> {
+    let step = if weekly {
+        Duration::weeks(1)
+    } else {
+        Duration::days(1)
+    };
+    let align = |date: NaiveDate| {
+        if weekly {
+            date - Duration::days(date.weekday().num_days_from_monday() as i64)
+        } else {
+            date
+        }
+    };
+
+    let last = align(today);
+    let Some(first) = since
+        .or_else(|| counts.iter().map(|count| count.bucket).min())
+        .map(align)
+    else {
+        return Vec::new();
+    };
+
+    let mut buckets = Vec::new();
+    let mut bucket = last;
+    while bucket >= first && buckets.len() < max_buckets {
+        let per_tool = Tool::ALL
+            .iter()
+            .map(|tool| {
+                let sessions = counts
+                    .iter()
+                    .filter(|count| count.bucket == bucket && count.tool == *tool)
+                    .map(|count| count.sessions)
+                    .sum();
+                (*tool, sessions)
+            })
+            .collect();
+        buckets.push((bucket, per_tool));
+        bucket -= step;
+    }
+    buckets.reverse();
+    buckets
+}
+
+fn heatmap_level(sessions: usize, max: usize) -> u8 {
+    if sessions == 0 || max == 0 {
+        return 0;
+    }
+    let scaled = (sessions * HEATMAP_LEVELS as usize).div_ceil(max);
+    scaled.clamp(1, HEATMAP_LEVELS as usize) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn heatmap_ends_on_given_day_with_monday_columns() {
+        // 2026-02-04 is a Wednesday.
+        let end = date(2026, 2, 4);
+        let mut daily = BTreeMap::new();
+        daily.insert(date(2026, 2, 2), 4);
+        daily.insert(date(2026, 2, 3), 1);
+
+        let grid = heatmap(&daily, end, 2);
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid[0].len(), 7);
+        assert_eq!(grid[0][0].date, date(2026, 1, 26));
+        assert_eq!(grid[1].len(), 3);
+        assert_eq!(grid[1][0].date, date(2026, 2, 2));
+        assert_eq!(grid[1][0].level, HEATMAP_LEVELS);
+        assert_eq!(grid[1][1].level, 1);
+        assert_eq!(grid[1][2].level, 0);
+    }
+
+    #[test]
+    fn chart_buckets_fill_gaps_and_cap_length() {
+        let counts = vec![ToolCount {
+            bucket: date(2026, 1, 5),
+            tool: Tool::Codex,
+            sessions: 3,
+        }];
+
+        let daily = chart_buckets(&counts, false, None, date(2026, 1, 8), 30);
+        assert_eq!(daily.len(), 4);
+        assert_eq!(daily[0].0, date(2026, 1, 5));
+        assert!(daily[0].1.contains(&(Tool::Codex, 3)));
+        assert!(daily[1].1.iter().all(|(_, sessions)| *sessions == 0));
+
+        let capped = chart_buckets(&counts, false, None, date(2026, 1, 8), 2);
+        assert_eq!(capped.len(), 2);
+        assert_eq!(capped[1].0, date(2026, 1, 8));
+
+        let weekly = chart_buckets(
+            &counts,
+            true,
+            Some(date(2025, 12, 31)),
+            date(2026, 1, 8),
+            52,
+        );
+        assert_eq!(weekly.len(), 2);
+        assert_eq!(weekly[0].0, date(2025, 12, 29));
+    }
+
+    #[test]
+    fn totals_by_bucket_sums_tools() {
+        let counts = vec![
+            ToolCount {
+                bucket: date(2026, 1, 1),
+                tool: Tool::ClaudeCode,
+                sessions: 2,
+            },
+            ToolCount {
+                bucket: date(2026, 1, 1),
+                tool: Tool::Codex,
+                sessions: 3,
+            },
+        ];
+        assert_eq!(totals_by_bucket(&counts).get(&date(2026, 1, 1)), Some(&5));
+    }
+}
@@ -0,0 +1,256 @@
+//! D-Bus API for editor plugins and scripts, served by the running app on its
+//! bus name at [`OBJECT_PATH`]. Queries go through the same `database`
+//! functions as the GUI, so both see the same index.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use anyhow::{Context, Result};
+use relm4::gtk::gio::{self, prelude::*};
+use relm4::gtk::glib::{self, Variant};
+
+use crate::database::{load_message_previews_for_session, load_session, search_sessions};
+use crate::models::{MessagePreview, Session, Tool};
+
+pub const OBJECT_PATH: &str = "/io/github/supermaciz/sessionschronicle/Sessions";
+pub const INTERFACE: &str = "io.github.supermaciz.SessionsChronicle1";
+
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="io.github.supermaciz.SessionsChronicle1">
+    <!-- Sessions matching a search query, in the app's query syntax.
+         Filters: "tools" (as) storage names of the tools to search,
+         "limit" (u) maximum number of sessions. -->
+    <method name="Search">
+      <arg type="s" name="query" direction="in"/>
+      <arg type="a{sv}" name="filters" direction="in"/>
+      <arg type="aa{sv}" name="sessions" direction="out"/>
+    </method>
+    <method name="GetSession">
+      <arg type="s" name="id" direction="in"/>
+      <arg type="a{sv}" name="session" direction="out"/>
+    </method>
+    <!-- Messages in order from `offset`; a `limit` of 0 returns the rest. -->
+    <method name="GetMessages">
+      <arg type="s" name="id" direction="in"/>
+      <arg type="u" name="offset" direction="in"/>
+      <arg type="u" name="limit" direction="in"/>
+      <arg type="aa{sv}" name="messages" direction="out"/>
+    </method>
+    <!-- Resume the session in the terminal chosen in Preferences. -->
+    <method name="Resume">
+      <arg type="s" name="id" direction="in"/>
+    </method>
+    <!-- Emitted after the index was refreshed, rebuilt or purged. -->
+    <signal name="SessionsChanged"/>
+  </interface>
+</node>
+"#;
+
+#[derive(Debug)]
+enum ApiCall {
+    Search(String, HashMap<String, Variant>),
+    GetSession(String),
+    GetMessages(String, u32, u32),
+    Resume(String),
+}
+
+impl DBusMethodCall for ApiCall {
+    fn parse_call(
+        _obj_path: &str,
+        _interface: Option<&str>,
+        method: &str,
+        params: Variant,
+    ) -> Result<Self, glib::Error> {
+        let call = match method {
+            "Search" => params
+                .get::<(String, HashMap<String, Variant>)>()
+                .map(|(query, filters)| Self::Search(query, filters)),
+            "GetSession" => params.get::<(String,)>().map(|(id,)| Self::GetSession(id)),
+            "GetMessages" => params
+                .get::<(String, u32, u32)>()
+                .map(|(id, offset, limit)| Self::GetMessages(id, offset, limit)),
+            "Resume" => params.get::<(String,)>().map(|(id,)| Self::Resume(id)),
+            _ => {
+                return Err(glib::Error::new(
+                    gio::DBusError::UnknownMethod,
+                    &format!("No such method: {}", method),
+                ));
+            }
+        };
+        call.ok_or_else(|| invalid_args(&format!("Invalid arguments for {}", method)))
+    }
+}
+
+/// Answers API calls from the index at `db_path`.
+pub struct SessionsApi {
+    db_path: PathBuf,
+    resume: Box<dyn Fn(&Session)>,
+}
+
+impl SessionsApi {
+    /// `resume` starts the session the way the GUI's resume button does.
+    pub fn new(db_path: PathBuf, resume: impl Fn(&Session) + 'static) -> Rc<Self> {
+        Rc::new(Self {
+            db_path,
+            resume: Box::new(resume),
+        })
+    }
+
+    /// Export the API on `connection` at [`OBJECT_PATH`].
+    pub fn register(
+        self: &Rc<Self>,
+        connection: &gio::DBusConnection,
+    ) -> Result<gio::RegistrationId> {
+        let node =
+            gio::DBusNodeInfo::for_xml(INTERFACE_XML).context("Failed to parse the D-Bus API")?;
+        let interface = node
+            .lookup_interface(INTERFACE)
+            .context("D-Bus API interface is missing")?;
+
+        let api = self.clone();
+        connection
+            .register_object(OBJECT_PATH, &interface)
+            .typed_method_call::<ApiCall>()
+            .invoke_and_return(move |_, _, call| api.handle_call(call))
+            .build()
+            .context("Failed to export the D-Bus API")
+    }
+
+    fn handle_call(&self, call: ApiCall) -> Result<Option<Variant>, glib::Error> {
+        match call {
+            ApiCall::Search(query, filters) => {
+                let (tools, limit) = parse_filters(&filters)?;
+                let sessions = search_sessions(&self.db_path, &tools, &query).map_err(failed)?;
+                let sessions: Vec<_> = sessions.iter().take(limit).map(session_dict).collect();
+                Ok(Some((sessions,).to_variant()))
+            }
+            ApiCall::GetSession(id) => {
+                let session = self.session(&id)?;
+                Ok(Some((session_dict(&session),).to_variant()))
+            }
+            ApiCall::GetMessages(id, offset, limit) => {
+                self.session(&id)?;
+                let limit = match limit {
+                    0 => i64::MAX as usize,
+                    limit => limit as usize,
+                };
+                let messages = load_message_previews_for_session(
+                    &self.db_path,
+                    &id,
+                    limit,
+                    offset as usize,
+                    i64::MAX as usize,
+                )
+                .map_err(failed)?;
+                let messages: Vec<_> = messages.iter().map(message_dict).collect();
+                Ok(Some((messages,).to_variant()))
+            }
+            ApiCall::Resume(id) => {
+                let session = self.session(&id)?;
+                (self.resume)(&session);
+                Ok(None)
+            }
+        }
+    }
+
+    fn session(&self, id: &str) -> Result<Session, glib::Error> {
+        load_session(&self.db_path, id)
+            .map_err(failed)?
+            .ok_or_else(|| invalid_args(&format!("No session with id {}", id)))
+    }
+}
+
+/// Tell clients the index changed. Emitting on a connection the API is not
+/// exported on is harmless.
+pub fn emit_sessions_changed(connection: &gio::DBusConnection) -> Result<()> {
+    connection
+        .emit_signal(None, OBJECT_PATH, INTERFACE, "SessionsChanged", None)
+        .context("Failed to emit SessionsChanged")
+}
+
+fn parse_filters(filters: &HashMap<String, Variant>) -> Result<(Vec<Tool>, usize), glib::Error> {
+    let mut tools = Tool::ALL.to_vec();
+    let mut limit = usize::MAX;
+    for (key, value) in filters {
+        match key.as_str() {
+            "tools" => {
+                let names = value
+                    .get::<Vec<String>>()
+                    .ok_or_else(|| invalid_args("The tools filter must be an array of strings"))?;
+                tools = names
+                    .iter()
+                    .map(|name| {
+                        Tool::from_storage(name)
+                            .ok_or_else(|| invalid_args(&format!("Unknown tool: {}", name)))
+                    })
+                    .collect::<Result<_, _>>()?;
+            }
+            "limit" => {
+                limit = value
+                    .get::<u32>()
+                    .ok_or_else(|| invalid_args("The limit filter must be a uint32"))?
+                    as usize;
+            }
+            _ => return Err(invalid_args(&format!("Unknown filter: {}", key))),
+        }
+    }
+    Ok((tools, limit))
+}
+
+fn session_dict(session: &Session) -> HashMap<String, Variant> {
+    let mut dict = HashMap::from([
+        ("id".to_string(), session.id.to_variant()),
+        ("tool".to_string(), session.tool.to_storage().to_variant()),
+        ("file_path".to_string(), session.file_path.to_variant()),
+        (
+            "start_time".to_string(),
+            session.start_time.timestamp().to_variant(),
+        ),
+        (
+            "last_updated".to_string(),
+            session.last_updated.timestamp().to_variant(),
+        ),
+        (
+            "message_count".to_string(),
+            (session.message_count as u32).to_variant(),
+        ),
+        ("starred".to_string(), session.starred.to_variant()),
+        ("tags".to_string(), session.tags.to_variant()),
+    ]);
+    // Absent values are left out: `a{sv}` has no null.
+    let optional = [
+        ("title", session.display_title()),
+        ("project_path", session.project_path.as_deref()),
+        ("first_prompt", session.first_prompt.as_deref()),
+        ("git_branch", session.git.branch.as_deref()),
+        ("parent_session_id", session.parent_session_id.as_deref()),
+    ];
+    for (key, value) in optional {
+        if let Some(value) = value {
+            dict.insert(key.to_string(), value.to_variant());
+        }
+    }
+    dict
+}
+
+fn message_dict(message: &MessagePreview) -> HashMap<String, Variant> {
+    HashMap::from([
+        ("index".to_string(), (message.index as u32).to_variant()),
+        ("role".to_string(), message.role.to_storage().to_variant()),
+        ("content".to_string(), message.content_preview.to_variant()),
+        (
+            "timestamp".to_string(),
+            message.timestamp.timestamp().to_variant(),
+        ),
+    ])
+}
+
+fn invalid_args(message: &str) -> glib::Error {
+    glib::Error::new(gio::DBusError::InvalidArgs, message)
+}
+
+fn failed(err: anyhow::Error) -> glib::Error {
+    glib::Error::new(gio::DBusError::Failed, &format!("{:#}", err))
+}
@@ -1,8 +1,16 @@
 pub mod config;
 pub mod database;
+pub mod dbus_api;
+pub mod export;
+pub mod mcp;
 pub mod models;
 pub mod parsers;
+pub mod pricing;
+pub mod redaction;
+pub mod search_provider;
 pub mod session_sources;
+pub mod settings;
+pub mod tui;
 pub mod utils;
 
 // Re-export commonly used types
@@ -1,29 +1,45 @@
 #[rustfmt::skip]
 mod config;
 mod app;
+mod cli;
 mod database;
+mod dbus_api;
+mod export;
+mod mcp;
 mod models;
 mod parsers;
+mod pricing;
+mod redaction;
+mod search_provider;
 mod session_sources;
+mod settings;
+mod tui;
 mod ui;
 mod utils;
 
 use config::{APP_ID, GETTEXT_PACKAGE, LOCALEDIR, RESOURCES_FILE};
 use gettextrs::{LocaleCategory, gettext};
-use gtk::prelude::ApplicationExt;
+use gtk::prelude::{
+    ApplicationExt, ApplicationExtManual, FileExt, GtkApplicationExt, GtkWindowExt, ToVariant,
+    WidgetExt,
+};
 use gtk::{gio, glib};
 use relm4::{RelmApp, gtk, main_application};
 use std::{env, path::PathBuf};
 
 use app::App;
+use session_sources::select_db_filename;
 
 use clap::Parser;
 
 #[derive(Parser)]
 struct Args {
-    #[arg(long, value_name = "DIR")]
+    #[arg(long, value_name = "DIR", global = true)]
     sessions_dir: Option<PathBuf>,
 
+    #[command(subcommand)]
+    command: Option<cli::Command>,
+
     #[arg(allow_hyphen_values = true, trailing_var_arg = true)]
     gtk_options: Vec<String>,
 }
@@ -34,6 +50,17 @@ relm4::new_stateless_action!(QuitAction, AppActionGroup, "quit");
 fn main() {
     let args = Args::parse();
 
+    if let Some(command) = args.command {
+        let db_path = glib::user_data_dir()
+            .join(APP_ID)
+            .join(select_db_filename(args.sessions_dir.is_some()));
+        if let Err(err) = cli::run(command, &db_path, args.sessions_dir.as_deref()) {
+            eprintln!("sessions-chronicle: {:#}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     gtk::init().unwrap();
 
     // Enable logging
@@ -57,6 +84,21 @@ fn main() {
     let app = main_application();
     app.set_resource_base_path(Some("/io/github/supermaciz/sessionschronicle/"));
 
+    // `sessions-chronicle://` permalinks arrive as files to open, either on the
+    // command line or from the desktop file's scheme handler; the running
+    // instance receives them when the app is already open.
+    app.set_flags(app.flags() | gio::ApplicationFlags::HANDLES_OPEN);
+    app.connect_open(|app, files, _hint| {
+        app.activate();
+        if let Some(window) = app.active_window() {
+            for file in files {
+                WidgetExt::activate_action(&window, "win.open-uri", Some(&file.uri().to_variant()))
+                    .ok();
+            }
+            window.present();
+        }
+    });
+
     let program_invocation = env::args()
         .next()
         .unwrap_or_else(|| String::from("sessions-chronicle"));
@@ -0,0 +1,465 @@
+//! `sessions-chronicle mcp`: a Model Context Protocol server on stdio, so
+//! coding agents can look up their own session history in the index.
+//!
+//! Messages are newline-delimited JSON-RPC 2.0. The server only reads the
+//! index; it never indexes or changes anything.
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+
+use crate::config::VERSION;
+use crate::database::{
+    load_message_previews_for_session, load_session, load_sessions, search_sessions,
+    sessions_touching_file,
+};
+use crate::models::file_access::lookup_path;
+use crate::models::{FileActivity, Session, Tool};
+
+/// Protocol revisions the server speaks, newest first.
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 100;
+
+const DEFAULT_MESSAGE_LIMIT: usize = 50;
+
+/// Characters of each message returned by `get_session`. Long tool calls
+/// keep every input field, with long values cut to this length.
+const MESSAGE_MAX_CHARS: usize = 4000;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// A JSON-RPC error response.
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Answer requests read from `input` on `output` until `input` is closed.
+pub fn serve(db_path: &Path, input: impl BufRead, mut output: impl Write) -> Result<()> {
+    for line in input.lines() {
+        let line = line.context("Failed to read a request")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_message(db_path, &line) {
+            writeln!(output, "{}", response).context("Failed to write a response")?;
+            output.flush().context("Failed to write a response")?;
+        }
+    }
+    Ok(())
+}
+
+/// Response to one JSON-RPC message, or `None` for a notification.
+pub fn handle_message(db_path: &Path, line: &str) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(err) => {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, format!("Parse error: {}", err)),
+            ));
+        }
+    };
+
+    let id = message.get("id").cloned();
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        // Responses to requests we never send are ignored.
+        if message.get("result").is_some() || message.get("error").is_some() {
+            return None;
+        }
+        return Some(error_response(
+            id.unwrap_or(Value::Null),
+            RpcError::new(INVALID_REQUEST, "Invalid request"),
+        ));
+    };
+    // Notifications (`notifications/initialized`, cancellations) need no
+    // answer.
+    let id = id?;
+
+    let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
+    let result = match method {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(db_path, &params),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Method not found: {}", method),
+        )),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => error_response(id, err),
+    })
+}
+
+fn error_response(id: Value, err: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": err.code, "message": err.message },
+    })
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .and_then(|requested| PROTOCOL_VERSIONS.iter().find(|v| **v == requested))
+        .unwrap_or(&PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": { "name": "sessions-chronicle", "version": VERSION },
+        "instructions": "Search the history of Claude Code, OpenCode, Codex and Mistral Vibe \
+            sessions on this machine, e.g. to find how a problem was solved before.",
+    })
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "search_sessions",
+            "description": "Full-text search over past coding agent sessions, best matches \
+                first. The query accepts filters such as `branch:main`, `file:src/app.rs` \
+                and `tag:design`.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Words to search for" },
+                    "tools": {
+                        "type": "array",
+                        "items": { "type": "string", "enum": tool_names() },
+                        "description": "Only search sessions of these tools",
+                    },
+                    "limit": limit_schema(),
+                },
+                "required": ["query"],
+            },
+        },
+        {
+            "name": "get_session",
+            "description": "A session's details and a page of its messages, in order.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "session_id": { "type": "string" },
+                    "message_offset": { "type": "integer", "minimum": 0 },
+                    "message_limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": MAX_LIMIT,
+                        "description": format!("Defaults to {}", DEFAULT_MESSAGE_LIMIT),
+                    },
+                },
+                "required": ["session_id"],
+            },
+        },
+        {
+            "name": "list_recent_sessions",
+            "description": "Most recently updated sessions, optionally of one project.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "project": {
+                        "type": "string",
+                        "description": "Project directory, or its trailing path components",
+                    },
+                    "limit": limit_schema(),
+                },
+            },
+        },
+        {
+            "name": "find_sessions_touching_file",
+            "description": "Sessions whose tool calls read, edited or ran commands on a file, \
+                most recent first.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path of the file; a path that does not exist matches \
+                            indexed paths ending with it",
+                    },
+                    "limit": limit_schema(),
+                },
+                "required": ["path"],
+            },
+        },
+    ])
+}
+
+fn tool_names() -> Vec<String> {
+    Tool::ALL.iter().map(|tool| tool.to_storage()).collect()
+}
+
+fn limit_schema() -> Value {
+    json!({
+        "type": "integer",
+        "minimum": 1,
+        "maximum": MAX_LIMIT,
+        "description": format!("Defaults to {}", DEFAULT_LIMIT),
+    })
+}
+
+/// Run a tool. Unknown tools are protocol errors; failures of a known tool
+/// are reported in its result, so the agent can see and correct them.
+fn call_tool(db_path: &Path, params: &Value) -> Result<Value, RpcError> {
+    let name = params
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing tool name"))?;
+    let empty = json!({});
+    let args = params.get("arguments").unwrap_or(&empty);
+
+    let output = match name {
+        "search_sessions" => tool_search_sessions(db_path, args),
+        "get_session" => tool_get_session(db_path, args),
+        "list_recent_sessions" => tool_list_recent_sessions(db_path, args),
+        "find_sessions_touching_file" => tool_find_sessions_touching_file(db_path, args),
+        _ => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("Unknown tool: {}", name),
+            ));
+        }
+    };
+
+    Ok(match output {
+        Ok(value) => json!({
+            "content": [{
+                "type": "text",
+                "text": serde_json::to_string_pretty(&value).unwrap_or_default(),
+            }],
+            "isError": false,
+        }),
+        Err(err) => json!({
+            "content": [{ "type": "text", "text": format!("{:#}", err) }],
+            "isError": true,
+        }),
+    })
+}
+
+fn tool_search_sessions(db_path: &Path, args: &Value) -> Result<Value> {
+    let query = string_arg(args, "query")?;
+    let tools = match args.get("tools") {
+        None | Some(Value::Null) => Tool::ALL.to_vec(),
+        Some(value) => value
+            .as_array()
+            .context("`tools` must be an array of tool names")?
+            .iter()
+            .map(|name| {
+                name.as_str()
+                    .and_then(Tool::from_storage)
+                    .with_context(|| format!("Unknown tool {}", name))
+            })
+            .collect::<Result<_>>()?,
+    };
+    let limit = limit_arg(args, "limit", DEFAULT_LIMIT)?;
+
+    let sessions = search_sessions(db_path, &tools, &query)?;
+    Ok(json!(
+        sessions
+            .iter()
+            .take(limit)
+            .map(session_json)
+            .collect::<Vec<_>>()
+    ))
+}
+
+fn tool_get_session(db_path: &Path, args: &Value) -> Result<Value> {
+    let id = string_arg(args, "session_id")?;
+    let offset = usize_arg(args, "message_offset")?.unwrap_or(0);
+    let limit = limit_arg(args, "message_limit", DEFAULT_MESSAGE_LIMIT)?;
+
+    let session =
+        load_session(db_path, &id)?.with_context(|| format!("No session with id {}", id))?;
+    let messages =
+        load_message_previews_for_session(db_path, &id, limit, offset, MESSAGE_MAX_CHARS)?;
+
+    let mut value = session_json(&session);
+    value["messages"] = messages
+        .iter()
+        .map(|message| {
+            json!({
+                "index": message.index,
+                "role": message.role.to_storage(),
+                "timestamp": message.timestamp.to_rfc3339(),
+                "content": message.content_preview,
+                "truncated": message.is_truncated(),
+            })
+        })
+        .collect();
+    Ok(value)
+}
+
+fn tool_list_recent_sessions(db_path: &Path, args: &Value) -> Result<Value> {
+    let project = optional_string_arg(args, "project")?;
+    let limit = limit_arg(args, "limit", DEFAULT_LIMIT)?;
+
+    let sessions = load_sessions(db_path, Tool::ALL)?;
+    Ok(json!(
+        sessions
+            .iter()
+            .filter(|session| {
+                project.as_deref().is_none_or(|project| {
+                    session
+                        .project_path
+                        .as_deref()
+                        .is_some_and(|path| project_matches(path, project))
+                })
+            })
+            .take(limit)
+            .map(session_json)
+            .collect::<Vec<_>>()
+    ))
+}
+
+fn tool_find_sessions_touching_file(db_path: &Path, args: &Value) -> Result<Value> {
+    let path = lookup_path(Path::new(&string_arg(args, "path")?));
+    let limit = limit_arg(args, "limit", DEFAULT_LIMIT)?;
+
+    let activities = sessions_touching_file(db_path, &path)?;
+    Ok(json!(
+        activities
+            .iter()
+            .take(limit)
+            .map(activity_json)
+            .collect::<Vec<_>>()
+    ))
+}
+
+/// Whether `project` names the project at `path`: the same directory, or its
+/// trailing path components (`chronicle`, `work/chronicle`).
+fn project_matches(path: &str, project: &str) -> bool {
+    let path = path.trim_end_matches('/');
+    let project = project.trim_end_matches('/');
+    if project.is_empty() {
+        return false;
+    }
+    path == project
+        || path
+            .strip_suffix(project)
+            .is_some_and(|rest| rest.ends_with('/') && !project.starts_with('/'))
+}
+
+fn session_json(session: &Session) -> Value {
+    json!({
+        "session_id": session.id,
+        "tool": session.tool.to_storage(),
+        "title": session.display_title(),
+        "project_path": session.project_path,
+        "session_file": session.file_path,
+        "first_prompt": session.first_prompt,
+        "started": session.start_time.to_rfc3339(),
+        "last_updated": session.last_updated.to_rfc3339(),
+        "message_count": session.message_count,
+        "git_branch": session.git.branch,
+        "starred": session.starred,
+        "tags": session.tags,
+    })
+}
+
+fn activity_json(activity: &FileActivity) -> Value {
+    let mut value = session_json(&activity.session);
+    value["path"] = json!(activity.path);
+    value["operations"] = activity
+        .operations
+        .iter()
+        .map(|operation| json!(operation.to_storage()))
+        .collect();
+    value["last_touched"] = json!(activity.last_touched.to_rfc3339());
+    value
+}
+
+fn string_arg(args: &Value, name: &str) -> Result<String> {
+    optional_string_arg(args, name)?.with_context(|| format!("Missing `{}` argument", name))
+}
+
+fn optional_string_arg(args: &Value, name: &str) -> Result<Option<String>> {
+    match args.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => anyhow::bail!("`{}` must be a string", name),
+    }
+}
+
+fn usize_arg(args: &Value, name: &str) -> Result<Option<usize>> {
+    match args.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .map(|value| Some(value as usize))
+            .with_context(|| format!("`{}` must be a non-negative integer", name)),
+    }
+}
+
+fn limit_arg(args: &Value, name: &str, default: usize) -> Result<usize> {
+    Ok(usize_arg(args, name)?
+        .unwrap_or(default)
+        .clamp(1, MAX_LIMIT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projects_match_whole_trailing_components() {
+        assert!(project_matches("/home/me/work/chronicle", "chronicle"));
+        assert!(project_matches(
+            "/home/me/work/chronicle",
+            "work/chronicle/"
+        ));
+        assert!(project_matches(
+            "/home/me/work/chronicle",
+            "/home/me/work/chronicle"
+        ));
+        assert!(!project_matches("/home/me/work/chronicle", "icle"));
+        assert!(!project_matches(
+            "/home/me/work/chronicle",
+            "/work/chronicle"
+        ));
+        assert!(!project_matches("/home/me/work/chronicle", ""));
+    }
+
+    #[test]
+    fn notifications_get_no_response_and_unknown_methods_an_error() {
+        let db_path = Path::new("/nonexistent/sessions.db");
+        assert!(
+            handle_message(
+                db_path,
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#
+            )
+            .is_none()
+        );
+
+        let response = handle_message(
+            db_path,
+            r#"{"jsonrpc":"2.0","id":7,"method":"resources/list"}"#,
+        )
+        .unwrap();
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = handle_message(db_path, "{not json").unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+    }
+}
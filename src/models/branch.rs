@@ -0,0 +1,13 @@
+use crate::models::Message;
+
+/// Messages of a conversation branch the user abandoned by rewinding or
+/// editing a prompt.
+///
+/// Abandoned branches are stored with the index, redacted like the active
+/// branch, but are not searched.
+#[derive(Debug, Clone)]
+pub struct ConversationBranch {
+    /// Index of the active-branch message this branch is an alternative to.
+    pub fork_index: usize,
+    pub messages: Vec<Message>,
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// Links between the files of a Claude Code conversation that was continued
+/// or compacted: the new file opens with `summary` events whose `leafUuid`
+/// names the last message of the previous one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Continuation {
+    /// Uuid of the last message of the session.
+    pub leaf_uuid: Option<String>,
+    /// `leafUuid`s of summary events naming messages outside the session.
+    /// Only known to the parser; not read back from the index.
+    #[serde(default)]
+    pub continued_leaves: Vec<String>,
+    /// The session this one continues, resolved by the indexer once every
+    /// session file has been read.
+    pub previous_session_id: Option<String>,
+}
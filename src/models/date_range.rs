@@ -0,0 +1,68 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Period filter shared by the session list and the statistics view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DateRange {
+    #[default]
+    AllTime,
+    LastWeek,
+    LastMonth,
+    LastQuarter,
+    LastYear,
+}
+
+impl DateRange {
+    pub const ALL: &'static [DateRange] = &[
+        DateRange::AllTime,
+        DateRange::LastWeek,
+        DateRange::LastMonth,
+        DateRange::LastQuarter,
+        DateRange::LastYear,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DateRange::AllTime => "All time",
+            DateRange::LastWeek => "Last 7 days",
+            DateRange::LastMonth => "Last 30 days",
+            DateRange::LastQuarter => "Last 90 days",
+            DateRange::LastYear => "Last 365 days",
+        }
+    }
+
+    /// Start of the period relative to `now`, or `None` for all time.
+    pub fn since(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let days = match self {
+            DateRange::AllTime => return None,
+            DateRange::LastWeek => 7,
+            DateRange::LastMonth => 30,
+            DateRange::LastQuarter => 90,
+            DateRange::LastYear => 365,
+        };
+        Some(now - Duration::days(days))
+    }
+
+    /// Whether `timestamp` falls within the period ending at `now`.
+    pub fn contains(&self, timestamp: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        self.since(now).is_none_or(|since| timestamp >= since)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_time_contains_everything() {
+        let now = Utc::now();
+        assert!(DateRange::AllTime.contains(now - Duration::days(10_000), now));
+        assert_eq!(DateRange::AllTime.since(now), None);
+    }
+
+    #[test]
+    fn last_week_excludes_older_timestamps() {
+        let now = Utc::now();
+        assert!(DateRange::LastWeek.contains(now - Duration::days(6), now));
+        assert!(!DateRange::LastWeek.contains(now - Duration::days(8), now));
+    }
+}
@@ -0,0 +1,305 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Component, Path, PathBuf};
+
+use crate::models::Session;
+use crate::models::ToolCall;
+use crate::models::file_change::{ChangeKind, file_changes};
+use crate::utils::exclusions::expand_home;
+
+/// Input keys holding the file a reading tool opens.
+const READ_PATH_KEYS: &[&str] = &["file_path", "filePath", "notebook_path", "path"];
+
+/// Path prefixes of pseudo-files that are never worth indexing.
+const IGNORED_PREFIXES: &[&str] = &["/dev/", "/proc/", "/sys/"];
+
+/// How a tool call used a file.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum FileOperation {
+    Read,
+    Edit,
+    Write,
+    Delete,
+    /// Named in a shell command; the command may have read or changed it.
+    Shell,
+}
+
+impl FileOperation {
+    pub fn from_storage(value: &str) -> Option<Self> {
+        match value {
+            "read" => Some(Self::Read),
+            "edit" => Some(Self::Edit),
+            "write" => Some(Self::Write),
+            "delete" => Some(Self::Delete),
+            "shell" => Some(Self::Shell),
+            _ => None,
+        }
+    }
+
+    pub fn to_storage(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Edit => "edit",
+            Self::Write => "write",
+            Self::Delete => "delete",
+            Self::Shell => "shell",
+        }
+    }
+}
+
+/// A file named by a tool call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileAccess {
+    pub path: String,
+    pub operation: FileOperation,
+}
+
+/// A session that touched a file, as returned by
+/// `database::sessions_touching_file`.
+#[derive(Debug, Clone)]
+pub struct FileActivity {
+    pub session: Session,
+    /// The indexed path that matched.
+    pub path: String,
+    pub operations: Vec<FileOperation>,
+    pub last_touched: DateTime<Utc>,
+}
+
+/// Files a tool call edited, wrote, deleted or read, plus paths named in
+/// shell commands. Paths are returned as written in the input.
+pub fn file_accesses(call: &ToolCall) -> Vec<FileAccess> {
+    let changes = file_changes(call);
+    if !changes.is_empty() {
+        return changes
+            .into_iter()
+            .map(|change| FileAccess {
+                path: change.path,
+                operation: match change.kind {
+                    ChangeKind::Edit => FileOperation::Edit,
+                    ChangeKind::Write => FileOperation::Write,
+                    ChangeKind::Delete => FileOperation::Delete,
+                },
+            })
+            .collect();
+    }
+
+    let input = &call.input;
+    let operation = match call.name.to_ascii_lowercase().as_str() {
+        "read" | "view" | "read_file" | "notebookread" => FileOperation::Read,
+        "notebookedit" => FileOperation::Edit,
+        "bash" | "shell" | "local_shell" | "exec_command" | "run_command" => {
+            return shell_script(input)
+                .map(|script| {
+                    shell_paths(&script)
+                        .into_iter()
+                        .map(|path| FileAccess {
+                            path,
+                            operation: FileOperation::Shell,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+        }
+        _ => return Vec::new(),
+    };
+
+    READ_PATH_KEYS
+        .iter()
+        .find_map(|key| input.get(key)?.as_str())
+        .map(|path| {
+            vec![FileAccess {
+                path: path.to_string(),
+                operation,
+            }]
+        })
+        .unwrap_or_default()
+}
+
+/// Absolute, lexically normalized form of `path`, expanding a leading `~`
+/// and resolving relative paths against `base` when known. The file system
+/// is never consulted: the file may be long gone.
+pub fn resolve_path(path: &str, base: Option<&str>) -> String {
+    let path = expand_home(path);
+    let path = Path::new(&path);
+    let joined = match base {
+        Some(base) if path.is_relative() => Path::new(base).join(path),
+        _ => path.to_path_buf(),
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized.to_string_lossy().into_owned()
+}
+
+/// Path to look up in the index for a file named by a user: absolute when
+/// `file` exists, so an editor's buffer path matches exactly; otherwise the
+/// argument as typed, for a suffix match.
+pub fn lookup_path(file: &Path) -> String {
+    let as_typed = expand_home(&file.to_string_lossy());
+    match std::env::current_dir() {
+        Ok(cwd) if Path::new(&as_typed).exists() => {
+            resolve_path(&as_typed, Some(&cwd.to_string_lossy()))
+        }
+        _ => as_typed,
+    }
+}
+
+/// The script run by a shell tool: a `command` string, or the script inside
+/// a `["bash", "-lc", script]` argv.
+fn shell_script(input: &Value) -> Option<String> {
+    match input.get("command").or_else(|| input.get("cmd"))? {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(argv) => {
+            let argv: Vec<&str> = argv.iter().filter_map(Value::as_str).collect();
+            match argv.as_slice() {
+                [shell, "-lc" | "-c", script] if shell.ends_with("sh") => Some(script.to_string()),
+                _ => Some(argv.join(" ")),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Words of a shell script that look like file paths: explicitly relative
+/// or absolute paths, and names with a file extension.
+fn shell_paths(script: &str) -> Vec<String> {
+    let mut paths: Vec<String> = Vec::new();
+    let words = script
+        .split(|ch: char| {
+            ch.is_whitespace() || matches!(ch, ';' | '|' | '&' | '<' | '>' | '(' | ')')
+        })
+        .map(|word| word.trim_matches(|ch| matches!(ch, '"' | '\'' | ',' | ':')));
+
+    for word in words {
+        if word.is_empty()
+            || word.starts_with('-')
+            || word.contains("://")
+            || word.contains(['$', '=', '*', '?', '{', '`', '\\'])
+            || IGNORED_PREFIXES
+                .iter()
+                .any(|prefix| word.starts_with(prefix))
+        {
+            continue;
+        }
+        let explicit = ["/", "./", "../", "~/"]
+            .iter()
+            .any(|prefix| word.starts_with(prefix))
+            && word.len() > 1;
+        if (explicit || has_extension(word)) && !paths.iter().any(|path| path == word) {
+            paths.push(word.to_string());
+        }
+    }
+    paths
+}
+
+/// Whether the last component of `word` ends in a plausible file extension
+/// (`main.rs`, `.env`), as opposed to a version number such as `1.2.3`.
+fn has_extension(word: &str) -> bool {
+    let name = word.rsplit('/').next().unwrap_or(word);
+    let Some((stem, extension)) = name.rsplit_once('.') else {
+        return false;
+    };
+    let extension_ok = (1..=8).contains(&extension.len())
+        && extension.chars().all(|ch| ch.is_ascii_alphanumeric())
+        && extension.chars().any(|ch| ch.is_ascii_alphabetic());
+    extension_ok && (!stem.is_empty() || name.len() > 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(name: &str, input: Value) -> ToolCall {
+        ToolCall {
+            id: None,
+            name: name.to_string(),
+            input,
+        }
+    }
+
+    fn accesses(name: &str, input: Value) -> Vec<(String, FileOperation)> {
+        file_accesses(&call(name, input))
+            .into_iter()
+            .map(|access| (access.path, access.operation))
+            .collect()
+    }
+
+    #[test]
+    fn edits_reads_and_patches_are_classified() {
+        assert_eq!(
+            accesses(
+                "Edit",
+                json!({"file_path": "/p/src/a.rs", "old_string": "a", "new_string": "b"})
+            ),
+            [("/p/src/a.rs".to_string(), FileOperation::Edit)]
+        );
+        assert_eq!(
+            accesses("Read", json!({"file_path": "/p/README.md"})),
+            [("/p/README.md".to_string(), FileOperation::Read)]
+        );
+        assert_eq!(
+            accesses(
+                "apply_patch",
+                json!("*** Begin Patch\n*** Delete File: old.txt\n*** End Patch")
+            ),
+            [("old.txt".to_string(), FileOperation::Delete)]
+        );
+        assert!(accesses("Grep", json!({"pattern": "fn", "path": "src"})).is_empty());
+    }
+
+    #[test]
+    fn shell_commands_yield_path_like_words() {
+        let paths: Vec<String> = accesses(
+            "Bash",
+            json!({"command": "cat src/main.rs | grep -n foo > /tmp/out.txt 2>&1 && cargo test --release v1.2.3"}),
+        )
+        .into_iter()
+        .map(|(path, operation)| {
+            assert_eq!(operation, FileOperation::Shell);
+            path
+        })
+        .collect();
+        assert_eq!(paths, ["src/main.rs", "/tmp/out.txt"]);
+
+        let argv = accesses(
+            "shell",
+            json!({"command": ["bash", "-lc", "sed -n 1,20p ./build.sh /dev/null https://x.io/a.js"]}),
+        );
+        assert_eq!(argv, [("./build.sh".to_string(), FileOperation::Shell)]);
+    }
+
+    #[test]
+    fn resolve_path_joins_and_normalizes() {
+        assert_eq!(
+            resolve_path("./src/../src/app.rs", Some("/home/me/proj")),
+            "/home/me/proj/src/app.rs"
+        );
+        assert_eq!(resolve_path("/etc/hosts", Some("/home/me")), "/etc/hosts");
+        assert_eq!(resolve_path("src/app.rs", None), "src/app.rs");
+    }
+
+    #[test]
+    fn resolve_path_expands_the_home_directory() {
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(
+            resolve_path("~/.bashrc", Some("/home/me/proj")),
+            format!("{}/.bashrc", home.trim_end_matches('/'))
+        );
+        assert_eq!(
+            resolve_path("~user/notes.md", Some("/proj")),
+            "/proj/~user/notes.md"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use serde_json::Value;
+
+use crate::models::ToolCall;
+use crate::utils::diff::unified_diff;
+
+/// Unchanged lines kept around each change in rendered diffs.
+const DIFF_CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Edit,
+    /// A whole file written, new or overwritten.
+    Write,
+    Delete,
+}
+
+/// One file modified by a tool call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    /// Unified diff hunks, without file headers.
+    pub hunks: String,
+}
+
+impl FileChange {
+    /// The change as a unified diff with `---`/`+++` file headers.
+    pub fn to_unified_diff(&self) -> String {
+        let (old, new) = match self.kind {
+            ChangeKind::Write => ("/dev/null".to_string(), format!("b/{}", self.path)),
+            ChangeKind::Delete => (format!("a/{}", self.path), "/dev/null".to_string()),
+            ChangeKind::Edit => (format!("a/{}", self.path), format!("b/{}", self.path)),
+        };
+        format!("--- {}\n+++ {}\n{}", old, new, self.hunks)
+    }
+}
+
+/// A file and how often the session changed it, for the "Files changed"
+/// summary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    pub path: String,
+    pub changes: usize,
+    pub deleted: bool,
+}
+
+/// File changes described by a tool call's input: Claude Code
+/// `Edit`/`MultiEdit`/`Write`, OpenCode `edit`/`write` and Codex
+/// `apply_patch`. Other tools yield nothing.
+pub fn file_changes(call: &ToolCall) -> Vec<FileChange> {
+    let input = &call.input;
+    let text = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| input.get(key)?.as_str())
+            .map(str::to_string)
+    };
+    let path = text(&["file_path", "filePath", "path"]);
+
+    match call.name.to_ascii_lowercase().as_str() {
+        "edit" | "edit_file" => {
+            let (Some(path), Some(old), Some(new)) = (
+                path,
+                text(&["old_string", "oldString"]),
+                text(&["new_string", "newString"]),
+            ) else {
+                return Vec::new();
+            };
+            vec![edit(path, &old, &new)]
+        }
+        "multiedit" => {
+            let Some(path) = path else {
+                return Vec::new();
+            };
+            let hunks: String = input
+                .get("edits")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|edit| {
+                    let old = edit.get("old_string")?.as_str()?;
+                    let new = edit.get("new_string")?.as_str()?;
+                    Some(unified_diff(old, new, DIFF_CONTEXT))
+                })
+                .collect();
+            vec![FileChange {
+                path,
+                kind: ChangeKind::Edit,
+                hunks,
+            }]
+        }
+        "write" | "write_file" | "create_file" => {
+            let (Some(path), Some(content)) = (path, text(&["content"])) else {
+                return Vec::new();
+            };
+            vec![FileChange {
+                path,
+                kind: ChangeKind::Write,
+                hunks: unified_diff("", &content, 0),
+            }]
+        }
+        // Codex also runs `apply_patch` through its shell tool.
+        "apply_patch" | "shell" | "exec_command" | "local_shell" => patch_text(input)
+            .map(|patch| parse_patch(&patch))
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Files changed across a session's tool calls, in order of first change.
+pub fn changed_files(calls: &[ToolCall]) -> Vec<ChangedFile> {
+    let mut files: Vec<ChangedFile> = Vec::new();
+    for change in calls.iter().flat_map(file_changes) {
+        let deleted = change.kind == ChangeKind::Delete;
+        match files.iter_mut().find(|file| file.path == change.path) {
+            Some(file) => {
+                file.changes += 1;
+                file.deleted = deleted;
+            }
+            None => files.push(ChangedFile {
+                path: change.path,
+                changes: 1,
+                deleted,
+            }),
+        }
+    }
+    files
+}
+
+fn edit(path: String, old: &str, new: &str) -> FileChange {
+    FileChange {
+        path,
+        kind: ChangeKind::Edit,
+        hunks: unified_diff(old, new, DIFF_CONTEXT),
+    }
+}
+
+/// The `*** Begin Patch` envelope in an `apply_patch` input: the raw input,
+/// an `input` field, or the last argument of a `["apply_patch", patch]` argv.
+fn patch_text(input: &Value) -> Option<String> {
+    let candidate = match input {
+        Value::String(text) => Some(text.as_str()),
+        _ => input
+            .get("input")
+            .and_then(Value::as_str)
+            .or_else(|| match input.get("command")? {
+                Value::Array(argv) => argv.last()?.as_str(),
+                Value::String(command) => Some(command.as_str()),
+                _ => None,
+            }),
+    }?;
+    let start = candidate.find("*** Begin Patch")?;
+    Some(candidate[start..].to_string())
+}
+
+/// Split a Codex patch envelope into per-file changes.
+///
+/// `*** Update File:` sections already hold unified hunks; added files list
+/// their content as `+` lines.
+fn parse_patch(patch: &str) -> Vec<FileChange> {
+    let mut changes: Vec<FileChange> = Vec::new();
+    for line in patch.lines() {
+        if let Some(path) = line.strip_prefix("*** Update File: ") {
+            changes.push(section(path, ChangeKind::Edit));
+        } else if let Some(path) = line.strip_prefix("*** Add File: ") {
+            changes.push(section(path, ChangeKind::Write));
+        } else if let Some(path) = line.strip_prefix("*** Delete File: ") {
+            changes.push(section(path, ChangeKind::Delete));
+        } else if let Some(path) = line.strip_prefix("*** Move to: ") {
+            if let Some(change) = changes.last_mut() {
+                change.path = path.trim().to_string();
+            }
+        } else if line.starts_with("*** ") {
+            // `*** Begin Patch`, `*** End Patch`, `*** End of File`
+        } else if let Some(change) = changes.last_mut() {
+            if change.kind == ChangeKind::Edit && change.hunks.is_empty() && !line.starts_with("@@")
+            {
+                change.hunks.push_str("@@\n");
+            }
+            change.hunks.push_str(line);
+            change.hunks.push('\n');
+        }
+    }
+
+    for change in &mut changes {
+        if change.kind == ChangeKind::Write {
+            let content: String = change
+                .hunks
+                .lines()
+                .map(|line| format!("{}\n", line.strip_prefix('+').unwrap_or(line)))
+                .collect();
+            change.hunks = unified_diff("", &content, 0);
+        }
+    }
+    changes
+}
+
+fn section(path: &str, kind: ChangeKind) -> FileChange {
+    FileChange {
+        path: path.trim().to_string(),
+        kind,
+        hunks: String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(name: &str, input: Value) -> ToolCall {
+        ToolCall {
+            id: None,
+            name: name.to_string(),
+            input,
+        }
+    }
+
+    #[test]
+    fn claude_and_opencode_edits_become_diffs() {
+        let claude = call(
+            "Edit",
+            json!({"file_path": "src/app.rs", "old_string": "let a = 1;", "new_string": "let a = 2;"}),
+        );
+        let opencode = call(
+            "edit",
+            json!({"filePath": "src/app.rs", "oldString": "let a = 1;", "newString": "let a = 2;"}),
+        );
+        for change in [file_changes(&claude), file_changes(&opencode)] {
+            assert_eq!(
+                change[0].to_unified_diff(),
+                "--- a/src/app.rs\n+++ b/src/app.rs\n@@ -1 +1 @@\n-let a = 1;\n+let a = 2;\n"
+            );
+        }
+    }
+
+    #[test]
+    fn write_is_a_new_file_diff() {
+        let change = &file_changes(&call(
+            "Write",
+            json!({"file_path": "notes.md", "content": "one\ntwo\n"}),
+        ))[0];
+        assert_eq!(change.kind, ChangeKind::Write);
+        assert!(
+            change
+                .to_unified_diff()
+                .starts_with("--- /dev/null\n+++ b/notes.md\n")
+        );
+        assert!(change.hunks.ends_with("+one\n+two\n"));
+    }
+
+    #[test]
+    fn codex_patch_is_split_per_file() {
+        let patch = "*** Begin Patch\n*** Update File: src/lib.rs\n@@ fn main\n-old\n+new\n\
+                     *** Add File: README.md\n+# Title\n*** Delete File: old.txt\n*** End Patch";
+        let changes = file_changes(&call("apply_patch", Value::String(patch.to_string())));
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["src/lib.rs", "README.md", "old.txt"]);
+        assert_eq!(changes[0].hunks, "@@ fn main\n-old\n+new\n");
+        assert_eq!(changes[1].hunks, "@@ -0,0 +1 @@\n+# Title\n");
+        assert_eq!(changes[2].kind, ChangeKind::Delete);
+
+        let shell = call("shell", json!({"command": ["apply_patch", patch]}));
+        assert_eq!(file_changes(&shell).len(), 3);
+    }
+
+    #[test]
+    fn changed_files_counts_changes_per_path() {
+        let calls = [
+            call(
+                "Edit",
+                json!({"file_path": "a.rs", "old_string": "1", "new_string": "2"}),
+            ),
+            call("Read", json!({"file_path": "b.rs"})),
+            call("Write", json!({"file_path": "c.rs", "content": "x"})),
+            call(
+                "Edit",
+                json!({"file_path": "a.rs", "old_string": "2", "new_string": "3"}),
+            ),
+        ];
+        let files = changed_files(&calls);
+        assert_eq!(files.len(), 2);
+        assert_eq!((files[0].path.as_str(), files[0].changes), ("a.rs", 2));
+        assert_eq!((files[1].path.as_str(), files[1].changes), ("c.rs", 1));
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// Git state of the repository a session ran in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitInfo {
+    pub branch: Option<String>,
+    /// Full commit hash of `HEAD`.
+    pub commit: Option<String>,
+    /// URL of the `origin` remote.
+    pub remote: Option<String>,
+}
+
+impl GitInfo {
+    pub fn is_empty(&self) -> bool {
+        self.branch.is_none() && self.commit.is_none() && self.remote.is_none()
+    }
+
+    /// One-line summary such as `feature-x @ 1a2b3c4`.
+    pub fn summary(&self) -> Option<String> {
+        let commit = self.commit.as_deref().map(short_commit);
+        match (self.branch.as_deref(), commit) {
+            (Some(branch), Some(commit)) => Some(format!("{} @ {}", branch, commit)),
+            (Some(branch), None) => Some(branch.to_string()),
+            (None, Some(commit)) => Some(format!("detached @ {}", commit)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Abbreviate a commit hash to the 7 characters git shows by default.
+pub fn short_commit(commit: &str) -> &str {
+    commit.get(..7).unwrap_or(commit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_combines_branch_and_short_commit() {
+        let info = GitInfo {
+            branch: Some("feature-x".to_string()),
+            commit: Some("1a2b3c4d5e6f".to_string()),
+            remote: None,
+        };
+        assert_eq!(info.summary().as_deref(), Some("feature-x @ 1a2b3c4"));
+        assert_eq!(GitInfo::default().summary(), None);
+    }
+}
@@ -14,8 +14,13 @@ pub struct Message {
 pub enum Role {
     User,
     Assistant,
+    /// Model reasoning shown before an assistant answer.
+    Thinking,
     ToolCall,
     ToolResult,
+    /// Text inserted by the tool rather than typed or generated, such as the
+    /// summary that replaces history when a conversation is compacted.
+    System,
 }
 
 impl Role {
@@ -24,8 +29,10 @@ impl Role {
         match self {
             Role::User => "#3584e4",
             Role::Assistant => "#26a269",
+            Role::Thinking => "#9141ac",
             Role::ToolCall => "#e66100",
             Role::ToolResult => "#1c71d8",
+            Role::System => "#77767b",
         }
     }
 
@@ -34,8 +41,10 @@ impl Role {
         match s.to_lowercase().as_str() {
             "user" => Some(Role::User),
             "assistant" => Some(Role::Assistant),
+            "thinking" => Some(Role::Thinking),
             "toolcall" | "tool_call" => Some(Role::ToolCall),
             "toolresult" | "tool_result" => Some(Role::ToolResult),
+            "system" => Some(Role::System),
             _ => None,
         }
     }
@@ -46,18 +55,33 @@ impl Role {
         match self {
             Role::User => "user",
             Role::Assistant => "assistant",
+            Role::Thinking => "thinking",
             Role::ToolCall => "toolcall",
             Role::ToolResult => "toolresult",
+            Role::System => "system",
         }
     }
 
+    /// Whether the message is tool traffic rather than conversation.
+    pub fn is_tool(&self) -> bool {
+        matches!(self, Role::ToolCall | Role::ToolResult)
+    }
+
+    /// Whether the message is part of the visible exchange: what the user
+    /// wrote and what the assistant answered.
+    pub fn is_conversation(&self) -> bool {
+        matches!(self, Role::User | Role::Assistant)
+    }
+
     /// Return a display label for the role.
     pub fn label(&self) -> &'static str {
         match self {
             Role::User => "USER",
             Role::Assistant => "ASSISTANT",
+            Role::Thinking => "THINKING",
             Role::ToolCall => "TOOL CALL",
             Role::ToolResult => "TOOL RESULT",
+            Role::System => "SYSTEM",
         }
     }
 
@@ -66,8 +90,10 @@ impl Role {
         match self {
             Role::User => "role-user",
             Role::Assistant => "role-assistant",
+            Role::Thinking => "role-thinking",
             Role::ToolCall => "role-toolcall",
             Role::ToolResult => "role-toolresult",
+            Role::System => "role-system",
         }
     }
 }
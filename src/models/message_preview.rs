@@ -4,6 +4,8 @@ use crate::models::Role;
 
 #[derive(Debug, Clone)]
 pub struct MessagePreview {
+    /// Position of the message in its session (`messages.message_index`).
+    pub index: usize,
     pub role: Role,
     pub content_preview: String,
     pub content_len: usize,
@@ -14,4 +16,10 @@ impl MessagePreview {
     pub fn is_truncated(&self) -> bool {
         self.content_preview.chars().count() < self.content_len
     }
+
+    /// Replace the preview with the message's full content.
+    pub fn expand(&mut self, content: String) {
+        self.content_len = content.chars().count();
+        self.content_preview = content;
+    }
 }
@@ -1,7 +1,26 @@
+pub mod branch;
+pub mod continuation;
+pub mod date_range;
+pub mod file_access;
+pub mod file_change;
+pub mod git;
 pub mod message;
 pub mod message_preview;
+pub mod permalink;
 pub mod session;
+pub mod tag;
+pub mod tool_call;
+pub mod usage;
 
+pub use branch::ConversationBranch;
+pub use continuation::Continuation;
+pub use date_range::DateRange;
+pub use file_access::{FileActivity, FileOperation};
+pub use file_change::ChangedFile;
+pub use git::GitInfo;
 pub use message::{Message, Role};
 pub use message_preview::MessagePreview;
+pub use permalink::{MessageBookmark, Permalink, parse_search_uri, search_uri};
 pub use session::{Session, Tool};
+pub use tool_call::{ToolCall, ToolResult};
+pub use usage::{SessionUsage, TokenUsage};
@@ -0,0 +1,163 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::{MessagePreview, Session};
+
+/// URI scheme registered by the desktop file.
+pub const URI_SCHEME: &str = "sessions-chronicle";
+
+/// A link to a session, or to one of its messages:
+/// `sessions-chronicle://session/<id>#<index>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permalink {
+    pub session_id: String,
+    pub message_index: Option<usize>,
+}
+
+impl Permalink {
+    pub fn to_session(session_id: &str) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            message_index: None,
+        }
+    }
+
+    pub fn to_message(session_id: &str, message_index: usize) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            message_index: Some(message_index),
+        }
+    }
+
+    /// Parse a permalink URI; `None` for any other URI or a malformed one.
+    pub fn parse(uri: &str) -> Option<Self> {
+        let (scheme, rest) = uri.trim().split_once("://")?;
+        if !scheme.eq_ignore_ascii_case(URI_SCHEME) {
+            return None;
+        }
+        let (path, fragment) = match rest.split_once('#') {
+            Some((path, fragment)) => (path, Some(fragment)),
+            None => (rest, None),
+        };
+        let session_id = path.strip_prefix("session/")?.trim_end_matches('/');
+        if session_id.is_empty() || session_id.contains('/') {
+            return None;
+        }
+        let message_index = match fragment {
+            Some("") | None => None,
+            Some(fragment) => Some(fragment.parse().ok()?),
+        };
+        Some(Self {
+            session_id: session_id.to_string(),
+            message_index,
+        })
+    }
+}
+
+impl fmt::Display for Permalink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://session/{}", URI_SCHEME, self.session_id)?;
+        if let Some(index) = self.message_index {
+            write!(f, "#{}", index)?;
+        }
+        Ok(())
+    }
+}
+
+/// Link opening the app with `query` in its search bar:
+/// `sessions-chronicle://search/<query>`, percent-encoded.
+pub fn search_uri(query: &str) -> String {
+    let mut uri = format!("{}://search/", URI_SCHEME);
+    for byte in query.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+/// Query of a search link; `None` for any other URI or a malformed one.
+pub fn parse_search_uri(uri: &str) -> Option<String> {
+    let (scheme, rest) = uri.trim().split_once("://")?;
+    if !scheme.eq_ignore_ascii_case(URI_SCHEME) {
+        return None;
+    }
+    let encoded = rest.strip_prefix("search/")?.as_bytes();
+    let mut query = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        if encoded[i] == b'%' {
+            let hex = std::str::from_utf8(encoded.get(i + 1..i + 3)?).ok()?;
+            query.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            query.push(encoded[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(query).ok()
+}
+
+/// A bookmarked message, as returned by `database::load_bookmarks`.
+#[derive(Debug, Clone)]
+pub struct MessageBookmark {
+    pub session: Session,
+    pub message: MessagePreview,
+    pub bookmarked_at: DateTime<Utc>,
+}
+
+impl MessageBookmark {
+    pub fn permalink(&self) -> Permalink {
+        Permalink::to_message(&self.session.id, self.message.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permalinks_round_trip() {
+        let link = Permalink::to_message("rollout-2025-01-01-abc", 57);
+        assert_eq!(
+            link.to_string(),
+            "sessions-chronicle://session/rollout-2025-01-01-abc#57"
+        );
+        assert_eq!(Permalink::parse(&link.to_string()), Some(link));
+
+        assert_eq!(
+            Permalink::parse("sessions-chronicle://session/abc/"),
+            Some(Permalink {
+                session_id: "abc".to_string(),
+                message_index: None,
+            })
+        );
+    }
+
+    #[test]
+    fn search_links_round_trip() {
+        let uri = search_uri("café tool:codex 50%");
+        assert_eq!(
+            uri,
+            "sessions-chronicle://search/caf%C3%A9%20tool%3Acodex%2050%25"
+        );
+        assert_eq!(
+            parse_search_uri(&uri).as_deref(),
+            Some("café tool:codex 50%")
+        );
+        assert_eq!(parse_search_uri("sessions-chronicle://search/%2"), None);
+        assert_eq!(parse_search_uri("sessions-chronicle://session/abc"), None);
+        assert_eq!(Permalink::parse(&uri), None);
+    }
+
+    #[test]
+    fn parse_rejects_other_and_malformed_uris() {
+        assert_eq!(Permalink::parse("https://session/abc#1"), None);
+        assert_eq!(Permalink::parse("sessions-chronicle://session/"), None);
+        assert_eq!(Permalink::parse("sessions-chronicle://message/abc"), None);
+        assert_eq!(Permalink::parse("sessions-chronicle://session/abc#x"), None);
+    }
+}
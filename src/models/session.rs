@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::models::{Continuation, GitInfo, SessionUsage};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
     pub id: String,
@@ -12,6 +14,40 @@ pub struct Session {
     pub last_updated: DateTime<Utc>,
     #[serde(default)]
     pub first_prompt: Option<String>,
+    #[serde(default)]
+    pub usage: SessionUsage,
+    #[serde(default)]
+    pub git: GitInfo,
+    /// Session that spawned this one as a subagent. Child sessions are
+    /// shown inside their parent rather than in the session list.
+    #[serde(default)]
+    pub parent_session_id: Option<String>,
+    #[serde(default)]
+    pub continuation: Continuation,
+    /// Marked by the user; kept across reindexing.
+    #[serde(default)]
+    pub starred: bool,
+    /// User tags, normalized and sorted; kept across reindexing.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Title recorded by the tool, or derived from the first substantive
+    /// prompt when the tool has none.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Title set by the user; kept across reindexing.
+    #[serde(default)]
+    pub custom_title: Option<String>,
+}
+
+impl Session {
+    /// Title to show for the session: the user's, the tool's or derived
+    /// one, then the first prompt.
+    pub fn display_title(&self) -> Option<&str> {
+        [&self.custom_title, &self.title, &self.first_prompt]
+            .into_iter()
+            .filter_map(|title| title.as_deref().map(str::trim))
+            .find(|title| !title.is_empty())
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -30,7 +66,6 @@ impl Tool {
         Tool::MistralVibe,
     ];
 
-    #[allow(dead_code)]
     pub fn color(&self) -> &'static str {
         match self {
             Tool::ClaudeCode => "#3584e4",
@@ -0,0 +1,52 @@
+/// Canonical form of a user tag: without the leading `#`, lowercased.
+/// Returns `None` for text that cannot be a tag.
+pub fn normalize_tag(raw: &str) -> Option<String> {
+    let tag = raw.trim().trim_start_matches('#').to_lowercase();
+    let valid = !tag.is_empty()
+        && tag
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || matches!(ch, '-' | '_' | '/' | '.'));
+    valid.then_some(tag)
+}
+
+/// Tags typed as a list separated by spaces or commas (`#bugfix, design`),
+/// normalized, in order, without duplicates. Invalid entries are dropped.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text
+        .split(|ch: char| ch.is_whitespace() || ch == ',')
+        .filter_map(normalize_tag)
+    {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Tags as shown to the user: `#bugfix #design`.
+pub fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| format!("#{}", tag))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_normalized_and_deduplicated() {
+        assert_eq!(
+            parse_tags("#BugFix, design  #incident-42 bugfix #"),
+            ["bugfix", "design", "incident-42"]
+        );
+        assert_eq!(normalize_tag("not a tag"), None);
+        assert_eq!(normalize_tag("wip!"), None);
+        assert_eq!(
+            format_tags(&["bugfix".to_string(), "design".to_string()]),
+            "#bugfix #design"
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use serde_json::Value;
+
+/// Longest summary shown on a collapsed tool card, in characters.
+const SUMMARY_MAX_CHARS: usize = 120;
+
+/// Input keys holding the file a tool operates on. A bare `path` is only a
+/// fallback: search tools use it for the directory they search in.
+const PATH_KEYS: &[&str] = &["file_path", "filePath", "notebook_path"];
+
+/// Input keys whose value best describes a call, tried in order.
+const DETAIL_KEYS: &[&str] = &[
+    "command",
+    "cmd",
+    "pattern",
+    "query",
+    "url",
+    "description",
+    "prompt",
+];
+
+/// A tool invocation, stored as the content of a `Role::ToolCall` message.
+///
+/// The stored form is a `name<TAB>id` header line followed by the input:
+/// pretty-printed JSON, or the raw text for tools that take a plain string
+/// (Codex `apply_patch`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    pub id: Option<String>,
+    pub name: String,
+    pub input: Value,
+}
+
+/// The output of a tool invocation, stored as a `Role::ToolResult` message.
+///
+/// The stored form is an `ok<TAB>id` or `error<TAB>id` header line followed
+/// by the raw output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolResult {
+    /// Id of the `ToolCall` this result answers.
+    pub call_id: Option<String>,
+    pub is_error: bool,
+    pub output: String,
+}
+
+impl ToolCall {
+    pub fn to_content(&self) -> String {
+        let body = match &self.input {
+            Value::String(text) => text.clone(),
+            Value::Null => String::new(),
+            input => serde_json::to_string_pretty(input).unwrap_or_default(),
+        };
+        format!("{}\n{}", header(&self.name, self.id.as_deref()), body)
+    }
+
+    pub fn from_content(content: &str) -> Option<Self> {
+        let (name, id, body) = split_header(content)?;
+        let input = if body.trim().is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.to_string()))
+        };
+        Some(Self {
+            id,
+            name: name.to_string(),
+            input,
+        })
+    }
+
+    fn file_path(&self) -> Option<&str> {
+        PATH_KEYS
+            .iter()
+            .find_map(|key| self.input.get(key)?.as_str())
+            .or_else(|| patch_path(self.input.as_str()?))
+    }
+
+    /// One-line description such as `Bash: cargo test` or `Edit src/app.rs`.
+    pub fn summary(&self) -> String {
+        let summary = if let Some(path) = self.file_path() {
+            format!("{} {}", self.name, path)
+        } else if let Some(detail) = self.detail() {
+            format!("{}: {}", self.name, detail)
+        } else if let Some(path) = self.input.get("path").and_then(Value::as_str) {
+            format!("{} {}", self.name, path)
+        } else {
+            self.name.clone()
+        };
+        truncate_line(&summary)
+    }
+
+    fn detail(&self) -> Option<String> {
+        if let Some(text) = self.input.as_str() {
+            return Some(text.to_string());
+        }
+        DETAIL_KEYS
+            .iter()
+            .find_map(|key| match self.input.get(key)? {
+                Value::String(text) => Some(text.clone()),
+                // Codex shell calls pass argv, usually `["bash", "-lc", "<script>"]`.
+                Value::Array(argv) => {
+                    let argv: Vec<&str> = argv.iter().filter_map(Value::as_str).collect();
+                    match argv.as_slice() {
+                        [shell, "-lc" | "-c", script] if shell.ends_with("sh") => {
+                            Some(script.to_string())
+                        }
+                        _ => Some(argv.join(" ")),
+                    }
+                }
+                _ => None,
+            })
+    }
+}
+
+impl ToolResult {
+    pub fn to_content(&self) -> String {
+        let status = if self.is_error { "error" } else { "ok" };
+        format!(
+            "{}\n{}",
+            header(status, self.call_id.as_deref()),
+            self.output
+        )
+    }
+
+    pub fn from_content(content: &str) -> Option<Self> {
+        let (status, call_id, output) = split_header(content)?;
+        Some(Self {
+            call_id,
+            is_error: status == "error",
+            output: output.to_string(),
+        })
+    }
+}
+
+/// Id in the header of a stored tool call or tool result, without parsing
+/// the rest of the content.
+pub fn call_id(content: &str) -> Option<&str> {
+    let header = content.split('\n').next()?;
+    let (_, id) = header.split_once('\t')?;
+    (!id.is_empty()).then_some(id)
+}
+
+fn header(first: &str, id: Option<&str>) -> String {
+    match id {
+        Some(id) => format!("{}\t{}", first, id),
+        None => first.to_string(),
+    }
+}
+
+fn split_header(content: &str) -> Option<(&str, Option<String>, &str)> {
+    let (header, body) = content.split_once('\n').unwrap_or((content, ""));
+    let (first, id) = match header.split_once('\t') {
+        Some((first, id)) => (first, Some(id.to_string()).filter(|id| !id.is_empty())),
+        None => (header, None),
+    };
+    (!first.is_empty()).then_some((first, id, body))
+}
+
+/// First file named in an `apply_patch` envelope.
+fn patch_path(patch: &str) -> Option<&str> {
+    patch.lines().find_map(|line| {
+        ["*** Update File: ", "*** Add File: ", "*** Delete File: "]
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix))
+            .map(str::trim)
+    })
+}
+
+fn truncate_line(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default().trim();
+    if line.chars().count() <= SUMMARY_MAX_CHARS && !text.trim().contains('\n') {
+        return line.to_string();
+    }
+    let mut truncated: String = line.chars().take(SUMMARY_MAX_CHARS).collect();
+    truncated.push('\u{2026}');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(name: &str, input: Value) -> ToolCall {
+        ToolCall {
+            id: Some("toolu_1".to_string()),
+            name: name.to_string(),
+            input,
+        }
+    }
+
+    #[test]
+    fn tool_call_round_trips_through_content() {
+        let original = call("Bash", json!({"command": "cargo test", "timeout": 60}));
+        let content = original.to_content();
+        assert!(content.starts_with("Bash\ttoolu_1\n"));
+        assert_eq!(ToolCall::from_content(&content), Some(original));
+
+        let patch = ToolCall {
+            id: None,
+            name: "apply_patch".to_string(),
+            input: Value::String("*** Begin Patch\n*** Update File: src/app.rs".to_string()),
+        };
+        assert_eq!(ToolCall::from_content(&patch.to_content()), Some(patch));
+    }
+
+    #[test]
+    fn summary_prefers_paths_then_commands() {
+        assert_eq!(
+            call(
+                "Edit",
+                json!({"file_path": "src/app.rs", "old_string": "a"})
+            )
+            .summary(),
+            "Edit src/app.rs"
+        );
+        assert_eq!(
+            call("Bash", json!({"command": "cargo test\ncargo clippy"})).summary(),
+            "Bash: cargo test\u{2026}"
+        );
+        assert_eq!(
+            call("shell", json!({"command": ["bash", "-lc", "ls -la"]})).summary(),
+            "shell: ls -la"
+        );
+        assert_eq!(
+            call(
+                "apply_patch",
+                json!("*** Begin Patch\n*** Add File: notes.md\n+hi")
+            )
+            .summary(),
+            "apply_patch notes.md"
+        );
+        assert_eq!(
+            call("Grep", json!({"pattern": "fn main", "path": "src"})).summary(),
+            "Grep: fn main"
+        );
+        assert_eq!(
+            call("TodoWrite", json!({"todos": []})).summary(),
+            "TodoWrite"
+        );
+    }
+
+    #[test]
+    fn tool_result_round_trips_error_status() {
+        let result = ToolResult {
+            call_id: Some("toolu_1".to_string()),
+            is_error: true,
+            output: "No such file\nor directory".to_string(),
+        };
+        assert_eq!(call_id(&result.to_content()), Some("toolu_1"));
+        assert_eq!(ToolResult::from_content(&result.to_content()), Some(result));
+        assert_eq!(ToolResult::from_content(""), None);
+    }
+}
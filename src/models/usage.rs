@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+/// Token counts reported by a tool for one or more model calls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    /// Uncached input tokens.
+    pub input: u64,
+    pub output: u64,
+    pub cache_read: u64,
+    pub cache_write: u64,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_read + self.cache_write
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_read += other.cache_read;
+        self.cache_write += other.cache_write;
+    }
+}
+
+/// Models, token totals and cost for a whole session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionUsage {
+    /// Models in order of first use.
+    pub models: Vec<String>,
+    pub tokens: TokenUsage,
+    /// Cost in USD, either reported by the tool or estimated from the local
+    /// price table. `None` when no call used a known model.
+    pub cost_usd: Option<f64>,
+    /// Whether any of `cost_usd` was estimated rather than reported.
+    pub cost_is_estimate: bool,
+}
+
+impl SessionUsage {
+    /// Record usage of `model`, estimating its cost from the price table.
+    pub fn record(&mut self, model: Option<&str>, tokens: TokenUsage) {
+        let cost = model.and_then(|model| crate::pricing::estimate_cost(model, &tokens));
+        self.record_with_cost(model, tokens, cost);
+        self.cost_is_estimate |= cost.is_some();
+    }
+
+    /// Record usage of `model` with a cost already known (e.g. reported by the tool).
+    pub fn record_with_cost(&mut self, model: Option<&str>, tokens: TokenUsage, cost: Option<f64>) {
+        if let Some(model) = model.map(str::trim)
+            && !model.is_empty()
+            && !self.models.iter().any(|known| known == model)
+        {
+            self.models.push(model.to_string());
+        }
+
+        self.tokens.add(&tokens);
+
+        if let Some(cost) = cost {
+            self.cost_usd = Some(self.cost_usd.unwrap_or(0.0) + cost);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty() && self.tokens.is_empty() && self.cost_usd.is_none()
+    }
+}
+
+/// Format a token count compactly (`950`, `12.3k`, `4.56M`).
+pub fn format_token_count(count: u64) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", count as f64 / 1_000.0),
+        _ => format!("{:.2}M", count as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: u64, output: u64) -> TokenUsage {
+        TokenUsage {
+            input,
+            output,
+            ..TokenUsage::default()
+        }
+    }
+
+    #[test]
+    fn record_accumulates_tokens_and_unique_models() {
+        let mut usage = SessionUsage::default();
+        usage.record(Some("claude-sonnet-4-5"), tokens(1_000, 100));
+        usage.record(Some("claude-haiku-4-5"), tokens(500, 50));
+        usage.record(Some("claude-sonnet-4-5"), tokens(1_000, 100));
+
+        assert_eq!(usage.models, vec!["claude-sonnet-4-5", "claude-haiku-4-5"]);
+        assert_eq!(usage.tokens, tokens(2_500, 250));
+        assert!(usage.cost_usd.unwrap() > 0.0);
+        assert!(usage.cost_is_estimate);
+    }
+
+    #[test]
+    fn reported_costs_are_not_estimates() {
+        let mut usage = SessionUsage::default();
+        usage.record_with_cost(Some("claude-sonnet-4-5"), tokens(1_000, 100), Some(0.25));
+        assert_eq!(usage.cost_usd, Some(0.25));
+        assert!(!usage.cost_is_estimate);
+
+        usage.record(Some("claude-sonnet-4-5"), tokens(1_000, 100));
+        assert!(usage.cost_is_estimate);
+    }
+
+    #[test]
+    fn unknown_models_leave_cost_empty() {
+        let mut usage = SessionUsage::default();
+        usage.record(Some("local-model"), tokens(10, 10));
+        assert_eq!(usage.cost_usd, None);
+        assert!(!usage.is_empty());
+    }
+
+    #[test]
+    fn format_token_count_uses_compact_units() {
+        assert_eq!(format_token_count(950), "950");
+        assert_eq!(format_token_count(12_345), "12.3k");
+        assert_eq!(format_token_count(4_560_000), "4.56M");
+    }
+}
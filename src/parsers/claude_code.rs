@@ -1,22 +1,38 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
-use crate::models::{Message, Role, Session, Tool};
+use crate::models::{
+    Continuation, ConversationBranch, GitInfo, Message, Role, Session, SessionUsage, TokenUsage,
+    Tool, ToolCall, ToolResult,
+};
 
 pub struct ClaudeCodeParser;
 
 impl ClaudeCodeParser {
-    pub fn parse(&self, file_path: &Path) -> Result<(Session, Vec<Message>)> {
-        let file = File::open(file_path).context("Failed to open session file")?;
-        let reader = BufReader::new(file);
+    /// Parse a session file, keeping the messages of the active branch and,
+    /// separately, those of branches abandoned by rewinding or editing a
+    /// prompt.
+    ///
+    /// Usage still counts every branch: abandoned attempts were billed too.
+    /// Subagent (sidechain) files carry their parent's `sessionId`; they are
+    /// identified by file name and linked to that parent instead.
+    pub fn parse(
+        &self,
+        file_path: &Path,
+    ) -> Result<(Session, Vec<Message>, Vec<ConversationBranch>)> {
+        let events = Self::read_events(file_path)?;
+        let tree = EventTree::new(&events);
 
         let mut earliest_timestamp: Option<DateTime<Utc>> = None;
         let mut latest_timestamp: Option<DateTime<Utc>> = None;
         let mut project_path = None;
+        // Latest branch wins: sessions often start on main and switch.
+        let mut git_branch: Option<String> = None;
 
         let file_stem_id = file_path
             .file_stem()
@@ -24,16 +40,12 @@ impl ClaudeCodeParser {
             .map(|s| s.to_string());
         let mut session_id = None;
         let mut has_user_message = false;
-        let mut messages = Vec::new();
-
-        for (index, line) in reader.lines().enumerate() {
-            let line = line.context("Failed to read line")?;
-            if line.trim().is_empty() {
-                continue;
-            }
-
-            let event: Value = serde_json::from_str(&line).context("Failed to parse JSON")?;
+        // Streamed responses repeat the same usage on every content block,
+        // so keep one entry per API message id (latest wins).
+        let mut usage_by_message: Vec<(Option<String>, TokenUsage)> = Vec::new();
+        let mut usage_slots: HashMap<String, usize> = HashMap::new();
 
+        for event in &events {
             if session_id.is_none() {
                 session_id = event
                     .get("sessionId")
@@ -48,6 +60,13 @@ impl ClaudeCodeParser {
                     .map(|s| s.to_string());
             }
 
+            if let Some(branch) = event.get("gitBranch").and_then(|v| v.as_str())
+                && !branch.is_empty()
+                && branch != "HEAD"
+            {
+                git_branch = Some(branch.to_string());
+            }
+
             let event_type = event.get("type").and_then(|v| v.as_str());
             let is_message_like = matches!(event_type, Some("user") | Some("assistant"));
 
@@ -70,8 +89,14 @@ impl ClaudeCodeParser {
                 });
             }
 
-            if let Some(msg) = Self::parse_event(&event, index) {
-                messages.push(msg);
+            if let Some((message_id, model, tokens)) = Self::parse_usage(event) {
+                match usage_slots.get(&message_id) {
+                    Some(&slot) => usage_by_message[slot] = (model, tokens),
+                    None => {
+                        usage_slots.insert(message_id, usage_by_message.len());
+                        usage_by_message.push((model, tokens));
+                    }
+                }
             }
         }
 
@@ -83,15 +108,35 @@ impl ClaudeCodeParser {
             anyhow::bail!("Session contains no user messages");
         }
 
-        let final_session_id = session_id
-            .or(file_stem_id)
-            .unwrap_or_else(|| "unknown".to_string());
-        for message in &mut messages {
+        let is_sidechain = events
+            .iter()
+            .any(|event| event.get("isSidechain").and_then(|v| v.as_bool()) == Some(true));
+        let (session_id, parent_session_id) = match (is_sidechain, file_stem_id) {
+            (true, Some(stem)) => (Some(stem), session_id),
+            (_, file_stem_id) => (session_id.or(file_stem_id), None),
+        };
+        let final_session_id = session_id.unwrap_or_else(|| "unknown".to_string());
+        let active_events = tree.active_events();
+        let continuation = Self::continuation(&events, &active_events);
+        let (mut messages, active_sources) = Self::collect_messages(&events, active_events);
+        let mut branches = Self::branches(&events, &tree, &active_sources);
+        for message in branches
+            .iter_mut()
+            .flat_map(|branch| &mut branch.messages)
+            .chain(&mut messages)
+        {
             message.session_id = final_session_id.clone();
         }
 
         let last_updated = latest_timestamp.unwrap_or(start_time);
         let first_prompt = crate::parsers::extract_first_prompt(&messages);
+        let title =
+            Self::summary_title(&events).or_else(|| crate::parsers::title::derive_title(&messages));
+
+        let mut usage = SessionUsage::default();
+        for (model, tokens) in usage_by_message {
+            usage.record(model.as_deref(), tokens);
+        }
 
         Ok((
             Session {
@@ -99,28 +144,165 @@ impl ClaudeCodeParser {
                 tool: Tool::ClaudeCode,
                 project_path,
                 start_time,
-                message_count: messages.len(),
+                message_count: crate::parsers::conversation_len(&messages),
                 file_path: file_path.to_str().unwrap().to_string(),
                 last_updated,
                 first_prompt,
+                usage,
+                git: GitInfo {
+                    branch: git_branch,
+                    ..GitInfo::default()
+                },
+                parent_session_id,
+                continuation,
+                starred: false,
+                tags: Vec::new(),
+                title,
+                custom_title: None,
             },
             messages,
+            branches,
         ))
     }
 
-    fn parse_event(event: &Value, index: usize) -> Option<Message> {
-        let event_type = event.get("type")?.as_str()?;
+    /// Branches abandoned by rewinding or editing a prompt, each placed at
+    /// the active-branch prompt it is an alternative to. `active_sources`
+    /// holds the event position of each active-branch message.
+    fn branches(
+        events: &[Value],
+        tree: &EventTree,
+        active_sources: &[usize],
+    ) -> Vec<ConversationBranch> {
+        let mut branches = Vec::new();
+        for fork in tree.forks() {
+            // The active prompt starts its message run; anything later is a
+            // fallback for prompts that produced no message.
+            let Some(fork_index) = active_sources
+                .iter()
+                .position(|&source| source >= fork.active)
+            else {
+                continue;
+            };
+            for alternative in fork.alternatives {
+                let (messages, _) = Self::collect_messages(events, tree.branch_events(alternative));
+                if !messages.is_empty() {
+                    branches.push(ConversationBranch {
+                        fork_index,
+                        messages,
+                    });
+                }
+            }
+        }
+        branches
+    }
 
-        let (role, content) = match event_type {
-            "user" => {
-                let content = Self::extract_content(event.get("message")?.get("content")?)?;
-                (Role::User, content)
+    /// The last message on the active branch, and the messages of other
+    /// files named by `summary` events: a continued or compacted
+    /// conversation starts a new file that summarizes the previous one.
+    fn continuation(events: &[Value], active_events: &[usize]) -> Continuation {
+        let uuid = |event: &Value| {
+            event
+                .get("uuid")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        let own: HashSet<&str> = events
+            .iter()
+            .filter_map(|event| event.get("uuid")?.as_str())
+            .collect();
+
+        let mut continued_leaves: Vec<String> = Vec::new();
+        for event in events {
+            if event.get("type").and_then(|v| v.as_str()) == Some("summary")
+                && let Some(leaf) = event.get("leafUuid").and_then(|v| v.as_str())
+                && !own.contains(leaf)
+                && !continued_leaves.iter().any(|known| known == leaf)
+            {
+                continued_leaves.push(leaf.to_string());
             }
-            "assistant" => {
-                let content = Self::extract_content(event.get("message")?.get("content")?)?;
-                (Role::Assistant, content)
+        }
+
+        Continuation {
+            leaf_uuid: active_events
+                .iter()
+                .rev()
+                .map(|&position| &events[position])
+                .filter(|event| {
+                    matches!(
+                        event.get("type").and_then(|v| v.as_str()),
+                        Some("user") | Some("assistant")
+                    )
+                })
+                .find_map(uuid),
+            continued_leaves,
+            previous_session_id: None,
+        }
+    }
+
+    /// The latest `summary` event describing this file's own messages;
+    /// summaries of other files describe the conversation it continues.
+    fn summary_title(events: &[Value]) -> Option<String> {
+        let own: HashSet<&str> = events
+            .iter()
+            .filter_map(|event| event.get("uuid")?.as_str())
+            .collect();
+        events
+            .iter()
+            .rev()
+            .filter(|event| event.get("type").and_then(|v| v.as_str()) == Some("summary"))
+            .filter(|event| {
+                event
+                    .get("leafUuid")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|leaf| own.contains(leaf))
+            })
+            .find_map(|event| event.get("summary")?.as_str())
+            .and_then(crate::parsers::title::clean_title)
+    }
+
+    fn read_events(file_path: &Path) -> Result<Vec<Value>> {
+        let file = File::open(file_path).context("Failed to open session file")?;
+        let reader = BufReader::new(file);
+
+        let mut events = Vec::new();
+        for line in reader.lines() {
+            let line = line.context("Failed to read line")?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            events.push(serde_json::from_str(&line).context("Failed to parse JSON")?);
+        }
+        Ok(events)
+    }
+
+    /// Messages of the events at `positions`, numbered from zero, with the
+    /// position of the event each message came from.
+    fn collect_messages(events: &[Value], positions: Vec<usize>) -> (Vec<Message>, Vec<usize>) {
+        let mut messages = Vec::new();
+        let mut sources = Vec::new();
+        for position in positions {
+            for mut msg in Self::parse_event(&events[position]) {
+                msg.index = messages.len();
+                messages.push(msg);
+                sources.push(position);
             }
-            _ => return None,
+        }
+        (messages, sources)
+    }
+
+    /// Messages carried by one event: its reasoning, its text, then any tool
+    /// calls or tool results among its content blocks.
+    fn parse_event(event: &Value) -> Vec<Message> {
+        let is_compact_summary =
+            event.get("isCompactSummary").and_then(|v| v.as_bool()) == Some(true);
+        let (role, tool_role) = match event.get("type").and_then(|v| v.as_str()) {
+            Some("user") if is_compact_summary => (Role::System, Role::ToolResult),
+            Some("user") => (Role::User, Role::ToolResult),
+            Some("assistant") => (Role::Assistant, Role::ToolCall),
+            _ => return Vec::new(),
+        };
+        let Some(content) = event.get("message").and_then(|m| m.get("content")) else {
+            return Vec::new();
         };
 
         let timestamp = event
@@ -135,13 +317,103 @@ impl ClaudeCodeParser {
             .unwrap_or("unknown")
             .to_string();
 
-        Some(Message {
-            session_id,
-            index,
-            role,
-            content,
-            timestamp,
-        })
+        let thinking = Self::extract_thinking(content).map(|text| (Role::Thinking, text));
+        let text = Self::extract_content(content).map(|text| (role, text));
+        let tools = content
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|block| Self::extract_tool_block(block, tool_role))
+            .map(|content| (tool_role, content));
+
+        thinking
+            .into_iter()
+            .chain(text)
+            .chain(tools)
+            .map(|(role, content)| Message {
+                session_id: session_id.clone(),
+                index: 0,
+                role,
+                content,
+                timestamp,
+            })
+            .collect()
+    }
+
+    /// Stored content of a `tool_use` block (assistant) or `tool_result` block
+    /// (user).
+    fn extract_tool_block(block: &Value, role: Role) -> Option<String> {
+        let block_type = block.get("type")?.as_str()?;
+        let id = |key: &str| block.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+        match (role, block_type) {
+            (Role::ToolCall, "tool_use") => Some(
+                ToolCall {
+                    id: id("id"),
+                    name: block.get("name")?.as_str()?.to_string(),
+                    input: block.get("input").cloned().unwrap_or(Value::Null),
+                }
+                .to_content(),
+            ),
+            (Role::ToolResult, "tool_result") => {
+                let output = match block.get("content") {
+                    Some(Value::String(text)) => text.clone(),
+                    Some(Value::Array(parts)) => parts
+                        .iter()
+                        .filter_map(|part| part.get("text")?.as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    _ => String::new(),
+                };
+                Some(
+                    ToolResult {
+                        call_id: id("tool_use_id"),
+                        is_error: block
+                            .get("is_error")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false),
+                        output,
+                    }
+                    .to_content(),
+                )
+            }
+            _ => None,
+        }
+    }
+
+    /// Extract `(message id, model, tokens)` from an assistant event.
+    fn parse_usage(event: &Value) -> Option<(String, Option<String>, TokenUsage)> {
+        if event.get("type")?.as_str()? != "assistant" {
+            return None;
+        }
+
+        let message = event.get("message")?;
+        let usage = message.get("usage")?;
+        let count = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+
+        let message_id = message
+            .get("id")
+            .or_else(|| event.get("uuid"))
+            .and_then(|v| v.as_str())?
+            .to_string();
+
+        // Placeholder entries (API errors, interruptions) use "<synthetic>".
+        let model = message
+            .get("model")
+            .and_then(|v| v.as_str())
+            .filter(|model| !model.starts_with('<'))
+            .map(str::to_string);
+
+        Some((
+            message_id,
+            model,
+            TokenUsage {
+                input: count("input_tokens"),
+                output: count("output_tokens"),
+                cache_read: count("cache_read_input_tokens"),
+                cache_write: count("cache_creation_input_tokens"),
+            },
+        ))
     }
 
     fn parse_timestamp(s: &str) -> Result<DateTime<Utc>> {
@@ -164,7 +436,6 @@ impl ClaudeCodeParser {
                     let block_type = block.get("type")?.as_str()?;
                     match block_type {
                         "text" => block.get("text")?.as_str().map(|s| s.to_string()),
-                        "thinking" => block.get("thinking")?.as_str().map(|s| s.to_string()),
                         _ => None,
                     }
                 })
@@ -178,6 +449,168 @@ impl ClaudeCodeParser {
 
         None
     }
+
+    /// Text of the `thinking` blocks in a content array. Redacted thinking
+    /// carries no readable text and is skipped.
+    fn extract_thinking(value: &Value) -> Option<String> {
+        let parts: Vec<&str> = value
+            .as_array()?
+            .iter()
+            .filter(|block| block.get("type").and_then(|v| v.as_str()) == Some("thinking"))
+            .filter_map(|block| block.get("thinking")?.as_str())
+            .filter(|text| !text.trim().is_empty())
+            .collect();
+
+        (!parts.is_empty()).then(|| parts.join("\n\n"))
+    }
+}
+
+/// A point where the user rewound or edited a prompt: several prompts share
+/// one parent event.
+struct Fork {
+    /// The prompt whose subtree holds the most recent event.
+    active: usize,
+    alternatives: Vec<usize>,
+}
+
+/// `uuid`/`parentUuid` links between the events of a session file, by
+/// event position.
+struct EventTree {
+    children: Vec<Vec<usize>>,
+    /// Most recent (highest) position in each event's subtree.
+    latest: Vec<usize>,
+    /// Prompts sharing a parent, in file order, per parent position.
+    prompt_children: Vec<Vec<usize>>,
+}
+
+impl EventTree {
+    fn new(events: &[Value]) -> Self {
+        let positions: HashMap<&str, usize> = events
+            .iter()
+            .enumerate()
+            .filter_map(|(position, event)| Some((event.get("uuid")?.as_str()?, position)))
+            .collect();
+
+        let mut children = vec![Vec::new(); events.len()];
+        let mut prompt_children = vec![Vec::new(); events.len()];
+        let mut parents = vec![None; events.len()];
+        for (position, event) in events.iter().enumerate() {
+            // Parents always precede their children; anything else is
+            // treated as unlinked so the tree stays acyclic.
+            let Some(parent) = event
+                .get("parentUuid")
+                .and_then(|v| v.as_str())
+                .and_then(|parent| positions.get(parent).copied())
+                .filter(|&parent| parent < position)
+            else {
+                continue;
+            };
+            parents[position] = Some(parent);
+            children[parent].push(position);
+            if Self::is_prompt(event) {
+                prompt_children[parent].push(position);
+            }
+        }
+
+        let mut latest: Vec<usize> = (0..events.len()).collect();
+        for position in (0..events.len()).rev() {
+            if let Some(parent) = parents[position] {
+                latest[parent] = latest[parent].max(latest[position]);
+            }
+        }
+
+        Self {
+            children,
+            latest,
+            prompt_children,
+        }
+    }
+
+    /// A prompt typed by the user, as opposed to tool results, meta
+    /// messages and sidechain traffic.
+    fn is_prompt(event: &Value) -> bool {
+        let flag = |key: &str| event.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        if event.get("type").and_then(|v| v.as_str()) != Some("user")
+            || flag("isMeta")
+            || flag("isSidechain")
+            || flag("isCompactSummary")
+        {
+            return false;
+        }
+        match event.get("message").and_then(|m| m.get("content")) {
+            Some(Value::String(_)) => true,
+            Some(Value::Array(blocks)) => blocks
+                .iter()
+                .all(|block| block.get("type").and_then(|v| v.as_str()) != Some("tool_result")),
+            _ => false,
+        }
+    }
+
+    /// Forks below linked events. Edits of the very first prompt create a
+    /// second root, which cannot be told apart from unlinked events in
+    /// older files, so they are not detected.
+    fn forks(&self) -> Vec<Fork> {
+        self.prompt_children
+            .iter()
+            .filter(|prompts| prompts.len() > 1)
+            .filter_map(|prompts| {
+                let active = *prompts.iter().max_by_key(|&&prompt| self.latest[prompt])?;
+                Some(Fork {
+                    active,
+                    alternatives: prompts
+                        .iter()
+                        .copied()
+                        .filter(|&prompt| prompt != active)
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Positions of `root` and its descendants, skipping the subtrees of
+    /// `excluded` events, in file order.
+    fn subtree(&self, root: usize, excluded: &HashSet<usize>) -> Vec<usize> {
+        let mut positions = Vec::new();
+        let mut stack = vec![root];
+        while let Some(position) = stack.pop() {
+            positions.push(position);
+            stack.extend(
+                self.children[position]
+                    .iter()
+                    .filter(|child| !excluded.contains(child)),
+            );
+        }
+        positions.sort_unstable();
+        positions
+    }
+
+    fn abandoned_roots(&self) -> HashSet<usize> {
+        self.forks()
+            .into_iter()
+            .flat_map(|fork| fork.alternatives)
+            .collect()
+    }
+
+    /// Events outside every abandoned branch, in file order. Events without
+    /// links are always kept.
+    fn active_events(&self) -> Vec<usize> {
+        let abandoned_roots = self.abandoned_roots();
+        let abandoned: HashSet<usize> = abandoned_roots
+            .iter()
+            .flat_map(|&root| self.subtree(root, &HashSet::new()))
+            .collect();
+        (0..self.latest.len())
+            .filter(|position| !abandoned.contains(position))
+            .collect()
+    }
+
+    /// Events of the branch starting at `root`, without the branches
+    /// abandoned inside it.
+    fn branch_events(&self, root: usize) -> Vec<usize> {
+        let mut excluded = self.abandoned_roots();
+        excluded.remove(&root);
+        self.subtree(root, &excluded)
+    }
 }
 
 #[cfg(test)]
@@ -254,7 +687,7 @@ mod tests {
         ]);
 
         let parser = ClaudeCodeParser;
-        let (session, messages) = parser.parse(file.path()).unwrap();
+        let (session, messages, _) = parser.parse(file.path()).unwrap();
 
         let expected_start = ClaudeCodeParser::parse_timestamp("2024-01-01T00:00:00Z").unwrap();
         let expected_end = ClaudeCodeParser::parse_timestamp("2024-01-01T00:00:01Z").unwrap();
@@ -285,7 +718,7 @@ mod tests {
         ]);
 
         let parser = ClaudeCodeParser;
-        let (session, messages) = parser.parse(file.path()).unwrap();
+        let (session, messages, _) = parser.parse(file.path()).unwrap();
 
         assert_eq!(session.id, "event-123");
         assert_eq!(messages.len(), 2);
@@ -302,29 +735,180 @@ mod tests {
         ]);
 
         let parser = ClaudeCodeParser;
-        let (session, messages) = parser.parse(file.path()).unwrap();
+        let (session, messages, _) = parser.parse(file.path()).unwrap();
 
         assert_eq!(messages.len(), 2);
         assert_eq!(session.message_count, 2);
     }
 
     #[test]
-    fn parse_ignores_tool_events() {
+    fn parse_keeps_tool_traffic_out_of_message_count() {
         let file = create_temp_session(&[
             r#"{"type":"user","timestamp":"2024-01-01T00:00:00Z","sessionId":"session-123","message":{"content":"Hello"}}"#,
-            r#"{"type":"assistant","timestamp":"2024-01-01T00:00:01Z","sessionId":"session-123","message":{"content":[{"type":"tool_use","name":"bash","input":{"command":"ls"}}]}}"#,
+            r#"{"type":"assistant","timestamp":"2024-01-01T00:00:01Z","sessionId":"session-123","message":{"content":[{"type":"text","text":"Listing"},{"type":"tool_use","id":"toolu_1","name":"Bash","input":{"command":"ls"}}]}}"#,
             r#"{"type":"system","timestamp":"2024-01-01T00:00:02Z","subtype":"local_command","command":["ls","-la"],"stdout":"file1.txt\nfile2.txt"}"#,
+            r#"{"type":"user","timestamp":"2024-01-01T00:00:02Z","sessionId":"session-123","message":{"content":[{"type":"tool_result","tool_use_id":"toolu_1","content":[{"type":"text","text":"file1.txt"}],"is_error":true}]}}"#,
             r#"{"type":"assistant","timestamp":"2024-01-01T00:00:03Z","sessionId":"session-123","message":{"content":"Here are the files"}}"#,
         ]);
 
         let parser = ClaudeCodeParser;
-        let (session, messages) = parser.parse(file.path()).unwrap();
+        let (session, messages, _) = parser.parse(file.path()).unwrap();
+
+        let roles: Vec<Role> = messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            [
+                Role::User,
+                Role::Assistant,
+                Role::ToolCall,
+                Role::ToolResult,
+                Role::Assistant
+            ]
+        );
+        assert_eq!(session.message_count, 3);
+        assert_eq!(messages[1].content, "Listing");
+        assert_eq!(messages[2].index, 2);
+
+        let call = ToolCall::from_content(&messages[2].content).unwrap();
+        assert_eq!(call.summary(), "Bash: ls");
+        let result = ToolResult::from_content(&messages[3].content).unwrap();
+        assert_eq!(result.call_id.as_deref(), Some("toolu_1"));
+        assert!(result.is_error);
+        assert_eq!(result.output, "file1.txt");
+    }
 
-        assert_eq!(messages.len(), 2);
+    #[test]
+    fn parse_splits_thinking_from_the_answer() {
+        let file = create_temp_session(&[
+            r#"{"type":"user","timestamp":"2024-01-01T00:00:00Z","sessionId":"session-123","message":{"content":"Hello"}}"#,
+            r#"{"type":"assistant","timestamp":"2024-01-01T00:00:01Z","sessionId":"session-123","message":{"content":[{"type":"thinking","thinking":"The user greets me."},{"type":"redacted_thinking","data":"xyz"},{"type":"text","text":"Hi!"}]}}"#,
+        ]);
+
+        let parser = ClaudeCodeParser;
+        let (session, messages, _) = parser.parse(file.path()).unwrap();
+
+        let roles: Vec<Role> = messages.iter().map(|m| m.role).collect();
+        assert_eq!(roles, [Role::User, Role::Thinking, Role::Assistant]);
+        assert_eq!(messages[1].content, "The user greets me.");
+        assert_eq!(messages[2].content, "Hi!");
         assert_eq!(session.message_count, 2);
-        assert_eq!(messages[0].role, Role::User);
-        assert_eq!(messages[0].content, "Hello");
-        assert_eq!(messages[1].role, Role::Assistant);
-        assert_eq!(messages[1].content, "Here are the files");
+    }
+
+    #[test]
+    fn parse_keeps_the_active_branch_after_a_rewind() {
+        let file = create_temp_session(&[
+            r#"{"type":"user","uuid":"u1","parentUuid":null,"timestamp":"2024-01-01T00:00:00Z","sessionId":"s","message":{"content":"Fix the bug"}}"#,
+            r#"{"type":"assistant","uuid":"a1","parentUuid":"u1","timestamp":"2024-01-01T00:00:01Z","sessionId":"s","message":{"content":[{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"ls"}}]}}"#,
+            r#"{"type":"user","uuid":"r1","parentUuid":"a1","timestamp":"2024-01-01T00:00:02Z","sessionId":"s","message":{"content":[{"type":"tool_result","tool_use_id":"t1","content":"a.rs"}]}}"#,
+            r#"{"type":"user","uuid":"u2","parentUuid":"r1","timestamp":"2024-01-01T00:00:03Z","sessionId":"s","message":{"content":"Use a regex"}}"#,
+            r#"{"type":"assistant","uuid":"a2","parentUuid":"u2","timestamp":"2024-01-01T00:00:04Z","sessionId":"s","message":{"content":"Done with a regex"}}"#,
+            r#"{"type":"user","uuid":"u3","parentUuid":"r1","timestamp":"2024-01-01T00:00:05Z","sessionId":"s","message":{"content":"Use a parser"}}"#,
+            r#"{"type":"assistant","uuid":"a3","parentUuid":"u3","timestamp":"2024-01-01T00:00:06Z","sessionId":"s","message":{"content":"Done with a parser"}}"#,
+        ]);
+
+        let parser = ClaudeCodeParser;
+        let (session, messages, branches) = parser.parse(file.path()).unwrap();
+        let contents: Vec<&str> = messages
+            .iter()
+            .filter(|m| m.role.is_conversation())
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(
+            contents,
+            ["Fix the bug", "Use a parser", "Done with a parser"]
+        );
+        assert_eq!(session.message_count, 3);
+
+        assert_eq!(branches.len(), 1);
+        assert_eq!(messages[branches[0].fork_index].content, "Use a parser");
+        let abandoned: Vec<&str> = branches[0]
+            .messages
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(abandoned, ["Use a regex", "Done with a regex"]);
+    }
+
+    #[test]
+    fn parse_links_sidechain_files_to_their_parent() {
+        let mut file = tempfile::Builder::new()
+            .prefix("agent-a1b2")
+            .suffix(".jsonl")
+            .tempfile()
+            .unwrap();
+        for line in [
+            r#"{"type":"user","isSidechain":true,"agentId":"a1b2","timestamp":"2024-01-01T00:00:00Z","sessionId":"parent-1","message":{"content":"Find the parser"}}"#,
+            r#"{"type":"assistant","isSidechain":true,"agentId":"a1b2","timestamp":"2024-01-01T00:00:01Z","sessionId":"parent-1","message":{"content":"Found it"}}"#,
+        ] {
+            writeln!(file, "{}", line).unwrap();
+        }
+
+        let (session, messages, _) = ClaudeCodeParser.parse(file.path()).unwrap();
+
+        assert!(session.id.starts_with("agent-a1b2"));
+        assert_eq!(session.parent_session_id.as_deref(), Some("parent-1"));
+        assert!(messages.iter().all(|m| m.session_id == session.id));
+    }
+
+    #[test]
+    fn parse_reads_continuation_links_and_compaction_summaries() {
+        let file = create_temp_session(&[
+            r#"{"type":"summary","summary":"Parser fix","leafUuid":"old-leaf"}"#,
+            r#"{"type":"summary","summary":"This file","leafUuid":"u3"}"#,
+            r#"{"type":"user","isCompactSummary":true,"uuid":"u1","timestamp":"2024-01-02T00:00:00Z","sessionId":"next","message":{"content":"This session is being continued from a previous conversation."}}"#,
+            r#"{"type":"user","uuid":"u2","parentUuid":"u1","timestamp":"2024-01-02T00:00:01Z","sessionId":"next","message":{"content":"Carry on"}}"#,
+            r#"{"type":"assistant","uuid":"u3","parentUuid":"u2","timestamp":"2024-01-02T00:00:02Z","sessionId":"next","message":{"content":"Done"}}"#,
+            r#"{"type":"system","uuid":"u4","parentUuid":"u3","timestamp":"2024-01-02T00:00:03Z","sessionId":"next","content":"Stop hook ran"}"#,
+        ]);
+
+        let (session, messages, _) = ClaudeCodeParser.parse(file.path()).unwrap();
+
+        assert_eq!(session.continuation.continued_leaves, ["old-leaf"]);
+        assert_eq!(session.continuation.leaf_uuid.as_deref(), Some("u3"));
+        let roles: Vec<Role> = messages.iter().map(|m| m.role).collect();
+        assert_eq!(roles, [Role::System, Role::User, Role::Assistant]);
+        assert_eq!(session.first_prompt.as_deref(), Some("Carry on"));
+        assert_eq!(session.title.as_deref(), Some("This file"));
+        assert_eq!(session.message_count, 2);
+    }
+
+    #[test]
+    fn parse_collects_usage_once_per_api_message() {
+        let file = create_temp_session(&[
+            r#"{"type":"user","timestamp":"2024-01-01T00:00:00Z","sessionId":"session-123","message":{"content":"Hello"}}"#,
+            r#"{"type":"assistant","timestamp":"2024-01-01T00:00:01Z","sessionId":"session-123","message":{"id":"msg_1","model":"claude-sonnet-4-5-20250929","content":[{"type":"thinking","thinking":"Hmm"}],"usage":{"input_tokens":10,"output_tokens":5,"cache_read_input_tokens":1000,"cache_creation_input_tokens":200}}}"#,
+            r#"{"type":"assistant","timestamp":"2024-01-01T00:00:02Z","sessionId":"session-123","message":{"id":"msg_1","model":"claude-sonnet-4-5-20250929","content":[{"type":"text","text":"Hi"}],"usage":{"input_tokens":10,"output_tokens":50,"cache_read_input_tokens":1000,"cache_creation_input_tokens":200}}}"#,
+            r#"{"type":"assistant","timestamp":"2024-01-01T00:00:03Z","sessionId":"session-123","message":{"id":"msg_2","model":"<synthetic>","content":"Interrupted","usage":{"input_tokens":0,"output_tokens":0}}}"#,
+        ]);
+
+        let parser = ClaudeCodeParser;
+        let (session, _, _) = parser.parse(file.path()).unwrap();
+
+        assert_eq!(session.usage.models, vec!["claude-sonnet-4-5-20250929"]);
+        assert_eq!(
+            session.usage.tokens,
+            TokenUsage {
+                input: 10,
+                output: 50,
+                cache_read: 1000,
+                cache_write: 200,
+            }
+        );
+        assert!(session.usage.cost_usd.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn parse_records_latest_git_branch() {
+        let file = create_temp_session(&[
+            r#"{"type":"user","timestamp":"2024-01-01T00:00:00Z","sessionId":"session-123","gitBranch":"main","message":{"content":"Hello"}}"#,
+            r#"{"type":"assistant","timestamp":"2024-01-01T00:00:01Z","sessionId":"session-123","gitBranch":"feature-x","message":{"content":"Switched"}}"#,
+            r#"{"type":"assistant","timestamp":"2024-01-01T00:00:02Z","sessionId":"session-123","gitBranch":"","message":{"content":"Done"}}"#,
+        ]);
+
+        let parser = ClaudeCodeParser;
+        let (session, _, _) = parser.parse(file.path()).unwrap();
+
+        assert_eq!(session.git.branch.as_deref(), Some("feature-x"));
+        assert_eq!(session.git.commit, None);
     }
 }
@@ -5,7 +5,10 @@ use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
-use crate::models::{Message, Role, Session, Tool};
+use crate::models::{
+    Continuation, GitInfo, Message, Role, Session, SessionUsage, TokenUsage, Tool, ToolCall,
+    ToolResult,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -72,9 +75,14 @@ impl CodexParser {
             .and_then(|v| v.as_str())
             .map(str::to_string);
 
+        let git = payload.get("git").map(Self::parse_git).unwrap_or_default();
+
         let mut last_updated = start_time;
         let mut messages = Vec::new();
         let mut has_user_message = false;
+        let mut usage = SessionUsage::default();
+        let mut current_model: Option<String> = None;
+        let mut previous_total = TokenUsage::default();
 
         for line in lines {
             let line = line.context("Failed to read line")?;
@@ -91,7 +99,14 @@ impl CodexParser {
             };
 
             let event_type = event.get("type").and_then(|v| v.as_str());
-            if event_type != Some("event_msg") {
+            if event_type == Some("turn_context") {
+                if let Some(model) = event
+                    .get("payload")
+                    .and_then(|payload| payload.get("model"))
+                    .and_then(|v| v.as_str())
+                {
+                    current_model = Some(model.to_string());
+                }
                 continue;
             }
 
@@ -100,21 +115,42 @@ impl CodexParser {
                 None => continue,
             };
 
-            let message_type = payload.get("type").and_then(|v| v.as_str());
-            let (role, content) = match message_type {
-                Some("user_message") => {
-                    let content = match payload.get("message").and_then(|v| v.as_str()) {
-                        Some(content) => content.to_string(),
-                        None => continue,
+            let (role, content) = match event_type {
+                Some("response_item") => match Self::parse_tool_item(payload) {
+                    Some(tool_message) => tool_message,
+                    None => continue,
+                },
+                Some("event_msg") => {
+                    let message_type = payload.get("type").and_then(|v| v.as_str());
+                    if message_type == Some("token_count") {
+                        if let Some(total) = Self::parse_total_token_usage(payload) {
+                            // Totals are cumulative; attribute each increase to the
+                            // model active for the current turn.
+                            let delta = TokenUsage {
+                                input: total.input.saturating_sub(previous_total.input),
+                                output: total.output.saturating_sub(previous_total.output),
+                                cache_read: total
+                                    .cache_read
+                                    .saturating_sub(previous_total.cache_read),
+                                cache_write: 0,
+                            };
+                            if !delta.is_empty() {
+                                usage.record(current_model.as_deref(), delta);
+                            }
+                            previous_total = total;
+                        }
+                        continue;
+                    }
+
+                    let role = match message_type {
+                        Some("user_message") => Role::User,
+                        Some("agent_message") => Role::Assistant,
+                        _ => continue,
                     };
-                    (Role::User, content)
-                }
-                Some("agent_message") => {
-                    let content = match payload.get("message").and_then(|v| v.as_str()) {
-                        Some(content) => content.to_string(),
+                    match payload.get("message").and_then(|v| v.as_str()) {
+                        Some(content) => (role, content.to_string()),
                         None => continue,
-                    };
-                    (Role::Assistant, content)
+                    }
                 }
                 _ => continue,
             };
@@ -158,6 +194,7 @@ impl CodexParser {
 
         // first_prompt is populated via extract_first_prompt() in parsers/mod.rs.
         let first_prompt = crate::parsers::extract_first_prompt(&messages);
+        let title = crate::parsers::title::derive_title(&messages);
 
         Ok((
             Session {
@@ -165,15 +202,110 @@ impl CodexParser {
                 tool: Tool::Codex,
                 project_path,
                 start_time,
-                message_count: messages.len(),
+                message_count: crate::parsers::conversation_len(&messages),
                 file_path: file_path.to_str().unwrap_or_default().to_string(),
                 last_updated,
                 first_prompt,
+                usage,
+                git,
+                parent_session_id: None,
+                continuation: Continuation::default(),
+                starred: false,
+                tags: Vec::new(),
+                title,
+                custom_title: None,
             },
             messages,
         ))
     }
 
+    /// Read the `git` object of a `session_meta` payload.
+    fn parse_git(git: &Value) -> GitInfo {
+        let field = |key: &str| {
+            git.get(key)
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        GitInfo {
+            branch: field("branch"),
+            commit: field("commit_hash"),
+            remote: field("repository_url"),
+        }
+    }
+
+    /// Tool call or tool output carried by a `response_item` payload.
+    ///
+    /// `function_call` arguments are a JSON string; `custom_tool_call` input
+    /// (`apply_patch`) is free text. Outputs may wrap the text in
+    /// `{"output", "metadata": {"exit_code"}}`.
+    fn parse_tool_item(payload: &Value) -> Option<(Role, String)> {
+        let call_id = payload
+            .get("call_id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        match payload.get("type")?.as_str()? {
+            "function_call" | "custom_tool_call" => {
+                let input = match payload.get("arguments").or_else(|| payload.get("input")) {
+                    Some(Value::String(raw)) => {
+                        serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.clone()))
+                    }
+                    Some(value) => value.clone(),
+                    None => Value::Null,
+                };
+                let call = ToolCall {
+                    id: call_id,
+                    name: payload.get("name")?.as_str()?.to_string(),
+                    input,
+                };
+                Some((Role::ToolCall, call.to_content()))
+            }
+            "function_call_output" | "custom_tool_call_output" => {
+                let raw = match payload.get("output")? {
+                    Value::String(raw) => raw.clone(),
+                    // Newer rollouts nest `{ "content": ..., "success": bool }`.
+                    value => value.to_string(),
+                };
+                let wrapped: Option<Value> = serde_json::from_str(&raw).ok();
+                let output = wrapped
+                    .as_ref()
+                    .and_then(|value| value.get("output"))
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+                    .unwrap_or(raw);
+                let is_error = wrapped
+                    .as_ref()
+                    .and_then(|value| value.get("metadata")?.get("exit_code")?.as_i64())
+                    .is_some_and(|code| code != 0);
+                let result = ToolResult {
+                    call_id,
+                    is_error,
+                    output,
+                };
+                Some((Role::ToolResult, result.to_content()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Read `info.total_token_usage` from a `token_count` payload.
+    ///
+    /// Codex counts cached tokens as part of `input_tokens`; they are split out
+    /// here so they can be priced separately.
+    fn parse_total_token_usage(payload: &Value) -> Option<TokenUsage> {
+        let total = payload.get("info")?.get("total_token_usage")?;
+        let count = |key: &str| total.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+        let cached = count("cached_input_tokens");
+
+        Some(TokenUsage {
+            input: count("input_tokens").saturating_sub(cached),
+            output: count("output_tokens"),
+            cache_read: cached,
+            cache_write: 0,
+        })
+    }
+
     fn parse_timestamp(value: &str) -> Result<DateTime<Utc>> {
         DateTime::parse_from_rfc3339(value)
             .map(|dt| dt.with_timezone(&Utc))
@@ -302,4 +434,90 @@ mod tests {
         let logs = writer.contents();
         assert!(logs.contains("Failed to parse event timestamp not-a-ts"));
     }
+
+    #[test]
+    fn parse_accumulates_token_counts_per_model() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        for line in [
+            r#"{"type":"session_meta","payload":{"id":"usage-session","timestamp":"2026-01-18T02:01:28Z","cwd":"/tmp"}}"#,
+            r#"{"type":"turn_context","payload":{"model":"gpt-5-codex"}}"#,
+            r#"{"type":"event_msg","timestamp":"2026-01-18T02:01:29Z","payload":{"type":"user_message","message":"Hi"}}"#,
+            r#"{"type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1000,"cached_input_tokens":400,"output_tokens":100}}}}"#,
+            r#"{"type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1000,"cached_input_tokens":400,"output_tokens":100}}}}"#,
+            r#"{"type":"turn_context","payload":{"model":"gpt-5-mini"}}"#,
+            r#"{"type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1500,"cached_input_tokens":400,"output_tokens":150}}}}"#,
+        ] {
+            writeln!(file, "{}", line).unwrap();
+        }
+
+        let parser = CodexParser;
+        let (session, _) = parser.parse(file.path()).unwrap();
+
+        assert_eq!(session.usage.models, vec!["gpt-5-codex", "gpt-5-mini"]);
+        assert_eq!(
+            session.usage.tokens,
+            TokenUsage {
+                input: 1100,
+                output: 150,
+                cache_read: 400,
+                cache_write: 0,
+            }
+        );
+        assert!(session.usage.cost_usd.is_some());
+    }
+
+    #[test]
+    fn parse_reads_git_info_from_session_meta() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        for line in [
+            r#"{"type":"session_meta","payload":{"id":"git-session","timestamp":"2026-01-18T02:01:28Z","cwd":"/tmp","git":{"commit_hash":"0123456789abcdef0123456789abcdef01234567","branch":"feature-x","repository_url":"git@github.com:me/repo.git"}}}"#,
+            r#"{"type":"event_msg","timestamp":"2026-01-18T02:01:29Z","payload":{"type":"user_message","message":"Hi"}}"#,
+        ] {
+            writeln!(file, "{}", line).unwrap();
+        }
+
+        let parser = CodexParser;
+        let (session, _) = parser.parse(file.path()).unwrap();
+
+        assert_eq!(session.git.branch.as_deref(), Some("feature-x"));
+        assert_eq!(
+            session.git.commit.as_deref(),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+        assert_eq!(
+            session.git.remote.as_deref(),
+            Some("git@github.com:me/repo.git")
+        );
+    }
+
+    #[test]
+    fn parse_reads_tool_calls_and_outputs() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        for line in [
+            r#"{"type":"session_meta","payload":{"id":"tool-session","timestamp":"2026-01-18T02:01:28Z","cwd":"/tmp"}}"#,
+            r#"{"type":"event_msg","timestamp":"2026-01-18T02:01:29Z","payload":{"type":"user_message","message":"Run the tests"}}"#,
+            r#"{"type":"response_item","timestamp":"2026-01-18T02:01:30Z","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"bash\",\"-lc\",\"cargo test\"]}","call_id":"call_1"}}"#,
+            r#"{"type":"response_item","timestamp":"2026-01-18T02:01:31Z","payload":{"type":"function_call_output","call_id":"call_1","output":"{\"output\":\"1 failed\",\"metadata\":{\"exit_code\":101}}"}}"#,
+            r#"{"type":"response_item","timestamp":"2026-01-18T02:01:32Z","payload":{"type":"message","role":"assistant","content":[]}}"#,
+        ] {
+            writeln!(file, "{}", line).unwrap();
+        }
+
+        let parser = CodexParser;
+        let (session, messages) = parser.parse(file.path()).unwrap();
+
+        assert_eq!(session.message_count, 1);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[1].role, Role::ToolCall);
+        assert_eq!(
+            ToolCall::from_content(&messages[1].content)
+                .unwrap()
+                .summary(),
+            "shell: cargo test"
+        );
+        let result = ToolResult::from_content(&messages[2].content).unwrap();
+        assert_eq!(result.call_id.as_deref(), Some("call_1"));
+        assert_eq!(result.output, "1 failed");
+        assert!(result.is_error);
+    }
 }
@@ -5,7 +5,9 @@ use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
-use crate::models::{Message, Role, Session, Tool};
+use crate::models::{
+    Continuation, GitInfo, Message, Role, Session, SessionUsage, Tool, ToolCall, ToolResult,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -61,7 +63,28 @@ impl MistralVibeParser {
             let role = event.get("role").and_then(|v| v.as_str());
 
             match role {
-                Some("system") | Some("tool") => continue,
+                Some("system") => continue,
+                Some("tool") => {
+                    let result = ToolResult {
+                        call_id: event
+                            .get("tool_call_id")
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string),
+                        is_error: false,
+                        output: event
+                            .get("content")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                    };
+                    Self::push_message(
+                        &mut messages,
+                        &session_id,
+                        Role::ToolResult,
+                        result.to_content(),
+                        start_time,
+                    );
+                }
                 Some("user") => {
                     if let Some(content) = Self::extract_content(&event) {
                         has_user_message = true;
@@ -84,6 +107,15 @@ impl MistralVibeParser {
                             start_time,
                         );
                     }
+                    for call in Self::extract_tool_calls(&event) {
+                        Self::push_message(
+                            &mut messages,
+                            &session_id,
+                            Role::ToolCall,
+                            call.to_content(),
+                            start_time,
+                        );
+                    }
                 }
                 _ => continue,
             }
@@ -94,6 +126,11 @@ impl MistralVibeParser {
         }
 
         let first_prompt = crate::parsers::extract_first_prompt(&messages);
+        let title = metadata
+            .get("title")
+            .and_then(|v| v.as_str())
+            .and_then(crate::parsers::title::clean_title)
+            .or_else(|| crate::parsers::title::derive_title(&messages));
 
         Ok((
             Session {
@@ -101,10 +138,18 @@ impl MistralVibeParser {
                 tool: Tool::MistralVibe,
                 project_path,
                 start_time,
-                message_count: messages.len(),
+                message_count: crate::parsers::conversation_len(&messages),
                 file_path: session_dir.to_str().unwrap_or_default().to_string(),
                 last_updated: end_time,
                 first_prompt,
+                usage: SessionUsage::default(),
+                git: GitInfo::default(),
+                parent_session_id: None,
+                continuation: Continuation::default(),
+                starred: false,
+                tags: Vec::new(),
+                title,
+                custom_title: None,
             },
             messages,
         ))
@@ -118,6 +163,31 @@ impl MistralVibeParser {
             .filter(|value| !value.trim().is_empty())
     }
 
+    /// OpenAI-style `tool_calls`, whose arguments are a JSON string.
+    fn extract_tool_calls(event: &Value) -> Vec<ToolCall> {
+        let Some(calls) = event.get("tool_calls").and_then(|v| v.as_array()) else {
+            return Vec::new();
+        };
+        calls
+            .iter()
+            .filter_map(|call| {
+                let function = call.get("function")?;
+                let input = match function.get("arguments") {
+                    Some(Value::String(raw)) => {
+                        serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.clone()))
+                    }
+                    Some(value) => value.clone(),
+                    None => Value::Null,
+                };
+                Some(ToolCall {
+                    id: call.get("id").and_then(|v| v.as_str()).map(str::to_string),
+                    name: function.get("name")?.as_str()?.to_string(),
+                    input,
+                })
+            })
+            .collect()
+    }
+
     fn push_message(
         messages: &mut Vec<Message>,
         session_id: &str,
@@ -175,6 +245,7 @@ mod tests {
             "session_id": "temp-session",
             "start_time": "2026-02-03T19:14:51Z",
             "end_time": "2026-02-03T19:16:05Z",
+            "title": "Project layout tour",
             "environment": { "working_directory": "/tmp/project" }
         });
         fs::write(path, serde_json::to_vec(&value).unwrap()).unwrap();
@@ -211,9 +282,25 @@ mod tests {
             session.first_prompt.as_deref(),
             Some("List the files in the project root.")
         );
-        assert_eq!(messages.len(), 2);
-        assert_eq!(messages[0].role, Role::User);
-        assert_eq!(messages[1].role, Role::Assistant);
+        assert_eq!(
+            session.title.as_deref(),
+            Some("List the files in the project root")
+        );
+        let roles: Vec<Role> = messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            [
+                Role::User,
+                Role::ToolCall,
+                Role::ToolResult,
+                Role::Assistant
+            ]
+        );
+        let call = ToolCall::from_content(&messages[1].content).unwrap();
+        assert_eq!(call.summary(), "list_files .");
+        let result = ToolResult::from_content(&messages[2].content).unwrap();
+        assert_eq!(result.call_id.as_deref(), Some("call_1"));
+        assert_eq!(result.output, "README.md\nsrc\n");
     }
 
     #[test]
@@ -259,7 +346,7 @@ mod tests {
     }
 
     #[test]
-    fn parse_ignores_system_and_tool_roles() {
+    fn parse_ignores_system_role_and_keeps_tool_output() {
         let temp_dir = create_temp_session_dir(&[
             r#"{"role":"system","content":"Boot"}"#,
             r#"{"role":"tool","content":"Tool output"}"#,
@@ -267,12 +354,14 @@ mod tests {
             r#"{"role":"assistant","content":"Hello"}"#,
         ]);
         let parser = MistralVibeParser;
-        let (_session, messages) = parser.parse(temp_dir.path()).unwrap();
-
-        assert_eq!(messages.len(), 2);
-        assert_eq!(messages[0].role, Role::User);
-        assert_eq!(messages[0].content, "Hi");
-        assert_eq!(messages[1].role, Role::Assistant);
-        assert_eq!(messages[1].content, "Hello");
+        let (session, messages) = parser.parse(temp_dir.path()).unwrap();
+
+        assert_eq!(session.title.as_deref(), Some("Project layout tour"));
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].role, Role::ToolResult);
+        assert_eq!(messages[1].role, Role::User);
+        assert_eq!(messages[1].content, "Hi");
+        assert_eq!(messages[2].role, Role::Assistant);
+        assert_eq!(messages[2].content, "Hello");
     }
 }
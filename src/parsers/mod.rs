@@ -2,6 +2,7 @@ pub mod claude_code;
 pub mod codex;
 pub mod mistral_vibe;
 pub mod opencode;
+pub mod title;
 
 use crate::models::{Message, Role};
 
@@ -15,7 +16,16 @@ pub(crate) fn extract_first_prompt(messages: &[Message]) -> Option<String> {
         .find(|prompt| !prompt.is_empty())
 }
 
-fn normalize_prompt(content: &str) -> String {
+/// Number of user and assistant messages, leaving reasoning and tool
+/// traffic out of the count shown for a session.
+pub(crate) fn conversation_len(messages: &[Message]) -> usize {
+    messages
+        .iter()
+        .filter(|message| message.role.is_conversation())
+        .count()
+}
+
+pub(crate) fn normalize_prompt(content: &str) -> String {
     let normalized = content.split_whitespace().collect::<Vec<_>>().join(" ");
     truncate_chars(&normalized, FIRST_PROMPT_MAX_CHARS)
 }
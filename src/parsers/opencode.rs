@@ -5,12 +5,13 @@ use std::cmp::Ordering;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::models::{Message, Role, Session, Tool};
+use crate::models::{
+    Continuation, GitInfo, Message, Role, Session, SessionUsage, TokenUsage, Tool, ToolCall,
+    ToolResult,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
-    #[error("Skipping subagent session")]
-    SubagentSession,
     #[error("Session contains no user messages")]
     NoUserMessages,
 }
@@ -25,12 +26,16 @@ struct SessionMetadata {
     time_created: DateTime<Utc>,
     time_updated: DateTime<Utc>,
     parent_id: Option<String>,
+    title: Option<String>,
 }
 
 struct MessageMetadata {
     id: String,
     role: Option<Role>,
     time_created: DateTime<Utc>,
+    model: Option<String>,
+    tokens: TokenUsage,
+    cost: Option<f64>,
 }
 
 struct PartData {
@@ -50,9 +55,6 @@ impl OpenCodeParser {
 
     pub fn parse(&self, session_path: &Path) -> Result<(Session, Vec<Message>)> {
         let metadata = self.parse_session_metadata(session_path)?;
-        if metadata.parent_id.is_some() {
-            return Err(ParseError::SubagentSession.into());
-        }
 
         let mut messages = self.load_messages(&metadata.id)?;
         messages.sort_by(|a, b| {
@@ -63,8 +65,19 @@ impl OpenCodeParser {
 
         let mut flattened = Vec::new();
         let mut has_user_message = false;
+        let mut usage = SessionUsage::default();
 
         for message in messages {
+            if message.model.is_some() || !message.tokens.is_empty() {
+                // OpenCode reports its own cost; fall back to the price table.
+                match message.cost {
+                    Some(cost) => {
+                        usage.record_with_cost(message.model.as_deref(), message.tokens, Some(cost))
+                    }
+                    None => usage.record(message.model.as_deref(), message.tokens),
+                }
+            }
+
             let mut parts = self.load_parts(&message.id)?;
             parts.sort_by(|a, b| match (a.order, b.order) {
                 (Some(left), Some(right)) => left.cmp(&right).then_with(|| a.id.cmp(&b.id)),
@@ -94,16 +107,28 @@ impl OpenCodeParser {
         }
 
         let first_prompt = crate::parsers::extract_first_prompt(&flattened);
+        let title = metadata
+            .title
+            .clone()
+            .or_else(|| crate::parsers::title::derive_title(&flattened));
 
         let session = Session {
             id: metadata.id.clone(),
             tool: Tool::OpenCode,
             project_path: metadata.directory.clone(),
             start_time: metadata.time_created,
-            message_count: flattened.len(),
+            message_count: crate::parsers::conversation_len(&flattened),
             file_path: session_path.to_str().unwrap_or_default().to_string(),
             last_updated: metadata.time_updated,
             first_prompt,
+            usage,
+            git: GitInfo::default(),
+            parent_session_id: metadata.parent_id.clone(),
+            continuation: Continuation::default(),
+            starred: false,
+            tags: Vec::new(),
+            title,
+            custom_title: None,
         };
 
         Ok((session, flattened))
@@ -134,6 +159,14 @@ impl OpenCodeParser {
             .and_then(|v| v.as_str())
             .map(str::to_string);
 
+        // OpenCode names new sessions "New session - <timestamp>" until it
+        // has generated a title.
+        let title = value
+            .get("title")
+            .and_then(|v| v.as_str())
+            .filter(|title| !title.starts_with("New session - "))
+            .and_then(crate::parsers::title::clean_title);
+
         let created_ms = value
             .get("time")
             .and_then(|v| v.get("created"))
@@ -152,6 +185,7 @@ impl OpenCodeParser {
             time_created: Self::timestamp_from_millis(created_ms)?,
             time_updated: Self::timestamp_from_millis(updated_ms)?,
             parent_id,
+            title,
         })
     }
 
@@ -233,16 +267,42 @@ impl OpenCodeParser {
                 }
             };
 
+            let model = value
+                .get("modelID")
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+            let tokens = value
+                .get("tokens")
+                .map(Self::parse_tokens)
+                .unwrap_or_default();
+            let cost = value.get("cost").and_then(|v| v.as_f64());
+
             messages.push(MessageMetadata {
                 id,
                 role,
                 time_created,
+                model,
+                tokens,
+                cost,
             });
         }
 
         Ok(messages)
     }
 
+    /// Parse a message `tokens` object; reasoning tokens are billed as output.
+    fn parse_tokens(value: &Value) -> TokenUsage {
+        let count = |value: Option<&Value>| value.and_then(|v| v.as_u64()).unwrap_or(0);
+        let cache = value.get("cache");
+
+        TokenUsage {
+            input: count(value.get("input")),
+            output: count(value.get("output")) + count(value.get("reasoning")),
+            cache_read: count(cache.and_then(|c| c.get("read"))),
+            cache_write: count(cache.and_then(|c| c.get("write"))),
+        }
+    }
+
     fn load_parts(&self, message_id: &str) -> Result<Vec<PartData>> {
         let parts_dir = self.storage_root.join("part").join(message_id);
         let entries = match fs::read_dir(&parts_dir) {
@@ -353,9 +413,11 @@ impl OpenCodeParser {
                     timestamp,
                 }]
             }
+            "tool" => Self::tool_part_messages(session_id, timestamp, part),
             // Skip metadata/control parts
-            "tool" | "reasoning" | "step-start" | "step-finish" | "snapshot" | "compaction"
-            | "subtask" => Vec::new(),
+            "reasoning" | "step-start" | "step-finish" | "snapshot" | "compaction" | "subtask" => {
+                Vec::new()
+            }
             other => {
                 tracing::debug!("Unhandled part type: {}", other);
                 Vec::new()
@@ -363,6 +425,56 @@ impl OpenCodeParser {
         }
     }
 
+    /// A tool part becomes a call, followed by its output or error once the
+    /// tool has finished.
+    fn tool_part_messages(
+        session_id: &str,
+        timestamp: DateTime<Utc>,
+        part: &PartData,
+    ) -> Vec<Message> {
+        let Some(name) = part.raw.get("tool").and_then(|v| v.as_str()) else {
+            return Vec::new();
+        };
+        let state = part.raw.get("state");
+        let field = |key: &str| state.and_then(|state| state.get(key));
+        let call_id = part
+            .raw
+            .get("callID")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&part.id)
+            .to_string();
+
+        let call = ToolCall {
+            id: Some(call_id.clone()),
+            name: name.to_string(),
+            input: field("input").cloned().unwrap_or(Value::Null),
+        };
+        let result = match (field("output"), field("error")) {
+            (_, Some(error)) => Some((true, error)),
+            (Some(output), None) => Some((false, output)),
+            (None, None) => None,
+        }
+        .map(|(is_error, value)| ToolResult {
+            call_id: Some(call_id),
+            is_error,
+            output: value
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string()),
+        });
+
+        let message = |role, content| Message {
+            session_id: session_id.to_string(),
+            index: 0,
+            role,
+            content,
+            timestamp,
+        };
+        std::iter::once(message(Role::ToolCall, call.to_content()))
+            .chain(result.map(|result| message(Role::ToolResult, result.to_content())))
+            .collect()
+    }
+
     fn read_json(path: &Path) -> Result<Value> {
         let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
         serde_json::from_slice(&bytes).context("Failed to parse JSON")
@@ -430,6 +542,7 @@ mod tests {
             json!({
                 "id": "session-001",
                 "directory": "/projects/alpha",
+                "title": "Fix   the parser",
                 "time": { "created": created, "updated": updated }
             }),
         );
@@ -439,6 +552,7 @@ mod tests {
 
         assert_eq!(metadata.id, "session-001");
         assert_eq!(metadata.directory.as_deref(), Some("/projects/alpha"));
+        assert_eq!(metadata.title.as_deref(), Some("Fix the parser"));
         assert_eq!(
             metadata.time_created,
             DateTime::<Utc>::from_timestamp_millis(created).unwrap()
@@ -450,7 +564,7 @@ mod tests {
     }
 
     #[test]
-    fn parse_skips_subagent_sessions() {
+    fn parse_links_subagent_sessions_to_parent() {
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path();
 
@@ -464,11 +578,29 @@ mod tests {
                 "time": { "created": 1_704_067_200_000i64, "updated": 1_704_067_260_000i64 }
             }),
         );
+        write_message_file(
+            root,
+            "session-002",
+            "msg-001.json",
+            json!({
+                "id": "msg-001",
+                "sessionID": "session-002",
+                "role": "user",
+                "time": { "created": 1_704_067_200_000i64 }
+            }),
+        );
+        write_part_file(
+            root,
+            "msg-001",
+            "part-001.json",
+            json!({ "id": "part-001", "type": "text", "text": "Find all parsers" }),
+        );
 
         let parser = OpenCodeParser::new(root);
-        let result = parser.parse(&session_path);
+        let (session, _) = parser.parse(&session_path).unwrap();
 
-        assert!(result.is_err());
+        assert_eq!(session.parent_session_id.as_deref(), Some("session-001"));
+        assert_eq!(session.first_prompt.as_deref(), Some("Find all parsers"));
     }
 
     #[test]
@@ -605,13 +737,15 @@ mod tests {
         let parser = OpenCodeParser::new(root);
         let (session, messages) = parser.parse(&session_path).unwrap();
 
-        assert_eq!(messages.len(), 2);
+        assert_eq!(messages.len(), 3);
         assert_eq!(messages[0].index, 0);
         assert_eq!(messages[0].role, Role::User);
         assert_eq!(messages[0].content, "First");
         assert_eq!(messages[1].index, 1);
         assert_eq!(messages[1].role, Role::User);
         assert_eq!(messages[1].content, "Second");
+        assert_eq!(messages[2].index, 2);
+        assert_eq!(messages[2].role, Role::ToolCall);
         assert_eq!(session.first_prompt.as_deref(), Some("First"));
     }
 
@@ -759,7 +893,7 @@ mod tests {
     }
 
     #[test]
-    fn tool_part_is_skipped() {
+    fn tool_part_without_output_becomes_a_call() {
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path();
 
@@ -827,15 +961,23 @@ mod tests {
         );
 
         let parser = OpenCodeParser::new(root);
-        let (_session, messages) = parser.parse(&session_path).unwrap();
+        let (session, messages) = parser.parse(&session_path).unwrap();
 
-        assert_eq!(messages.len(), 1);
+        assert_eq!(session.message_count, 1);
+        assert_eq!(messages.len(), 2);
         assert_eq!(messages[0].role, Role::User);
         assert_eq!(messages[0].content, "Run tool");
+        assert_eq!(messages[1].role, Role::ToolCall);
+        assert_eq!(
+            ToolCall::from_content(&messages[1].content)
+                .unwrap()
+                .summary(),
+            "read /tmp/test.txt"
+        );
     }
 
     #[test]
-    fn tool_part_with_output_is_skipped() {
+    fn tool_part_with_output_becomes_call_and_result() {
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path();
 
@@ -904,15 +1046,21 @@ mod tests {
         );
 
         let parser = OpenCodeParser::new(root);
-        let (_session, messages) = parser.parse(&session_path).unwrap();
+        let (session, messages) = parser.parse(&session_path).unwrap();
 
-        assert_eq!(messages.len(), 1);
-        assert_eq!(messages[0].role, Role::User);
+        assert_eq!(session.message_count, 1);
+        assert_eq!(messages.len(), 3);
         assert_eq!(messages[0].content, "Read file");
+        assert_eq!(messages[1].role, Role::ToolCall);
+        assert_eq!(messages[2].role, Role::ToolResult);
+        let result = ToolResult::from_content(&messages[2].content).unwrap();
+        assert_eq!(result.call_id.as_deref(), Some("part-tool"));
+        assert!(!result.is_error);
+        assert_eq!(result.output, "File contents here\nLine 2\nLine 3");
     }
 
     #[test]
-    fn tool_part_with_error_is_skipped() {
+    fn tool_part_with_error_becomes_error_result() {
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path();
 
@@ -983,9 +1131,11 @@ mod tests {
         let parser = OpenCodeParser::new(root);
         let (_session, messages) = parser.parse(&session_path).unwrap();
 
-        assert_eq!(messages.len(), 1);
-        assert_eq!(messages[0].role, Role::User);
+        assert_eq!(messages.len(), 3);
         assert_eq!(messages[0].content, "Read file");
+        let result = ToolResult::from_content(&messages[2].content).unwrap();
+        assert!(result.is_error);
+        assert_eq!(result.output, "File not found");
     }
 
     #[test]
@@ -1058,4 +1208,77 @@ mod tests {
         assert_eq!(messages[1].role, Role::Assistant);
         assert_eq!(messages[1].content, "I can help");
     }
+
+    #[test]
+    fn parse_collects_model_tokens_and_reported_cost() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        let session_path = write_session_file(
+            root,
+            "project-a",
+            "session-usage.json",
+            json!({
+                "id": "session-usage",
+                "directory": "/projects/alpha",
+                "time": { "created": 1_704_067_200_000i64 }
+            }),
+        );
+
+        write_message_file(
+            root,
+            "session-usage",
+            "msg-001.json",
+            json!({
+                "id": "msg-001",
+                "sessionID": "session-usage",
+                "role": "user",
+                "time": { "created": 1_704_067_200_000i64 }
+            }),
+        );
+        write_part_file(
+            root,
+            "msg-001",
+            "part-001.json",
+            json!({ "id": "part-001", "type": "text", "text": "Hello" }),
+        );
+
+        write_message_file(
+            root,
+            "session-usage",
+            "msg-002.json",
+            json!({
+                "id": "msg-002",
+                "sessionID": "session-usage",
+                "role": "assistant",
+                "modelID": "claude-sonnet-4-5",
+                "providerID": "anthropic",
+                "cost": 0.25,
+                "tokens": { "input": 100, "output": 20, "reasoning": 5, "cache": { "read": 300, "write": 40 } },
+                "time": { "created": 1_704_067_210_000i64 }
+            }),
+        );
+        write_part_file(
+            root,
+            "msg-002",
+            "part-001.json",
+            json!({ "id": "part-001", "type": "text", "text": "Hi" }),
+        );
+
+        let parser = OpenCodeParser::new(root);
+        let (session, _) = parser.parse(&session_path).unwrap();
+
+        assert_eq!(session.usage.models, vec!["claude-sonnet-4-5"]);
+        assert_eq!(
+            session.usage.tokens,
+            TokenUsage {
+                input: 100,
+                output: 25,
+                cache_read: 300,
+                cache_write: 40,
+            }
+        );
+        assert_eq!(session.usage.cost_usd, Some(0.25));
+        assert!(!session.usage.cost_is_estimate);
+    }
 }
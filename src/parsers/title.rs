@@ -0,0 +1,190 @@
+use crate::models::{Message, Role};
+
+const TITLE_MAX_CHARS: usize = 80;
+
+/// Prompts shorter than this are follow-ups such as "continue" or
+/// "fix the tests", not a description of the task.
+const MIN_TITLE_WORDS: usize = 4;
+
+/// Title picked from the first substantive user prompt: its first sentence,
+/// ignoring code blocks, markup tags and pasted logs or stack traces.
+pub(crate) fn derive_title(messages: &[Message]) -> Option<String> {
+    messages
+        .iter()
+        .filter(|message| message.role == Role::User)
+        .find_map(|message| title_from_prompt(&message.content))
+}
+
+/// Title recorded by a tool, with whitespace collapsed and the length capped.
+pub(crate) fn clean_title(raw: &str) -> Option<String> {
+    let title = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!title.is_empty()).then(|| truncate_words(&title, TITLE_MAX_CHARS))
+}
+
+fn title_from_prompt(content: &str) -> Option<String> {
+    let mut in_code_block = false;
+    let mut prose = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || trimmed.is_empty() {
+            if !prose.is_empty() {
+                break;
+            }
+            continue;
+        }
+        if is_noise(line) {
+            continue;
+        }
+        prose.push(trimmed);
+    }
+
+    let text = prose.join(" ");
+    let sentence = first_sentence(&text)
+        .trim_end_matches(['.', ':', ',', ';'])
+        .trim();
+    if sentence.split_whitespace().count() < MIN_TITLE_WORDS {
+        return None;
+    }
+
+    let mut chars = sentence.chars();
+    let title: String = chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default();
+    Some(truncate_words(&title, TITLE_MAX_CHARS))
+}
+
+/// Lines that are markup, indented code or part of a log or stack trace.
+fn is_noise(line: &str) -> bool {
+    let trimmed = line.trim();
+    if line.starts_with("    ") || line.starts_with('\t') {
+        return true;
+    }
+    if trimmed.starts_with('<') || trimmed.starts_with('$') || trimmed.starts_with('>') {
+        return true;
+    }
+    const TRACE_PREFIXES: &[&str] = &[
+        "at ",
+        "File \"",
+        "Traceback",
+        "thread '",
+        "error[",
+        "warning[",
+        "panicked at",
+        "note:",
+        "-->",
+    ];
+    if TRACE_PREFIXES
+        .iter()
+        .any(|prefix| trimmed.starts_with(prefix))
+    {
+        return true;
+    }
+    let letters = trimmed.chars().filter(|ch| ch.is_alphabetic()).count();
+    letters * 2 < trimmed.chars().count()
+}
+
+fn first_sentence(text: &str) -> &str {
+    let mut previous = None;
+    for (index, ch) in text.char_indices() {
+        if ch.is_whitespace() && matches!(previous, Some('.' | '?' | '!')) {
+            return &text[..index];
+        }
+        previous = Some(ch);
+    }
+    text
+}
+
+/// `value` cut at a word boundary so it fits in `max_chars`, with an
+/// ellipsis when anything was dropped.
+fn truncate_words(value: &str, max_chars: usize) -> String {
+    if value.chars().count() <= max_chars {
+        return value.to_string();
+    }
+
+    let mut truncated = String::new();
+    for word in value.split(' ') {
+        let extra = usize::from(!truncated.is_empty()) + word.chars().count();
+        if truncated.chars().count() + extra >= max_chars {
+            break;
+        }
+        if !truncated.is_empty() {
+            truncated.push(' ');
+        }
+        truncated.push_str(word);
+    }
+    if truncated.is_empty() {
+        truncated = value.chars().take(max_chars - 1).collect();
+    }
+    truncated.push('\u{2026}');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn user(index: usize, content: &str) -> Message {
+        Message {
+            session_id: "session-1".to_string(),
+            index,
+            role: Role::User,
+            content: content.to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn derive_title_skips_short_follow_ups_and_pasted_traces() {
+        let messages = vec![
+            user(0, "continue"),
+            user(1, "fix the tests"),
+            user(
+                2,
+                "thread 'main' panicked at src/main.rs:4:5:\nnote: run with `RUST_BACKTRACE=1`",
+            ),
+            user(
+                3,
+                "<command-name>/review</command-name>\nwhy does the indexer drop stars on reindex? It used to work.",
+            ),
+        ];
+
+        assert_eq!(
+            derive_title(&messages).as_deref(),
+            Some("Why does the indexer drop stars on reindex?")
+        );
+    }
+
+    #[test]
+    fn derive_title_ignores_code_blocks_and_is_none_without_prose() {
+        let messages = vec![
+            user(
+                0,
+                "```\nlet x = 1;\n```\nrename the parser module to something clearer.",
+            ),
+            user(1, "ok"),
+        ];
+        assert_eq!(
+            derive_title(&messages).as_deref(),
+            Some("Rename the parser module to something clearer")
+        );
+        assert_eq!(derive_title(&[user(0, "go on")]), None);
+    }
+
+    #[test]
+    fn long_titles_are_cut_at_a_word_boundary() {
+        let title = clean_title(&"word ".repeat(40)).unwrap();
+        assert!(title.chars().count() <= TITLE_MAX_CHARS);
+        assert!(title.ends_with("word\u{2026}"));
+        assert_eq!(
+            clean_title("  Parser   fix "),
+            Some("Parser fix".to_string())
+        );
+        assert_eq!(clean_title("   "), None);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::models::TokenUsage;
+
+/// Prices in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_write: f64,
+}
+
+const fn price(input: f64, output: f64, cache_read: f64, cache_write: f64) -> ModelPrice {
+    ModelPrice {
+        input,
+        output,
+        cache_read,
+        cache_write,
+    }
+}
+
+/// Local price table, matched by model-name prefix.
+///
+/// More specific prefixes must come before the families they belong to.
+/// Figures are list prices and only meant as an estimate.
+const PRICE_TABLE: &[(&str, ModelPrice)] = &[
+    // Anthropic
+    ("claude-opus-4-5", price(5.0, 25.0, 0.5, 6.25)),
+    ("claude-opus-4-6", price(5.0, 25.0, 0.5, 6.25)),
+    ("claude-opus-4", price(15.0, 75.0, 1.5, 18.75)),
+    ("claude-3-opus", price(15.0, 75.0, 1.5, 18.75)),
+    ("claude-sonnet-4", price(3.0, 15.0, 0.3, 3.75)),
+    ("claude-3-7-sonnet", price(3.0, 15.0, 0.3, 3.75)),
+    ("claude-3-5-sonnet", price(3.0, 15.0, 0.3, 3.75)),
+    ("claude-haiku-4", price(1.0, 5.0, 0.1, 1.25)),
+    ("claude-3-5-haiku", price(0.8, 4.0, 0.08, 1.0)),
+    ("claude-3-haiku", price(0.25, 1.25, 0.03, 0.3)),
+    // OpenAI
+    ("gpt-5-nano", price(0.05, 0.4, 0.005, 0.05)),
+    ("gpt-5-mini", price(0.25, 2.0, 0.025, 0.25)),
+    ("gpt-5", price(1.25, 10.0, 0.125, 1.25)),
+    ("gpt-4.1-nano", price(0.1, 0.4, 0.025, 0.1)),
+    ("gpt-4.1-mini", price(0.4, 1.6, 0.1, 0.4)),
+    ("gpt-4.1", price(2.0, 8.0, 0.5, 2.0)),
+    ("gpt-4o-mini", price(0.15, 0.6, 0.075, 0.15)),
+    ("gpt-4o", price(2.5, 10.0, 1.25, 2.5)),
+    ("o4-mini", price(1.1, 4.4, 0.275, 1.1)),
+    ("o3", price(2.0, 8.0, 0.5, 2.0)),
+    // Mistral
+    ("devstral-small", price(0.1, 0.3, 0.1, 0.1)),
+    ("devstral", price(0.4, 2.0, 0.4, 0.4)),
+    ("mistral-medium", price(0.4, 2.0, 0.4, 0.4)),
+    ("mistral-large", price(2.0, 6.0, 2.0, 2.0)),
+    ("codestral", price(0.3, 0.9, 0.3, 0.3)),
+];
+
+/// Look up the price of `model`, ignoring any `provider/` prefix and case.
+pub fn price_for_model(model: &str) -> Option<ModelPrice> {
+    let name = model.rsplit('/').next().unwrap_or(model).to_lowercase();
+    PRICE_TABLE
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, price)| *price)
+}
+
+/// Estimated cost in USD of `usage` on `model`, or `None` if the model is unknown.
+pub fn estimate_cost(model: &str, usage: &TokenUsage) -> Option<f64> {
+    let price = price_for_model(model)?;
+    let per_token = |count: u64, per_million: f64| count as f64 * per_million / 1_000_000.0;
+
+    Some(
+        per_token(usage.input, price.input)
+            + per_token(usage.output, price.output)
+            + per_token(usage.cache_read, price.cache_read)
+            + per_token(usage.cache_write, price.cache_write),
+    )
+}
+
+/// Format a cost in USD for display.
+pub fn format_cost(cost: f64) -> String {
+    if cost > 0.0 && cost < 0.01 {
+        "< $0.01".to_string()
+    } else {
+        format!("${:.2}", cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn specific_prefixes_win_over_families() {
+        assert_eq!(
+            price_for_model("claude-opus-4-5-20251101").unwrap().input,
+            5.0
+        );
+        assert_eq!(
+            price_for_model("claude-opus-4-1-20250805").unwrap().input,
+            15.0
+        );
+        assert_eq!(price_for_model("gpt-5-mini").unwrap().input, 0.25);
+        assert_eq!(price_for_model("gpt-5-codex").unwrap().input, 1.25);
+    }
+
+    #[test]
+    fn provider_prefix_is_ignored() {
+        assert_eq!(
+            price_for_model("anthropic/claude-sonnet-4-5"),
+            price_for_model("claude-sonnet-4-5")
+        );
+    }
+
+    #[test]
+    fn unknown_model_has_no_estimate() {
+        assert!(estimate_cost("my-local-llama", &TokenUsage::default()).is_none());
+    }
+
+    #[test]
+    fn estimate_cost_sums_all_token_kinds() {
+        let usage = TokenUsage {
+            input: 1_000_000,
+            output: 100_000,
+            cache_read: 1_000_000,
+            cache_write: 0,
+        };
+        let cost = estimate_cost("claude-sonnet-4-5", &usage).unwrap();
+        assert!((cost - 4.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn format_cost_rounds_to_cents() {
+        assert_eq!(format_cost(0.0), "$0.00");
+        assert_eq!(format_cost(0.004), "< $0.01");
+        assert_eq!(format_cost(12.345), "$12.35");
+    }
+}
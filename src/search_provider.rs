@@ -0,0 +1,282 @@
+//! GNOME Shell search provider: answers `org.gnome.Shell.SearchProvider2`
+//! calls from the index, so sessions can be found from the overview.
+//!
+//! It runs as `sessions-chronicle search-provider`, a D-Bus activated service
+//! separate from the app, and opens results by launching the app with a
+//! session permalink, or with a search link for the overview's terms.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use relm4::gtk::gio::{self, prelude::*};
+use relm4::gtk::glib::{self, Variant};
+
+use crate::database::{load_session, search_sessions};
+use crate::models::{Permalink, Session, Tool, search_uri};
+
+pub const OBJECT_PATH: &str = "/io/github/supermaciz/sessionschronicle/SearchProvider";
+
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="org.gnome.Shell.SearchProvider2">
+    <method name="GetInitialResultSet">
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="as" name="results" direction="out"/>
+    </method>
+    <method name="GetSubsearchResultSet">
+      <arg type="as" name="previous_results" direction="in"/>
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="as" name="results" direction="out"/>
+    </method>
+    <method name="GetResultMetas">
+      <arg type="as" name="identifiers" direction="in"/>
+      <arg type="aa{sv}" name="metas" direction="out"/>
+    </method>
+    <method name="ActivateResult">
+      <arg type="s" name="identifier" direction="in"/>
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="u" name="timestamp" direction="in"/>
+    </method>
+    <method name="LaunchSearch">
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="u" name="timestamp" direction="in"/>
+    </method>
+  </interface>
+</node>
+"#;
+
+/// The overview only shows a handful of results per provider.
+const MAX_RESULTS: usize = 20;
+
+/// The service exits once it has been idle this long; the next search
+/// activates it again.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Characters of the first prompt shown under the result's title.
+const DESCRIPTION_MAX_CHARS: usize = 120;
+
+#[derive(Debug)]
+enum SearchProviderCall {
+    GetInitialResultSet(Vec<String>),
+    GetSubsearchResultSet(Vec<String>, Vec<String>),
+    GetResultMetas(Vec<String>),
+    ActivateResult(String),
+    LaunchSearch(Vec<String>),
+}
+
+impl DBusMethodCall for SearchProviderCall {
+    fn parse_call(
+        _obj_path: &str,
+        _interface: Option<&str>,
+        method: &str,
+        params: Variant,
+    ) -> Result<Self, glib::Error> {
+        let call = match method {
+            "GetInitialResultSet" => params
+                .get::<(Vec<String>,)>()
+                .map(|(terms,)| Self::GetInitialResultSet(terms)),
+            "GetSubsearchResultSet" => params
+                .get::<(Vec<String>, Vec<String>)>()
+                .map(|(previous, terms)| Self::GetSubsearchResultSet(previous, terms)),
+            "GetResultMetas" => params
+                .get::<(Vec<String>,)>()
+                .map(|(ids,)| Self::GetResultMetas(ids)),
+            "ActivateResult" => params
+                .get::<(String, Vec<String>, u32)>()
+                .map(|(id, _, _)| Self::ActivateResult(id)),
+            "LaunchSearch" => params
+                .get::<(Vec<String>, u32)>()
+                .map(|(terms, _)| Self::LaunchSearch(terms)),
+            _ => {
+                return Err(glib::Error::new(
+                    gio::DBusError::UnknownMethod,
+                    &format!("No such method: {}", method),
+                ));
+            }
+        };
+        call.ok_or_else(|| {
+            glib::Error::new(
+                gio::DBusError::InvalidArgs,
+                &format!("Invalid arguments for {}", method),
+            )
+        })
+    }
+}
+
+/// Answers search provider calls from the index at `db_path`.
+pub struct SearchProvider {
+    db_path: PathBuf,
+    launch: Box<dyn Fn(String)>,
+    last_call: Cell<Instant>,
+}
+
+impl SearchProvider {
+    /// `launch` opens the app at a URI: the permalink of the activated
+    /// result, or a search link for the overview's terms.
+    pub fn new(db_path: PathBuf, launch: impl Fn(String) + 'static) -> Rc<Self> {
+        Rc::new(Self {
+            db_path,
+            launch: Box::new(launch),
+            last_call: Cell::new(Instant::now()),
+        })
+    }
+
+    /// Export the provider on `connection` at [`OBJECT_PATH`].
+    pub fn register(
+        self: &Rc<Self>,
+        connection: &gio::DBusConnection,
+    ) -> Result<gio::RegistrationId> {
+        let node = gio::DBusNodeInfo::for_xml(INTERFACE_XML)
+            .context("Failed to parse the search provider interface")?;
+        let interface = node
+            .lookup_interface("org.gnome.Shell.SearchProvider2")
+            .context("Search provider interface is missing")?;
+
+        let provider = self.clone();
+        connection
+            .register_object(OBJECT_PATH, &interface)
+            .typed_method_call::<SearchProviderCall>()
+            .invoke_and_return(move |_, _, call| Ok(provider.handle_call(call)))
+            .build()
+            .context("Failed to export the search provider")
+    }
+
+    fn handle_call(&self, call: SearchProviderCall) -> Option<Variant> {
+        self.last_call.set(Instant::now());
+        match call {
+            SearchProviderCall::GetInitialResultSet(terms) => {
+                Some((result_ids(&self.db_path, &terms, None),).to_variant())
+            }
+            SearchProviderCall::GetSubsearchResultSet(previous, terms) => {
+                Some((result_ids(&self.db_path, &terms, Some(&previous)),).to_variant())
+            }
+            SearchProviderCall::GetResultMetas(ids) => {
+                Some((result_metas(&self.db_path, &ids),).to_variant())
+            }
+            SearchProviderCall::ActivateResult(id) => {
+                (self.launch)(Permalink::to_session(&id).to_string());
+                None
+            }
+            SearchProviderCall::LaunchSearch(terms) => {
+                (self.launch)(search_uri(&terms.join(" ")));
+                None
+            }
+        }
+    }
+}
+
+/// Ids of the sessions matching all of the overview's search terms; a
+/// subsearch only narrows down the `previous` results.
+fn result_ids(db_path: &Path, terms: &[String], previous: Option<&[String]>) -> Vec<String> {
+    let query = terms.join(" ");
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    let sessions = search_sessions(db_path, Tool::ALL, &query).unwrap_or_else(|err| {
+        tracing::error!("Search provider query failed: {}", err);
+        Vec::new()
+    });
+    sessions
+        .into_iter()
+        .map(|session| session.id)
+        .filter(|id| previous.is_none_or(|previous| previous.contains(id)))
+        .take(MAX_RESULTS)
+        .collect()
+}
+
+fn result_metas(db_path: &Path, ids: &[String]) -> Vec<HashMap<String, Variant>> {
+    ids.iter()
+        .filter_map(|id| match load_session(db_path, id) {
+            Ok(session) => session,
+            Err(err) => {
+                tracing::error!("Failed to load session {}: {}", id, err);
+                None
+            }
+        })
+        .map(|session| result_meta(&session))
+        .collect()
+}
+
+fn result_meta(session: &Session) -> HashMap<String, Variant> {
+    let name = session
+        .display_title()
+        .map(str::to_string)
+        .unwrap_or_else(|| session.tool.display_name().to_string());
+    let mut description = session
+        .project_path
+        .as_deref()
+        .and_then(|path| Path::new(path).file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if let Some(prompt) = session.first_prompt.as_deref() {
+        let prompt = prompt.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !prompt.is_empty() && prompt != name {
+            if !description.is_empty() {
+                description.push_str(" · ");
+            }
+            description.extend(prompt.chars().take(DESCRIPTION_MAX_CHARS));
+        }
+    }
+
+    HashMap::from([
+        ("id".to_string(), session.id.to_variant()),
+        ("name".to_string(), name.to_variant()),
+        ("description".to_string(), description.to_variant()),
+        // A themed icon serializes to its name.
+        ("gicon".to_string(), session.tool.icon_name().to_variant()),
+    ])
+}
+
+/// Serve the search provider on the session bus under `bus_name` until it
+/// has been idle for a minute.
+pub fn serve(bus_name: &str, db_path: PathBuf) -> Result<()> {
+    let connection = gio::bus_get_sync(gio::BusType::Session, gio::Cancellable::NONE)
+        .context("Failed to connect to the session bus")?;
+    let main_loop = glib::MainLoop::new(None, false);
+
+    let provider = SearchProvider::new(db_path, launch_app);
+    let _registration = provider.register(&connection)?;
+
+    let _owner = gio::bus_own_name_on_connection(
+        &connection,
+        bus_name,
+        gio::BusNameOwnerFlags::NONE,
+        |_, _| {},
+        {
+            let main_loop = main_loop.clone();
+            move |_, name| {
+                tracing::error!("Lost the {} bus name", name);
+                main_loop.quit();
+            }
+        },
+    );
+
+    glib::timeout_add_seconds_local(10, {
+        let main_loop = main_loop.clone();
+        move || {
+            if provider.last_call.get().elapsed() >= IDLE_TIMEOUT {
+                main_loop.quit();
+                glib::ControlFlow::Break
+            } else {
+                glib::ControlFlow::Continue
+            }
+        }
+    });
+
+    main_loop.run();
+    Ok(())
+}
+
+/// Start the app, or hand `uri` to the running instance. GIO reaps the
+/// child once it exits.
+fn launch_app(uri: String) {
+    let program = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("sessions-chronicle"));
+    let argv = [program.as_os_str(), uri.as_ref()];
+    if let Err(err) = gio::Subprocess::newv(&argv, gio::SubprocessFlags::NONE) {
+        tracing::error!("Failed to launch the app: {}", err);
+    }
+}
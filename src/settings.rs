@@ -0,0 +1,65 @@
+//! Preferences that change what gets indexed, shared by the app and the
+//! command-line modes.
+
+use relm4::gtk::gio::{self, prelude::*};
+
+use crate::config::APP_ID;
+use crate::redaction::Redactor;
+use crate::utils::exclusions::ExclusionRules;
+use crate::utils::multiplexer::Multiplexer;
+
+/// The app's settings, or `None` when its schema is not installed, as when
+/// running a command-line mode from a build tree.
+pub fn installed() -> Option<gio::Settings> {
+    gio::SettingsSchemaSource::default()?.lookup(APP_ID, true)?;
+    Some(gio::Settings::new(APP_ID))
+}
+
+/// Redactor built from the user's custom patterns on top of the built-in
+/// detectors. Invalid patterns are logged and skipped wholesale.
+pub fn custom_redactor(settings: &gio::Settings) -> Redactor {
+    let patterns: Vec<String> = settings
+        .strv("redaction-patterns")
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+
+    Redactor::with_custom_patterns(&patterns).unwrap_or_else(|err| {
+        tracing::warn!("Ignoring custom redaction patterns: {}", err);
+        Redactor::new()
+    })
+}
+
+/// Redactor applied while indexing, or `None` when disabled in preferences.
+pub fn index_redactor(settings: &gio::Settings) -> Option<Redactor> {
+    settings
+        .boolean("redact-on-index")
+        .then(|| custom_redactor(settings))
+}
+
+/// Exclusion rules from the user's `excluded-paths` list.
+pub fn exclusion_rules(settings: &gio::Settings) -> ExclusionRules {
+    let patterns: Vec<String> = settings
+        .strv("excluded-paths")
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+    ExclusionRules::new(&patterns)
+}
+
+/// Multiplexer to resume sessions in, from `resume-multiplexer`. Unknown
+/// values are logged and treated as off.
+pub fn resume_multiplexer(settings: &gio::Settings) -> Multiplexer {
+    let value = settings.string("resume-multiplexer");
+    value.parse().unwrap_or_else(|()| {
+        tracing::warn!("Invalid multiplexer preference: {}", value);
+        Multiplexer::Off
+    })
+}
+
+/// Multiplexer for resuming from the command-line modes. Without the
+/// installed schema, as over SSH, any running tmux or zellij server is used
+/// rather than the preference's default of off.
+pub fn terminal_resume_multiplexer() -> Multiplexer {
+    installed().map_or(Multiplexer::Auto, |settings| resume_multiplexer(&settings))
+}
@@ -0,0 +1,83 @@
+//! Terminal interface for machines where the GTK app cannot run, such as a
+//! remote host over SSH. It indexes and queries the same database as the app
+//! and resumes sessions in the terminal it runs in.
+
+mod state;
+mod transcript;
+mod view;
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+
+use crate::database::SessionIndexer;
+use crate::redaction::Redactor;
+use crate::session_sources::SessionSources;
+use crate::settings::{self, exclusion_rules, index_redactor, terminal_resume_multiplexer};
+use crate::utils::terminal::{resume_from_terminal, run_in_current_terminal};
+
+use state::{Action, TuiState};
+
+/// Refresh the index from `sources`, then run the interface until the user
+/// quits.
+pub fn run(db_path: &Path, sources: &SessionSources) -> Result<()> {
+    if let Some(db_dir) = db_path.parent() {
+        fs::create_dir_all(db_dir)
+            .with_context(|| format!("Failed to create data dir {}", db_dir.display()))?;
+    }
+
+    let mut indexer = SessionIndexer::new(db_path)?;
+    // Without the installed schema, keep the preference defaults: redact
+    // with the built-in detectors and exclude nothing.
+    match settings::installed() {
+        Some(settings) => {
+            indexer.set_redactor(index_redactor(&settings));
+            indexer.set_exclusions(exclusion_rules(&settings));
+        }
+        None => indexer.set_redactor(Some(Redactor::new())),
+    }
+    eprintln!("Indexing sessions…");
+    indexer.index_sources(sources);
+
+    let mut state = TuiState::new(db_path.to_path_buf());
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut state);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, state: &mut TuiState) -> Result<()> {
+    loop {
+        terminal.draw(|frame| view::draw(frame, state))?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match state.handle_key(key) {
+            Action::None => {}
+            Action::Quit => return Ok(()),
+            Action::Resume(session) => {
+                let result =
+                    resume_from_terminal(&session, terminal_resume_multiplexer(), |args| {
+                        ratatui::restore();
+                        let result = run_in_current_terminal(args);
+                        *terminal = ratatui::init();
+                        result
+                    });
+                state.status = Some(match result {
+                    Ok(Some(multiplexer)) => {
+                        format!("Resumed in a new {} window", multiplexer.display_name())
+                    }
+                    Ok(None) => "Returned from the resumed session".to_string(),
+                    Err(err) => format!("{:#}", err),
+                });
+            }
+        }
+    }
+}
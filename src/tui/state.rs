@@ -0,0 +1,342 @@
+use std::path::PathBuf;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::text::Line;
+use ratatui::widgets::ListState;
+
+use crate::database::{load_messages_for_session, search_sessions};
+use crate::models::{Session, Tool};
+use crate::tui::transcript::transcript_lines;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    List,
+    /// Typing in the search field; the list follows every keystroke.
+    Search,
+    Transcript,
+}
+
+/// What the event loop should do after a key press.
+#[derive(Debug)]
+pub enum Action {
+    None,
+    Quit,
+    Resume(Box<Session>),
+}
+
+/// The session open in the transcript view.
+pub struct Transcript {
+    pub session: Session,
+    pub lines: Vec<Line<'static>>,
+    /// First visible row, clamped by the view to the wrapped text height.
+    pub scroll: usize,
+}
+
+pub struct TuiState {
+    db_path: PathBuf,
+    pub tools: Vec<Tool>,
+    pub query: String,
+    pub sessions: Vec<Session>,
+    pub list_state: ListState,
+    pub focus: Focus,
+    pub transcript: Option<Transcript>,
+    /// One-line message in the footer, cleared by the next key press.
+    pub status: Option<String>,
+    /// Rows of the last rendered list or transcript, for paging.
+    pub page_height: usize,
+}
+
+impl TuiState {
+    pub fn new(db_path: PathBuf) -> Self {
+        let mut state = Self {
+            db_path,
+            tools: Tool::ALL.to_vec(),
+            query: String::new(),
+            sessions: Vec::new(),
+            list_state: ListState::default(),
+            focus: Focus::List,
+            transcript: None,
+            status: None,
+            page_height: 10,
+        };
+        state.reload();
+        state
+    }
+
+    /// Re-run the search with the current query and tool filters, keeping
+    /// the selected session when it is still listed.
+    pub fn reload(&mut self) {
+        let selected_id = self.selected().map(|session| session.id.clone());
+        self.sessions =
+            search_sessions(&self.db_path, &self.tools, &self.query).unwrap_or_else(|err| {
+                self.status = Some(format!("Search failed: {}", err));
+                Vec::new()
+            });
+        let index = selected_id
+            .and_then(|id| self.sessions.iter().position(|session| session.id == id))
+            .or((!self.sessions.is_empty()).then_some(0));
+        self.list_state.select(index);
+    }
+
+    pub fn selected(&self) -> Option<&Session> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.sessions.get(index))
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.status = None;
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+        match self.focus {
+            Focus::List => self.handle_list_key(key),
+            Focus::Search => {
+                self.handle_search_key(key);
+                Action::None
+            }
+            Focus::Transcript => self.handle_transcript_key(key),
+        }
+    }
+
+    fn handle_list_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char('q') => return Action::Quit,
+            KeyCode::Char('/') => self.focus = Focus::Search,
+            KeyCode::Esc if !self.query.is_empty() => {
+                self.query.clear();
+                self.reload();
+            }
+            KeyCode::Char(digit @ '1'..='9') => {
+                let index = digit as usize - '1' as usize;
+                if let Some(tool) = Tool::ALL.get(index) {
+                    self.toggle_tool(*tool);
+                }
+            }
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => self.open_transcript(),
+            KeyCode::Char('r') => {
+                if let Some(session) = self.selected() {
+                    return Action::Resume(Box::new(session.clone()));
+                }
+            }
+            _ => self.move_selection(key.code),
+        }
+        Action::None
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.focus = Focus::List,
+            KeyCode::Esc => {
+                self.focus = Focus::List;
+                self.query.clear();
+                self.reload();
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.reload();
+            }
+            KeyCode::Char(ch) => {
+                self.query.push(ch);
+                self.reload();
+            }
+            _ => self.move_selection(key.code),
+        }
+    }
+
+    fn handle_transcript_key(&mut self, key: KeyEvent) -> Action {
+        let page = self.page_height.max(1);
+        let Some(transcript) = self.transcript.as_mut() else {
+            self.focus = Focus::List;
+            return Action::None;
+        };
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Left | KeyCode::Char('h') => {
+                self.transcript = None;
+                self.focus = Focus::List;
+            }
+            KeyCode::Char('r') => return Action::Resume(Box::new(transcript.session.clone())),
+            KeyCode::Down | KeyCode::Char('j') => transcript.scroll += 1,
+            KeyCode::Up | KeyCode::Char('k') => {
+                transcript.scroll = transcript.scroll.saturating_sub(1)
+            }
+            KeyCode::PageDown | KeyCode::Char(' ') => transcript.scroll += page,
+            KeyCode::PageUp => transcript.scroll = transcript.scroll.saturating_sub(page),
+            KeyCode::Home | KeyCode::Char('g') => transcript.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => transcript.scroll = usize::MAX,
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn move_selection(&mut self, code: KeyCode) {
+        if self.sessions.is_empty() {
+            return;
+        }
+        let last = self.sessions.len() - 1;
+        let current = self.list_state.selected().unwrap_or(0);
+        // Each session takes two rows in the list.
+        let page = (self.page_height / 2).max(1);
+        let next = match code {
+            KeyCode::Down | KeyCode::Char('j') => current.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => current.saturating_sub(1),
+            KeyCode::PageDown => current.saturating_add(page),
+            KeyCode::PageUp => current.saturating_sub(page),
+            KeyCode::Home | KeyCode::Char('g') => 0,
+            KeyCode::End | KeyCode::Char('G') => last,
+            _ => return,
+        };
+        self.list_state.select(Some(next.min(last)));
+    }
+
+    fn toggle_tool(&mut self, tool: Tool) {
+        if let Some(position) = self.tools.iter().position(|enabled| *enabled == tool) {
+            self.tools.remove(position);
+        } else {
+            self.tools.push(tool);
+        }
+        self.reload();
+    }
+
+    fn open_transcript(&mut self) {
+        let Some(session) = self.selected().cloned() else {
+            return;
+        };
+        match load_messages_for_session(&self.db_path, &session.id) {
+            Ok(messages) => {
+                self.transcript = Some(Transcript {
+                    lines: transcript_lines(&messages),
+                    session,
+                    scroll: 0,
+                });
+                self.focus = Focus::Transcript;
+            }
+            Err(err) => self.status = Some(format!("Failed to load the session: {}", err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::initialize_database;
+    use rusqlite::Connection;
+    use tempfile::NamedTempFile;
+
+    /// Ten sessions alternating between Claude Code and OpenCode, listed
+    /// from `s0` to `s9`.
+    fn seeded_db() -> NamedTempFile {
+        let file = NamedTempFile::new().unwrap();
+        let db = Connection::open(file.path()).unwrap();
+        initialize_database(&db).unwrap();
+        for i in 0..10 {
+            let tool = if i % 2 == 0 {
+                "claude_code"
+            } else {
+                "opencode"
+            };
+            db.execute(
+                "INSERT INTO sessions (id, tool, start_time, message_count, file_path, last_updated)
+                 VALUES (?1, ?2, 0, 0, '/tmp/session.jsonl', ?3)",
+                rusqlite::params![format!("s{}", i), tool, 100 - i],
+            )
+            .unwrap();
+        }
+        file
+    }
+
+    fn press(state: &mut TuiState, code: KeyCode) -> Action {
+        state.handle_key(KeyEvent::from(code))
+    }
+
+    fn selected_id(state: &TuiState) -> Option<&str> {
+        state.selected().map(|session| session.id.as_str())
+    }
+
+    #[test]
+    fn digit_keys_toggle_tools() {
+        let db = seeded_db();
+        let mut state = TuiState::new(db.path().to_path_buf());
+        assert_eq!(state.sessions.len(), 10);
+
+        press(&mut state, KeyCode::Char('2'));
+        assert!(!state.tools.contains(&Tool::OpenCode));
+        assert_eq!(state.sessions.len(), 5);
+        assert!(
+            state
+                .sessions
+                .iter()
+                .all(|session| session.tool == Tool::ClaudeCode)
+        );
+
+        press(&mut state, KeyCode::Char('2'));
+        assert!(state.tools.contains(&Tool::OpenCode));
+        assert_eq!(state.sessions.len(), 10);
+    }
+
+    #[test]
+    fn reload_keeps_the_selected_session() {
+        let db = seeded_db();
+        let mut state = TuiState::new(db.path().to_path_buf());
+        assert_eq!(selected_id(&state), Some("s0"));
+
+        press(&mut state, KeyCode::Down);
+        press(&mut state, KeyCode::Char('j'));
+        assert_eq!(selected_id(&state), Some("s2"));
+
+        press(&mut state, KeyCode::Char('2'));
+        assert_eq!(state.list_state.selected(), Some(1));
+        assert_eq!(selected_id(&state), Some("s2"));
+
+        // A session that is no longer listed gives way to the first one.
+        press(&mut state, KeyCode::Char('2'));
+        press(&mut state, KeyCode::Down);
+        assert_eq!(selected_id(&state), Some("s3"));
+        press(&mut state, KeyCode::Char('2'));
+        assert_eq!(selected_id(&state), Some("s0"));
+    }
+
+    #[test]
+    fn page_keys_move_by_a_screen_of_sessions() {
+        let db = seeded_db();
+        let mut state = TuiState::new(db.path().to_path_buf());
+        // Three sessions of two rows each.
+        state.page_height = 6;
+
+        press(&mut state, KeyCode::PageDown);
+        assert_eq!(state.list_state.selected(), Some(3));
+        press(&mut state, KeyCode::PageDown);
+        press(&mut state, KeyCode::PageDown);
+        press(&mut state, KeyCode::PageDown);
+        assert_eq!(state.list_state.selected(), Some(9));
+        press(&mut state, KeyCode::PageUp);
+        assert_eq!(state.list_state.selected(), Some(6));
+        press(&mut state, KeyCode::Home);
+        assert_eq!(state.list_state.selected(), Some(0));
+        press(&mut state, KeyCode::End);
+        assert_eq!(state.list_state.selected(), Some(9));
+    }
+
+    #[test]
+    fn transcript_pages_and_returns_to_the_list() {
+        let db = seeded_db();
+        let mut state = TuiState::new(db.path().to_path_buf());
+        state.page_height = 6;
+
+        press(&mut state, KeyCode::Enter);
+        assert_eq!(state.focus, Focus::Transcript);
+        press(&mut state, KeyCode::PageDown);
+        press(&mut state, KeyCode::Down);
+        assert_eq!(state.transcript.as_ref().unwrap().scroll, 7);
+        press(&mut state, KeyCode::PageUp);
+        assert_eq!(state.transcript.as_ref().unwrap().scroll, 1);
+
+        assert!(matches!(
+            press(&mut state, KeyCode::Char('r')),
+            Action::Resume(session) if session.id == "s0"
+        ));
+        press(&mut state, KeyCode::Esc);
+        assert_eq!(state.focus, Focus::List);
+        assert!(state.transcript.is_none());
+    }
+}
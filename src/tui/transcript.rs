@@ -0,0 +1,231 @@
+use chrono::Local;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::models::{Message, Role, ToolCall, ToolResult};
+
+/// Output lines of a tool result shown before the rest is elided.
+const TOOL_OUTPUT_MAX_LINES: usize = 8;
+
+pub fn role_color(role: Role) -> Color {
+    match role {
+        Role::User => Color::Blue,
+        Role::Assistant => Color::Green,
+        Role::Thinking => Color::Magenta,
+        Role::ToolCall => Color::Yellow,
+        Role::ToolResult => Color::Cyan,
+        Role::System => Color::Gray,
+    }
+}
+
+/// A session's messages as styled lines: a header per message, then its
+/// content with light markdown formatting. Tool calls are summarized and
+/// tool output is cut short.
+pub fn transcript_lines(messages: &[Message]) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for message in messages {
+        lines.push(Line::from(vec![
+            Span::styled(
+                message.role.label(),
+                Style::default()
+                    .fg(role_color(message.role))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    " · {}",
+                    message
+                        .timestamp
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M")
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.extend(message_lines(message));
+        lines.push(Line::default());
+    }
+    lines
+}
+
+fn message_lines(message: &Message) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    match message.role {
+        Role::ToolCall => {
+            let summary = ToolCall::from_content(&message.content)
+                .map(|call| call.summary())
+                .unwrap_or_else(|| message.content.lines().next().unwrap_or("").to_string());
+            vec![Line::styled(
+                format!("⚙ {}", summary),
+                Style::default().fg(Color::Yellow),
+            )]
+        }
+        Role::ToolResult => {
+            let (output, style) = match ToolResult::from_content(&message.content) {
+                Some(result) if result.is_error => (result.output, Style::default().fg(Color::Red)),
+                Some(result) => (result.output, dim),
+                None => (message.content.clone(), dim),
+            };
+            let total = output.lines().count();
+            let mut lines: Vec<_> = output
+                .lines()
+                .take(TOOL_OUTPUT_MAX_LINES)
+                .map(|line| Line::styled(format!("  {}", line), style))
+                .collect();
+            if total > TOOL_OUTPUT_MAX_LINES {
+                lines.push(Line::styled(
+                    format!("  … {} more lines", total - TOOL_OUTPUT_MAX_LINES),
+                    dim.add_modifier(Modifier::ITALIC),
+                ));
+            }
+            lines
+        }
+        Role::Thinking => message
+            .content
+            .lines()
+            .map(|line| Line::styled(line.to_string(), dim.add_modifier(Modifier::ITALIC)))
+            .collect(),
+        Role::User | Role::Assistant | Role::System => markdown_lines(&message.content),
+    }
+}
+
+/// Line-by-line markdown: headings, code blocks, lists, quotes, and inline
+/// code and bold text. Anything else is shown as written.
+pub fn markdown_lines(content: &str) -> Vec<Line<'static>> {
+    let code = Style::default().fg(Color::Cyan);
+    let mut in_code_block = false;
+    let mut lines = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            lines.push(Line::styled(
+                line.to_string(),
+                Style::default().fg(Color::DarkGray),
+            ));
+            continue;
+        }
+        if in_code_block {
+            lines.push(Line::styled(format!("  {}", line), code));
+            continue;
+        }
+
+        let heading_level = trimmed.chars().take_while(|ch| *ch == '#').count();
+        if (1..=6).contains(&heading_level) && trimmed[heading_level..].starts_with(' ') {
+            let style = Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+            lines.push(Line::from(inline_spans(
+                trimmed[heading_level..].trim(),
+                style,
+            )));
+        } else if let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker))
+        {
+            let indent = " ".repeat(line.len() - trimmed.len());
+            let mut spans = vec![Span::raw(format!("{}  • ", indent))];
+            spans.extend(inline_spans(item, Style::default()));
+            lines.push(Line::from(spans));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let style = Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::ITALIC);
+            let mut spans = vec![Span::styled("│ ", Style::default().fg(Color::DarkGray))];
+            spans.extend(inline_spans(quote.trim_start(), style));
+            lines.push(Line::from(spans));
+        } else {
+            lines.push(Line::from(inline_spans(line, Style::default())));
+        }
+    }
+    lines
+}
+
+/// Spans for inline `code` and **bold** text on top of `base`. Markers
+/// without a closing one are kept as plain text.
+fn inline_spans(text: &str, base: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+    // Where to look for the next marker in `rest`, past unmatched ones.
+    let mut from = 0;
+    while !rest.is_empty() {
+        let next_code = rest[from..].find('`').map(|i| from + i);
+        let next_bold = rest[from..].find("**").map(|i| from + i);
+        let (start, marker, style) = match (next_code, next_bold) {
+            (Some(code), bold) if bold.is_none_or(|bold| code < bold) => {
+                (code, "`", base.fg(Color::Cyan))
+            }
+            (_, Some(bold)) => (bold, "**", base.add_modifier(Modifier::BOLD)),
+            _ => break,
+        };
+        let after = &rest[start + marker.len()..];
+        let Some(end) = after.find(marker).filter(|end| *end > 0) else {
+            from = start + marker.len();
+            continue;
+        };
+        if start > 0 {
+            spans.push(Span::styled(rest[..start].to_string(), base));
+        }
+        spans.push(Span::styled(after[..end].to_string(), style));
+        rest = &after[end + marker.len()..];
+        from = 0;
+    }
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest.to_string(), base));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn markdown_lines_format_lists_code_and_inline_markup() {
+        let lines = markdown_lines(
+            "## Plan\n- run `cargo test` **first**\n```rust\nfn main() {}\n```\nplain `unclosed",
+        );
+        let texts: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Plan",
+                "  • run cargo test first",
+                "```rust",
+                "  fn main() {}",
+                "```",
+                "plain `unclosed",
+            ]
+        );
+
+        assert!(
+            lines[0].spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::BOLD)
+        );
+        let code = &lines[1].spans[2];
+        assert_eq!(code.content, "cargo test");
+        assert_eq!(code.style.fg, Some(Color::Cyan));
+        assert!(
+            lines[1].spans[4]
+                .style
+                .add_modifier
+                .contains(Modifier::BOLD)
+        );
+    }
+
+    #[test]
+    fn inline_spans_style_markup_after_an_unmatched_marker() {
+        let spans = inline_spans("run `x and **bold**", Style::default());
+        let contents: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(contents, vec!["run `x and ", "bold"]);
+        assert!(spans[1].style.add_modifier.contains(Modifier::BOLD));
+    }
+}
@@ -0,0 +1,160 @@
+use chrono::Local;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
+
+use crate::models::{Session, Tool};
+use crate::tui::state::{Focus, TuiState};
+
+const HIGHLIGHT: Style = Style::new().bg(Color::DarkGray);
+
+pub fn draw(frame: &mut Frame, state: &mut TuiState) {
+    let [header, main, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    frame.render_widget(header_line(state), header);
+    if state.transcript.is_some() {
+        draw_transcript(frame, state, main);
+    } else {
+        draw_list(frame, state, main);
+    }
+    frame.render_widget(footer_line(state), footer);
+}
+
+/// Tool toggles with their number keys, then the search query.
+fn header_line(state: &TuiState) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (index, tool) in Tool::ALL.iter().enumerate() {
+        let style = if state.tools.contains(tool) {
+            Style::default().fg(Color::Black).bg(Color::Green)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        spans.push(Span::styled(
+            format!(" {} {} ", index + 1, tool.display_name()),
+            style,
+        ));
+        spans.push(Span::raw(" "));
+    }
+
+    let searching = state.focus == Focus::Search;
+    if searching || !state.query.is_empty() {
+        spans.push(Span::styled(
+            " / ",
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(state.query.clone()));
+        if searching {
+            spans.push(Span::styled("▏", Style::default().fg(Color::Yellow)));
+        }
+    }
+    Line::from(spans)
+}
+
+fn footer_line(state: &TuiState) -> Line<'static> {
+    if let Some(status) = &state.status {
+        return Line::styled(status.clone(), Style::default().fg(Color::Yellow));
+    }
+    let hints = match state.focus {
+        Focus::List => "↑↓ move  enter open  / search  1-4 tools  r resume  q quit",
+        Focus::Search => "type to search  enter done  esc clear",
+        Focus::Transcript => "↑↓ pgup pgdn scroll  g/G top/bottom  r resume  esc back",
+    };
+    Line::styled(hints, Style::default().fg(Color::DarkGray))
+}
+
+fn draw_list(frame: &mut Frame, state: &mut TuiState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .title(format!(" {} sessions ", state.sessions.len()));
+    state.page_height = block.inner(area).height as usize;
+
+    if state.sessions.is_empty() {
+        let message = if state.query.is_empty() {
+            "No sessions indexed"
+        } else {
+            "No sessions match the search"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::styled(message, Style::default().fg(Color::DarkGray)))
+                .block(block),
+            area,
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = state.sessions.iter().map(session_item).collect();
+    let list = List::new(items).block(block).highlight_style(HIGHLIGHT);
+    frame.render_stateful_widget(list, area, &mut state.list_state);
+}
+
+/// Title on the first row; tool, project, date and size dimmed below it.
+fn session_item(session: &Session) -> ListItem<'static> {
+    let title = session
+        .display_title()
+        .and_then(|title| title.lines().next())
+        .unwrap_or("Untitled session")
+        .to_string();
+    let project = session
+        .project_path
+        .as_deref()
+        .unwrap_or("no project")
+        .to_string();
+    let details = format!(
+        "  {} · {} · {} · {} messages",
+        session.tool.display_name(),
+        project,
+        session
+            .last_updated
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M"),
+        session.message_count
+    );
+
+    let mut title_spans = Vec::new();
+    if session.starred {
+        title_spans.push(Span::styled("★ ", Style::default().fg(Color::Yellow)));
+    }
+    title_spans.push(Span::styled(
+        title,
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    ListItem::new(Text::from(vec![
+        Line::from(title_spans),
+        Line::styled(details, Style::default().fg(Color::DarkGray)),
+    ]))
+}
+
+fn draw_transcript(frame: &mut Frame, state: &mut TuiState, area: Rect) {
+    let Some(transcript) = state.transcript.as_mut() else {
+        return;
+    };
+    let title = transcript
+        .session
+        .display_title()
+        .and_then(|title| title.lines().next())
+        .unwrap_or("Untitled session")
+        .to_string();
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .title(format!(" {} ", title));
+    let inner = block.inner(area);
+    state.page_height = inner.height as usize;
+
+    let paragraph = Paragraph::new(transcript.lines.clone()).wrap(Wrap { trim: false });
+    // Clamp here rather than in the key handler: only the view knows how
+    // many rows the wrapped text takes.
+    let max_scroll = paragraph
+        .line_count(inner.width)
+        .saturating_sub(inner.height as usize);
+    transcript.scroll = transcript.scroll.min(max_scroll);
+    let scroll = transcript.scroll.min(u16::MAX as usize) as u16;
+
+    frame.render_widget(paragraph.block(block).scroll((scroll, 0)), area);
+}
@@ -0,0 +1,221 @@
+use adw::prelude::*;
+use gtk::glib;
+use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, adw, gtk};
+use std::path::PathBuf;
+
+use crate::database::load_bookmarks;
+use crate::models::MessageBookmark;
+
+/// Characters of each bookmarked message shown in the list.
+const PREVIEW_LEN: usize = 200;
+
+/// Bookmarked messages of every session, most recently bookmarked first.
+#[derive(Debug)]
+pub struct BookmarksView {
+    db_path: PathBuf,
+    bookmarks: Vec<MessageBookmark>,
+    bookmarks_list: gtk::ListBox,
+}
+
+#[derive(Debug)]
+pub enum BookmarksMsg {
+    Refresh,
+    Activated(i32),
+    Remove(usize),
+}
+
+#[derive(Debug)]
+pub enum BookmarksOutput {
+    /// Open the session scrolled to the message.
+    OpenMessage(String, usize),
+    /// Remove the bookmark on a message.
+    Unbookmark(String, usize),
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for BookmarksView {
+    type Init = PathBuf;
+    type Input = BookmarksMsg;
+    type Output = BookmarksOutput;
+    type Widgets = BookmarksViewWidgets;
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+
+            #[name = "content_stack"]
+            gtk::Stack {
+                set_vexpand: true,
+
+                #[name = "empty_state"]
+                adw::StatusPage {
+                    set_icon_name: Some("user-bookmarks-symbolic"),
+                    set_title: "No Bookmarked Messages",
+                    set_description: Some("Bookmark a message from its header to find it here"),
+                },
+
+                #[name = "bookmarks_scroller"]
+                gtk::ScrolledWindow {
+                    set_hscrollbar_policy: gtk::PolicyType::Never,
+
+                    adw::Clamp {
+                        set_maximum_size: 960,
+
+                        #[local_ref]
+                        bookmarks_list -> gtk::ListBox {
+                            set_margin_all: 18,
+                            set_valign: gtk::Align::Start,
+                            add_css_class: "boxed-list",
+                            set_selection_mode: gtk::SelectionMode::None,
+                            connect_row_activated[sender] => move |_, row| {
+                                sender.input(BookmarksMsg::Activated(row.index()));
+                            },
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        db_path: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = Self {
+            db_path,
+            bookmarks: Vec::new(),
+            bookmarks_list: gtk::ListBox::new(),
+        };
+
+        let bookmarks_list = &model.bookmarks_list;
+        let widgets = view_output!();
+        sender.input(BookmarksMsg::Refresh);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
+        match message {
+            BookmarksMsg::Refresh => {
+                self.bookmarks = load_bookmarks(&self.db_path, PREVIEW_LEN).unwrap_or_else(|err| {
+                    tracing::error!("Failed to load bookmarks: {}", err);
+                    Vec::new()
+                });
+                self.rebuild_rows(&sender);
+            }
+            BookmarksMsg::Activated(index) => {
+                if let Some(bookmark) = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| self.bookmarks.get(index))
+                {
+                    let _ = sender.output(BookmarksOutput::OpenMessage(
+                        bookmark.session.id.clone(),
+                        bookmark.message.index,
+                    ));
+                }
+            }
+            BookmarksMsg::Remove(index) => {
+                if let Some(bookmark) = self.bookmarks.get(index) {
+                    let _ = sender.output(BookmarksOutput::Unbookmark(
+                        bookmark.session.id.clone(),
+                        bookmark.message.index,
+                    ));
+                }
+            }
+        }
+    }
+
+    fn post_view(&self, widgets: &mut Self::Widgets) {
+        if self.bookmarks.is_empty() {
+            widgets
+                .content_stack
+                .set_visible_child(&widgets.empty_state);
+        } else {
+            widgets
+                .content_stack
+                .set_visible_child(&widgets.bookmarks_scroller);
+        }
+    }
+}
+
+impl BookmarksView {
+    fn rebuild_rows(&self, sender: &ComponentSender<Self>) {
+        while let Some(child) = self.bookmarks_list.first_child() {
+            self.bookmarks_list.remove(&child);
+        }
+
+        for (index, bookmark) in self.bookmarks.iter().enumerate() {
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&bookmark_title(bookmark)))
+                .subtitle(glib::markup_escape_text(&bookmark_subtitle(bookmark)))
+                .title_lines(2)
+                .activatable(true)
+                .tooltip_text(format!(
+                    "Bookmarked {}",
+                    bookmark
+                        .bookmarked_at
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                ))
+                .build();
+            row.add_prefix(&gtk::Image::from_icon_name(
+                bookmark.session.tool.icon_name(),
+            ));
+
+            let copy_link = gtk::Button::builder()
+                .icon_name("insert-link-symbolic")
+                .tooltip_text("Copy link")
+                .valign(gtk::Align::Center)
+                .css_classes(["flat"])
+                .build();
+            let link = bookmark.permalink().to_string();
+            copy_link.connect_clicked(move |button| button.clipboard().set_text(&link));
+            row.add_suffix(&copy_link);
+
+            let remove = gtk::Button::builder()
+                .icon_name("user-trash-symbolic")
+                .tooltip_text("Remove bookmark")
+                .valign(gtk::Align::Center)
+                .css_classes(["flat"])
+                .build();
+            let input = sender.input_sender().clone();
+            remove.connect_clicked(move |_| input.emit(BookmarksMsg::Remove(index)));
+            row.add_suffix(&remove);
+
+            self.bookmarks_list.append(&row);
+        }
+    }
+}
+
+/// The start of the message, on one line.
+fn bookmark_title(bookmark: &MessageBookmark) -> String {
+    let text = bookmark
+        .message
+        .content_preview
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if bookmark.message.is_truncated() {
+        format!("{}\u{2026}", text)
+    } else {
+        text
+    }
+}
+
+/// `ASSISTANT · message 57 · <session title> · 2025-01-01 12:00`.
+fn bookmark_subtitle(bookmark: &MessageBookmark) -> String {
+    let session = &bookmark.session;
+    let title = session
+        .display_title()
+        .map(str::to_string)
+        .or_else(|| session.project_path.clone())
+        .unwrap_or_else(|| session.id.clone());
+    format!(
+        "{} · message {} · {} · {}",
+        bookmark.message.role.label(),
+        bookmark.message.index,
+        title,
+        bookmark.message.timestamp.format("%Y-%m-%d %H:%M")
+    )
+}
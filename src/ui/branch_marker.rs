@@ -0,0 +1,52 @@
+use relm4::gtk;
+use relm4::gtk::prelude::*;
+
+use crate::models::ConversationBranch;
+use crate::ui::transcript;
+
+/// Collapsed marker above a prompt listing the branches abandoned there.
+pub fn build(branches: &[ConversationBranch]) -> gtk::Expander {
+    let title = match branches.len() {
+        1 => "1 abandoned branch".to_string(),
+        count => format!("{} abandoned branches", count),
+    };
+    let header = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+    header.append(&gtk::Image::from_icon_name(
+        "media-playlist-shuffle-symbolic",
+    ));
+    let label = gtk::Label::new(Some(&title));
+    label.add_css_class("caption");
+    header.append(&label);
+    header.set_tooltip_text(Some(
+        "The prompt was rewound or edited here; earlier attempts are not searched",
+    ));
+
+    let body = gtk::Box::new(gtk::Orientation::Vertical, 12);
+    body.set_margin_top(6);
+    for (number, branch) in branches.iter().enumerate() {
+        let section = gtk::Box::new(gtk::Orientation::Vertical, 4);
+        section.add_css_class("branch-section");
+
+        let heading = gtk::Label::new(Some(&format!(
+            "Branch {} · {}",
+            number + 1,
+            branch
+                .messages
+                .first()
+                .map(|message| message.timestamp.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default()
+        )));
+        heading.add_css_class("heading");
+        heading.set_xalign(0.0);
+        section.append(&heading);
+
+        section.append(&transcript::build(&branch.messages));
+        body.append(&section);
+    }
+
+    let expander = gtk::Expander::new(None);
+    expander.add_css_class("branch-marker");
+    expander.set_label_widget(Some(&header));
+    expander.set_child(Some(&body));
+    expander
+}
@@ -1,24 +1,53 @@
 use gtk::prelude::*;
 use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, gtk};
 
-use crate::models::session::Tool;
+use crate::models::session::{Session, Tool};
+use crate::models::tag::{format_tags, parse_tags};
+use crate::models::usage::format_token_count;
+use crate::models::{ChangedFile, SessionUsage};
+use crate::pricing::format_cost;
+use crate::ui::markdown::pango_escape;
+use crate::ui::session_row::star_icon;
 
 #[derive(Debug)]
 pub struct DetailContextPane {
     project_name: Option<String>,
     tool: Option<Tool>,
+    usage: SessionUsage,
+    project_path: Option<String>,
+    changed_files: Vec<ChangedFile>,
+    starred: bool,
+    /// Tags as typed, applied with Enter.
+    tags_buffer: gtk::EntryBuffer,
+    /// Markdown note, saved with its button.
+    note_buffer: gtk::TextBuffer,
 }
 
 #[derive(Debug)]
 pub enum DetailContextPaneMsg {
-    SetSession { project_name: String, tool: Tool },
+    SetSession {
+        project_name: String,
+        session: Box<Session>,
+        changed_files: Vec<ChangedFile>,
+        note: Option<String>,
+    },
     ClearSession,
     ResumeClicked,
+    ExportClicked,
+    StarToggled(bool),
+    /// The session was starred or unstarred elsewhere.
+    SetStarred(bool),
+    ApplyTags,
+    SaveNote,
 }
 
 #[derive(Debug)]
 pub enum DetailContextPaneOutput {
     ResumeClicked,
+    ExportClicked,
+    StarToggled(bool),
+    TagsChanged(Vec<String>),
+    NoteChanged(String),
 }
 
 #[relm4::component(pub)]
@@ -67,6 +96,139 @@ impl SimpleComponent for DetailContextPane {
                     #[watch]
                     set_label: model.project_name.as_deref().unwrap_or("No session"),
                 },
+
+                gtk::ToggleButton {
+                    add_css_class: "flat",
+                    set_valign: gtk::Align::Center,
+                    #[watch]
+                    set_visible: model.project_name.is_some(),
+                    #[watch]
+                    set_active: model.starred,
+                    #[watch]
+                    set_icon_name: star_icon(model.starred),
+                    #[watch]
+                    set_tooltip_text: Some(if model.starred { "Unstar" } else { "Star" }),
+                    connect_toggled[sender] => move |button| {
+                        sender.input(DetailContextPaneMsg::StarToggled(button.is_active()));
+                    },
+                },
+            },
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 4,
+                #[watch]
+                set_visible: !model.usage.is_empty(),
+
+                gtk::Label {
+                    set_label: "Usage",
+                    set_halign: gtk::Align::Start,
+                    add_css_class: "heading",
+                },
+
+                gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    set_wrap: true,
+                    set_wrap_mode: gtk::pango::WrapMode::WordChar,
+                    set_selectable: true,
+                    add_css_class: "caption",
+                    #[watch]
+                    set_visible: !model.usage.models.is_empty(),
+                    #[watch]
+                    set_label: &model.usage.models.join("\n"),
+                },
+
+                gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    set_wrap: true,
+                    add_css_class: "dim-label",
+                    #[watch]
+                    set_label: &usage_tokens_label(&model.usage),
+                },
+
+                gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    add_css_class: "dim-label",
+                    #[watch]
+                    set_visible: model.usage.cost_usd.is_some(),
+                    #[watch]
+                    set_label: &usage_cost_label(&model.usage),
+                },
+            },
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 4,
+                #[watch]
+                set_visible: !model.changed_files.is_empty(),
+
+                gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    add_css_class: "heading",
+                    #[watch]
+                    set_label: &format!("Files changed ({})", model.changed_files.len()),
+                },
+
+                gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    set_xalign: 0.0,
+                    set_wrap: true,
+                    set_wrap_mode: gtk::pango::WrapMode::WordChar,
+                    set_selectable: true,
+                    add_css_class: "caption",
+                    add_css_class: "monospace",
+                    #[watch]
+                    set_markup: &changed_files_markup(
+                        &model.changed_files,
+                        model.project_path.as_deref(),
+                    ),
+                },
+            },
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 4,
+                #[watch]
+                set_visible: model.project_name.is_some(),
+
+                gtk::Label {
+                    set_label: "Tags",
+                    set_halign: gtk::Align::Start,
+                    add_css_class: "heading",
+                },
+
+                gtk::Entry {
+                    set_buffer: &model.tags_buffer,
+                    set_placeholder_text: Some("#bugfix #design"),
+                    set_tooltip_text: Some("Tags separated by spaces; press Enter to apply"),
+                    connect_activate => DetailContextPaneMsg::ApplyTags,
+                },
+
+                gtk::Label {
+                    set_label: "Note",
+                    set_halign: gtk::Align::Start,
+                    add_css_class: "heading",
+                    set_margin_top: 6,
+                },
+
+                gtk::Frame {
+                    gtk::TextView {
+                        set_buffer: Some(&model.note_buffer),
+                        set_wrap_mode: gtk::WrapMode::WordChar,
+                        set_height_request: 96,
+                        set_top_margin: 6,
+                        set_bottom_margin: 6,
+                        set_left_margin: 6,
+                        set_right_margin: 6,
+                        set_tooltip_text: Some("Markdown; searched along with the messages"),
+                    },
+                },
+
+                gtk::Button {
+                    set_label: "Save Note",
+                    set_halign: gtk::Align::Start,
+                    connect_clicked => DetailContextPaneMsg::SaveNote,
+                },
             },
 
             #[name = "resume_button"]
@@ -79,6 +241,16 @@ impl SimpleComponent for DetailContextPane {
                 set_sensitive: model.project_name.is_some(),
                 connect_clicked => DetailContextPaneMsg::ResumeClicked,
             },
+
+            #[name = "export_button"]
+            gtk::Button {
+                set_label: "Export as Markdown…",
+                set_halign: gtk::Align::Start,
+                // Any loaded session can be exported, project or not.
+                #[watch]
+                set_sensitive: model.tool.is_some(),
+                connect_clicked => DetailContextPaneMsg::ExportClicked,
+            },
         }
     }
 
@@ -90,6 +262,12 @@ impl SimpleComponent for DetailContextPane {
         let model = Self {
             project_name: None,
             tool: None,
+            usage: SessionUsage::default(),
+            project_path: None,
+            changed_files: Vec::new(),
+            starred: false,
+            tags_buffer: gtk::EntryBuffer::default(),
+            note_buffer: gtk::TextBuffer::new(None),
         };
         let widgets = view_output!();
         ComponentParts { model, widgets }
@@ -97,17 +275,56 @@ impl SimpleComponent for DetailContextPane {
 
     fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
         match message {
-            DetailContextPaneMsg::SetSession { project_name, tool } => {
+            DetailContextPaneMsg::SetSession {
+                project_name,
+                session,
+                changed_files,
+                note,
+            } => {
+                self.tags_buffer.set_text(format_tags(&session.tags));
+                self.note_buffer
+                    .set_text(note.as_deref().unwrap_or_default());
                 self.project_name = Some(project_name);
-                self.tool = Some(tool);
+                self.tool = Some(session.tool);
+                self.usage = session.usage;
+                self.project_path = session.project_path;
+                self.changed_files = changed_files;
+                self.starred = session.starred;
             }
             DetailContextPaneMsg::ClearSession => {
                 self.project_name = None;
                 self.tool = None;
+                self.usage = SessionUsage::default();
+                self.project_path = None;
+                self.changed_files.clear();
+                self.starred = false;
+                self.tags_buffer.set_text("");
+                self.note_buffer.set_text("");
             }
             DetailContextPaneMsg::ResumeClicked => {
                 let _ = sender.output(DetailContextPaneOutput::ResumeClicked);
             }
+            DetailContextPaneMsg::ExportClicked => {
+                let _ = sender.output(DetailContextPaneOutput::ExportClicked);
+            }
+            DetailContextPaneMsg::StarToggled(starred) => {
+                // Also fired when the button follows `SetStarred`.
+                if starred != self.starred {
+                    self.starred = starred;
+                    let _ = sender.output(DetailContextPaneOutput::StarToggled(starred));
+                }
+            }
+            DetailContextPaneMsg::SetStarred(starred) => self.starred = starred,
+            DetailContextPaneMsg::ApplyTags => {
+                let tags = parse_tags(&self.tags_buffer.text());
+                self.tags_buffer.set_text(format_tags(&tags));
+                let _ = sender.output(DetailContextPaneOutput::TagsChanged(tags));
+            }
+            DetailContextPaneMsg::SaveNote => {
+                let (start, end) = self.note_buffer.bounds();
+                let note = self.note_buffer.text(&start, &end, false).to_string();
+                let _ = sender.output(DetailContextPaneOutput::NoteChanged(note));
+            }
         }
     }
 
@@ -117,3 +334,108 @@ impl SimpleComponent for DetailContextPane {
         }
     }
 }
+
+fn usage_tokens_label(usage: &SessionUsage) -> String {
+    let tokens = &usage.tokens;
+    let mut label = format!(
+        "{} in · {} out",
+        format_token_count(tokens.input),
+        format_token_count(tokens.output)
+    );
+    let cached = tokens.cache_read + tokens.cache_write;
+    if cached > 0 {
+        label.push_str(&format!(" · {} cache", format_token_count(cached)));
+    }
+    label
+}
+
+fn usage_cost_label(usage: &SessionUsage) -> String {
+    let kind = if usage.cost_is_estimate {
+        "estimated"
+    } else {
+        "reported"
+    };
+    usage
+        .cost_usd
+        .map(|cost| format!("{} {} cost", format_cost(cost), kind))
+        .unwrap_or_default()
+}
+
+/// One line per changed file, relative to the project when inside it.
+fn changed_files_markup(files: &[ChangedFile], project_path: Option<&str>) -> String {
+    files
+        .iter()
+        .map(|file| {
+            let path = project_path
+                .and_then(|project| std::path::Path::new(&file.path).strip_prefix(project).ok())
+                .map(|relative| relative.display().to_string())
+                .unwrap_or_else(|| file.path.clone());
+            let path = pango_escape(&path);
+            let path = if file.deleted {
+                format!("<s>{}</s>", path)
+            } else {
+                path
+            };
+            if file.changes > 1 {
+                format!("{} <span alpha=\"55%\">×{}</span>", path, file.changes)
+            } else {
+                path
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TokenUsage;
+
+    #[test]
+    fn usage_labels_summarize_tokens_and_cost() {
+        let usage = SessionUsage {
+            models: vec!["gpt-5-codex".to_string()],
+            tokens: TokenUsage {
+                input: 12_345,
+                output: 678,
+                cache_read: 40_000,
+                cache_write: 0,
+            },
+            cost_usd: Some(0.4242),
+            cost_is_estimate: true,
+        };
+
+        assert_eq!(
+            usage_tokens_label(&usage),
+            "12.3k in · 678 out · 40.0k cache"
+        );
+        assert_eq!(usage_cost_label(&usage), "$0.42 estimated cost");
+
+        let reported = SessionUsage {
+            cost_is_estimate: false,
+            ..usage
+        };
+        assert_eq!(usage_cost_label(&reported), "$0.42 reported cost");
+    }
+
+    #[test]
+    fn changed_files_are_relative_to_the_project() {
+        let files = vec![
+            ChangedFile {
+                path: "/home/me/app/src/main.rs".to_string(),
+                changes: 3,
+                deleted: false,
+            },
+            ChangedFile {
+                path: "/tmp/a&b.txt".to_string(),
+                changes: 1,
+                deleted: true,
+            },
+        ];
+
+        assert_eq!(
+            changed_files_markup(&files, Some("/home/me/app")),
+            "src/main.rs <span alpha=\"55%\">×3</span>\n<s>/tmp/a&amp;b.txt</s>"
+        );
+    }
+}
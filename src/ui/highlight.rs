@@ -39,6 +39,11 @@ pub fn highlight_text(text: &str, query: &str) -> (String, usize) {
     (result, matches.len())
 }
 
+/// Whether `text` holds a case-insensitive occurrence of `query`.
+pub fn has_match(text: &str, query: &str) -> bool {
+    !query.is_empty() && !find_case_insensitive_matches_in_text(text, query).is_empty()
+}
+
 /// Highlight every case-insensitive occurrence of `query` inside existing Pango
 /// markup, skipping `<…>` tags and `&…;` entities so they are not altered.
 ///
@@ -2,6 +2,8 @@ use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
 use relm4::gtk;
 use relm4::gtk::prelude::*;
 
+use crate::ui::syntax::{Language, highlight_code};
+
 /// Intermediate representation of a parsed markdown block.
 /// Used by `render_markdown()` to produce GTK widgets, and directly testable.
 #[derive(Debug, Clone)]
@@ -44,6 +46,34 @@ pub fn pango_escape(s: &str) -> String {
     escaped
 }
 
+/// Redaction marker colors (Adwaita red 4 on white).
+const REDACTION_BG: &str = "#c01c28";
+const REDACTION_FG: &str = "#ffffff";
+
+/// Style every `[REDACTED:…]` marker found in Pango markup so redacted
+/// secrets stand out from the surrounding text.
+pub fn style_redaction_markers(markup: &str) -> String {
+    let markers = crate::redaction::find_markers(markup);
+    if markers.is_empty() {
+        return markup.to_string();
+    }
+
+    let mut result = String::with_capacity(markup.len() + markers.len() * 64);
+    let mut pos = 0usize;
+    for range in markers {
+        result.push_str(&markup[pos..range.start]);
+        result.push_str(&format!(
+            "<span background=\"{}\" foreground=\"{}\" font_family=\"monospace\">",
+            REDACTION_BG, REDACTION_FG
+        ));
+        result.push_str(&markup[range.clone()]);
+        result.push_str("</span>");
+        pos = range.end;
+    }
+    result.push_str(&markup[pos..]);
+    result
+}
+
 /// Parse markdown into intermediate blocks with Pango-markup strings.
 ///
 /// # Known Limitations
@@ -290,8 +320,9 @@ pub fn markdown_to_blocks(content: &str) -> Vec<MarkdownBlock> {
 
 /// Render markdown content as a vertical `gtk::Box` of native widgets.
 ///
-/// If `highlight_query` is provided, matches are highlighted in text blocks
-/// (paragraphs, headings, lists, tables) but not inside code blocks.
+/// Fenced code blocks are syntax highlighted when their language is known.
+/// If `highlight_query` is provided, matches are highlighted in every block,
+/// on top of the syntax colors.
 /// Returns the widget and the total number of highlighted matches.
 pub fn render_markdown(content: &str, highlight_query: Option<&str>) -> (gtk::Box, usize) {
     let container = gtk::Box::new(gtk::Orientation::Vertical, 4);
@@ -307,12 +338,13 @@ pub fn render_markdown(content: &str, highlight_query: Option<&str>) -> (gtk::Bo
 /// Apply highlighting to Pango markup if a query is provided.
 /// Returns the (possibly highlighted) markup and the match count.
 fn apply_highlight(markup: &str, highlight_query: Option<&str>) -> (String, usize) {
+    let markup = style_redaction_markers(markup);
     if let Some(query) = highlight_query
         && !query.is_empty()
     {
-        return crate::ui::highlight::highlight_in_markup(markup, query);
+        return crate::ui::highlight::highlight_in_markup(&markup, query);
     }
-    (markup.to_string(), 0)
+    (markup, 0)
 }
 
 /// Render a single `MarkdownBlock` as GTK widgets appended to `container`.
@@ -356,10 +388,10 @@ fn render_block(
             container.append(&label);
         }
         MarkdownBlock::CodeBlock { language, code } => {
-            // No highlighting inside code blocks (v1 scope exclusion)
             let wrapper = gtk::Box::new(gtk::Orientation::Vertical, 4);
             wrapper.add_css_class("code-block");
 
+            let syntax = language.as_deref().and_then(Language::from_fence);
             if let Some(language) = language {
                 let language_label = gtk::Label::new(Some(&language));
                 language_label.add_css_class("caption");
@@ -368,7 +400,11 @@ fn render_block(
                 wrapper.append(&language_label);
             }
 
-            let label = gtk::Label::new(Some(&code));
+            let (highlighted, count) =
+                apply_highlight(&highlight_code(&code, syntax), highlight_query);
+            matches += count;
+            let label = gtk::Label::new(None);
+            label.set_markup(&highlighted);
             label.set_wrap(true);
             label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
             label.set_halign(gtk::Align::Fill);
@@ -493,6 +529,16 @@ fn render_block(
 mod tests {
     use super::*;
 
+    #[test]
+    fn style_redaction_markers_wraps_markers() {
+        let styled = style_redaction_markers("key <b>[REDACTED:aws-key]</b> end");
+        assert_eq!(
+            styled,
+            "key <b><span background=\"#c01c28\" foreground=\"#ffffff\" font_family=\"monospace\">[REDACTED:aws-key]</span></b> end"
+        );
+        assert_eq!(style_redaction_markers("plain"), "plain");
+    }
+
     #[test]
     fn plain_text_produces_single_paragraph() {
         let blocks = markdown_to_blocks("Hello world");
@@ -2,30 +2,102 @@ use gtk::prelude::*;
 use relm4::factory::{DynamicIndex, FactoryComponent, FactorySender};
 use relm4::gtk;
 
-use crate::models::{MessagePreview, Role};
+use crate::models::{
+    ConversationBranch, Message, MessagePreview, Permalink, Role, Session, ToolCall, ToolResult,
+};
+use crate::ui::branch_marker;
 use crate::ui::highlight;
 use crate::ui::markdown;
+use crate::ui::tool_card;
+use crate::ui::transcript;
 
 pub struct MessageRowInit {
+    pub session_id: String,
     pub preview: MessagePreview,
     pub highlight_query: Option<String>,
+    pub show_tools: bool,
+    /// Branches abandoned in favour of this message.
+    pub branches: Vec<ConversationBranch>,
+    /// Subagent session spawned by this tool call.
+    pub subagent: Option<Session>,
+    pub bookmarked: bool,
+    /// Target of the permalink the session was opened from.
+    pub focused: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum MessageRowMsg {
+    ShowFull,
+    /// Ask for the full output of the result folded into a tool card.
+    ShowFullOutput,
+    /// Full content of message `message_index`, loaded by the parent;
+    /// re-renders the row in place.
+    SetFullContent {
+        message_index: usize,
+        content: String,
+    },
+    /// Fold a tool result under this row's tool call.
+    AttachResult(MessagePreview),
+    SetToolsVisible(bool),
+    /// Ask for the messages of the subagent spawned by this tool call.
+    LoadSubagent,
+    /// Messages of the subagent, loaded by the parent.
+    SetSubagentMessages(Vec<Message>),
+    /// The bookmark button was toggled.
+    BookmarkToggled(bool),
+    /// The bookmark was saved or reverted elsewhere.
+    SetBookmarked(bool),
+    /// Copy the message's permalink to the clipboard.
+    CopyLink,
 }
 
 #[derive(Debug)]
 pub enum MessageRowOutput {
-    MatchCount { count: usize },
+    /// Search matches in the row at `position`, re-sent whenever it re-renders.
+    MatchCount {
+        position: usize,
+        count: usize,
+    },
+    ExpandRequested {
+        position: usize,
+        message_index: usize,
+    },
+    SubagentRequested {
+        position: usize,
+        session_id: String,
+    },
+    BookmarkToggled {
+        message_index: usize,
+        bookmarked: bool,
+    },
 }
 
 #[derive(Debug)]
 pub struct MessageRow {
+    session_id: String,
     preview: MessagePreview,
+    /// Result folded under a tool call.
+    result: Option<MessagePreview>,
     highlight_query: Option<String>,
+    show_tools: bool,
+    branches: Vec<ConversationBranch>,
+    subagent: Option<Session>,
+    /// Transcript of `subagent`, loaded the first time it is expanded.
+    subagent_messages: Option<Vec<Message>>,
+    /// Kept to restore whether the transcript was open across re-renders.
+    subagent_expander: Option<gtk::Expander>,
+    /// Collapsible body of a tool, thinking or system row, to keep it open
+    /// across re-renders.
+    expander: Option<gtk::Expander>,
+    bookmarked: bool,
+    focused: bool,
+    index: DynamicIndex,
 }
 
 #[relm4::factory(pub)]
 impl FactoryComponent for MessageRow {
     type Init = MessageRowInit;
-    type Input = ();
+    type Input = MessageRowMsg;
     type Output = MessageRowOutput;
     type CommandOutput = ();
     type ParentWidget = gtk::Box;
@@ -36,10 +108,14 @@ impl FactoryComponent for MessageRow {
             set_spacing: 4,
             add_css_class: "message-row",
             add_css_class: self.preview.role.css_class(),
+            #[watch]
+            set_visible: self.show_tools || !self.preview.role.is_tool(),
 
+            // Tool cards carry their own header
             gtk::Box {
                 set_orientation: gtk::Orientation::Horizontal,
                 set_spacing: 8,
+                set_visible: !self.preview.role.is_tool(),
 
                 gtk::Label {
                     set_label: self.preview.role.label(),
@@ -54,6 +130,28 @@ impl FactoryComponent for MessageRow {
                     add_css_class: "caption",
                     add_css_class: "dim-label",
                     set_halign: gtk::Align::Start,
+                    set_hexpand: true,
+                },
+
+                gtk::Button {
+                    set_icon_name: "insert-link-symbolic",
+                    set_tooltip_text: Some("Copy link to message"),
+                    add_css_class: "flat",
+                    add_css_class: "message-action",
+                    connect_clicked => MessageRowMsg::CopyLink,
+                },
+
+                gtk::ToggleButton {
+                    set_icon_name: "user-bookmarks-symbolic",
+                    add_css_class: "flat",
+                    add_css_class: "message-action",
+                    #[watch]
+                    set_active: self.bookmarked,
+                    #[watch]
+                    set_tooltip_text: Some(if self.bookmarked { "Remove bookmark" } else { "Bookmark message" }),
+                    connect_toggled[sender] => move |button| {
+                        sender.input(MessageRowMsg::BookmarkToggled(button.is_active()));
+                    },
                 },
             },
 
@@ -63,23 +161,41 @@ impl FactoryComponent for MessageRow {
                 set_spacing: 4,
             },
 
-            // Truncation badge
-            gtk::Label {
-                set_label: "(content truncated)",
+            // Truncation: load the rest of the message on demand
+            gtk::Button {
+                set_label: "Show full message",
+                add_css_class: "flat",
                 add_css_class: "caption",
-                add_css_class: "dim-label",
                 set_halign: gtk::Align::Start,
                 set_margin_top: 4,
                 #[watch]
+                set_tooltip_text: Some(&format!(
+                    "Showing {} of {} characters",
+                    self.preview.content_preview.chars().count(),
+                    self.preview.content_len
+                )),
+                #[watch]
                 set_visible: self.preview.is_truncated(),
+                connect_clicked => MessageRowMsg::ShowFull,
             },
         }
     }
 
-    fn init_model(init: Self::Init, _index: &DynamicIndex, _sender: FactorySender<Self>) -> Self {
+    fn init_model(init: Self::Init, index: &DynamicIndex, _sender: FactorySender<Self>) -> Self {
         Self {
+            session_id: init.session_id,
             preview: init.preview,
+            result: None,
             highlight_query: init.highlight_query,
+            show_tools: init.show_tools,
+            branches: init.branches,
+            subagent: init.subagent,
+            subagent_messages: None,
+            subagent_expander: None,
+            expander: None,
+            bookmarked: init.bookmarked,
+            focused: init.focused,
+            index: index.clone(),
         }
     }
 
@@ -91,38 +207,288 @@ impl FactoryComponent for MessageRow {
         sender: FactorySender<Self>,
     ) -> Self::Widgets {
         let widgets = view_output!();
+        if self.preview.role.is_tool() {
+            widgets.root.add_css_class("tool-card");
+        }
+        if self.focused {
+            widgets.root.add_css_class("focused-message");
+        }
+        if !self.branches.is_empty() {
+            widgets.root.prepend(&branch_marker::build(&self.branches));
+        }
+        self.render_content(&widgets.content_container, &sender);
+        widgets
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: FactorySender<Self>,
+    ) {
+        match message {
+            MessageRowMsg::ShowFull => {
+                if self.preview.is_truncated() {
+                    let _ = sender.output(MessageRowOutput::ExpandRequested {
+                        position: self.index.current_index(),
+                        message_index: self.preview.index,
+                    });
+                }
+            }
+            MessageRowMsg::ShowFullOutput => {
+                if let Some(result) = &self.result
+                    && result.is_truncated()
+                {
+                    let _ = sender.output(MessageRowOutput::ExpandRequested {
+                        position: self.index.current_index(),
+                        message_index: result.index,
+                    });
+                }
+            }
+            MessageRowMsg::SetFullContent {
+                message_index,
+                content,
+            } => {
+                match &mut self.result {
+                    Some(result) if result.index == message_index => result.expand(content),
+                    _ => self.preview.expand(content),
+                }
+                self.rerender(widgets, &sender);
+            }
+            MessageRowMsg::AttachResult(result) => {
+                self.result = Some(result);
+                self.rerender(widgets, &sender);
+            }
+            MessageRowMsg::SetToolsVisible(visible) => {
+                self.show_tools = visible;
+            }
+            MessageRowMsg::LoadSubagent => {
+                if let Some(subagent) = &self.subagent
+                    && self.subagent_messages.is_none()
+                {
+                    let _ = sender.output(MessageRowOutput::SubagentRequested {
+                        position: self.index.current_index(),
+                        session_id: subagent.id.clone(),
+                    });
+                }
+            }
+            MessageRowMsg::SetSubagentMessages(messages) => {
+                self.subagent_messages = Some(messages);
+                self.rerender(widgets, &sender);
+            }
+            MessageRowMsg::BookmarkToggled(bookmarked) => {
+                // Also fired when `SetBookmarked` updates the button.
+                if bookmarked != self.bookmarked {
+                    self.bookmarked = bookmarked;
+                    let _ = sender.output(MessageRowOutput::BookmarkToggled {
+                        message_index: self.preview.index,
+                        bookmarked,
+                    });
+                }
+            }
+            MessageRowMsg::SetBookmarked(bookmarked) => self.bookmarked = bookmarked,
+            MessageRowMsg::CopyLink => {
+                let link = Permalink::to_message(&self.session_id, self.preview.index);
+                widgets.root.clipboard().set_text(&link.to_string());
+            }
+        }
+        self.update_view(widgets, sender);
+    }
+}
+
+impl MessageRow {
+    pub fn message_index(&self) -> usize {
+        self.preview.index
+    }
+
+    fn rerender(&mut self, widgets: &MessageRowWidgets, sender: &FactorySender<Self>) {
+        while let Some(child) = widgets.content_container.first_child() {
+            widgets.content_container.remove(&child);
+        }
+        self.render_content(&widgets.content_container, sender);
+    }
+
+    /// Render the message body into `container` and report its match count.
+    fn render_content(&mut self, container: &gtk::Box, sender: &FactorySender<Self>) {
         let mut match_count = 0usize;
 
-        if self.preview.role == Role::Assistant {
+        if self.preview.role.is_tool() {
+            // Tool traffic is searched only on request; a card holding the
+            // query is opened, but its matches are not counted or highlighted.
+            container.append(&self.render_tool_card(sender));
+        } else if matches!(self.preview.role, Role::Thinking | Role::System) {
+            let (body, count) = markdown::render_markdown(
+                &self.preview.content_preview,
+                self.highlight_query.as_deref(),
+            );
+            match_count = count;
+            container.append(&self.render_folded(&body, count > 0));
+        } else if self.preview.role == Role::Assistant {
             let rendered = markdown::render_markdown(
                 &self.preview.content_preview,
                 self.highlight_query.as_deref(),
             );
             match_count = rendered.1;
-            widgets.content_container.append(&rendered.0);
+            container.append(&rendered.0);
         } else if let Some(ref query) = self.highlight_query {
             let (markup, count) = highlight::highlight_text(&self.preview.content_preview, query);
             match_count = count;
-            let label = gtk::Label::new(None);
-            label.set_markup(&markup);
-            label.set_wrap(true);
-            label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
-            label.set_halign(gtk::Align::Start);
-            label.set_xalign(0.0);
-            label.set_selectable(true);
-            widgets.content_container.append(&label);
+            container.append(&Self::text_label(&markdown::style_redaction_markers(
+                &markup,
+            )));
         } else {
-            let label = gtk::Label::new(Some(&self.preview.content_preview));
-            label.set_wrap(true);
-            label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
-            label.set_halign(gtk::Align::Start);
-            label.set_xalign(0.0);
-            label.set_selectable(true);
-            widgets.content_container.append(&label);
+            container.append(&Self::text_label(&markdown::style_redaction_markers(
+                &markdown::pango_escape(&self.preview.content_preview),
+            )));
         }
 
-        let _ = sender.output(MessageRowOutput::MatchCount { count: match_count });
+        let _ = sender.output(MessageRowOutput::MatchCount {
+            position: self.index.current_index(),
+            count: match_count,
+        });
+    }
 
-        widgets
+    fn render_tool_card(&mut self, sender: &FactorySender<Self>) -> gtk::Expander {
+        let is_call = self.preview.role == Role::ToolCall;
+        let call = is_call
+            .then(|| ToolCall::from_content(&self.preview.content_preview))
+            .flatten();
+        let result_preview = if is_call {
+            self.result.as_ref()
+        } else {
+            Some(&self.preview)
+        };
+        let result =
+            result_preview.and_then(|preview| ToolResult::from_content(&preview.content_preview));
+        let has_matches = self.highlight_query.as_deref().is_some_and(|query| {
+            std::iter::once(&self.preview)
+                .chain(self.result.as_ref())
+                .any(|preview| highlight::has_match(&preview.content_preview, query))
+        });
+        let expanded = has_matches || self.is_expanded();
+
+        let card = tool_card::build(call.as_ref(), result.as_ref(), expanded);
+        // Previews of long calls cut their large input fields; load the
+        // whole input once the card is opened.
+        if is_call && self.preview.is_truncated() {
+            let input = sender.input_sender().clone();
+            if expanded {
+                input.emit(MessageRowMsg::ShowFull);
+            }
+            card.expander.connect_expanded_notify(move |expander| {
+                if expander.is_expanded() {
+                    input.emit(MessageRowMsg::ShowFull);
+                }
+            });
+        }
+        // A standalone result uses the row's own "Show full message" button.
+        if let Some(preview) = result_preview
+            && preview.is_truncated()
+            && is_call
+        {
+            let button = gtk::Button::with_label("Show full output");
+            button.add_css_class("flat");
+            button.add_css_class("caption");
+            button.set_halign(gtk::Align::Start);
+            button.set_tooltip_text(Some(&format!(
+                "Showing {} of {} characters",
+                preview.content_preview.chars().count(),
+                preview.content_len
+            )));
+            let input = sender.input_sender().clone();
+            button.connect_clicked(move |_| input.emit(MessageRowMsg::ShowFullOutput));
+            card.output_box.append(&button);
+        }
+        if self.subagent.is_some() {
+            card.output_box.append(&self.render_subagent(sender));
+        }
+
+        self.expander = Some(card.expander.clone());
+        card.expander
+    }
+
+    /// Reasoning or system text folded under its first line, opened when it
+    /// holds search matches.
+    fn render_folded(&mut self, body: &gtk::Box, has_matches: bool) -> gtk::Expander {
+        let summary = self
+            .preview
+            .content_preview
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default();
+        let label = gtk::Label::new(Some(summary));
+        label.add_css_class("dim-label");
+        label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        label.set_xalign(0.0);
+        label.set_hexpand(true);
+
+        body.set_margin_top(6);
+        let expander = gtk::Expander::new(None);
+        expander.set_label_widget(Some(&label));
+        expander.set_child(Some(body));
+        expander.set_expanded(has_matches || self.is_expanded());
+
+        self.expander = Some(expander.clone());
+        expander
+    }
+
+    /// The subagent's transcript, folded under its message count and
+    /// loaded on first expansion.
+    fn render_subagent(&mut self, sender: &FactorySender<Self>) -> gtk::Expander {
+        let subagent = self.subagent.as_ref().expect("subagent is set");
+        let title = match subagent.message_count {
+            1 => "Subagent · 1 message".to_string(),
+            count => format!("Subagent · {} messages", count),
+        };
+        let header = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        header.append(&gtk::Image::from_icon_name("system-users-symbolic"));
+        let label = gtk::Label::new(Some(&title));
+        label.add_css_class("caption");
+        header.append(&label);
+
+        let was_expanded = self
+            .subagent_expander
+            .as_ref()
+            .is_some_and(|expander| expander.is_expanded());
+        let expander = gtk::Expander::new(None);
+        expander.add_css_class("subagent-transcript");
+        expander.set_label_widget(Some(&header));
+        match &self.subagent_messages {
+            Some(messages) => {
+                let body = transcript::build(messages);
+                body.set_margin_top(6);
+                expander.set_child(Some(&body));
+                expander.set_expanded(was_expanded);
+            }
+            None => {
+                let input = sender.input_sender().clone();
+                expander.connect_expanded_notify(move |expander| {
+                    if expander.is_expanded() {
+                        input.emit(MessageRowMsg::LoadSubagent);
+                    }
+                });
+            }
+        }
+
+        self.subagent_expander = Some(expander.clone());
+        expander
+    }
+
+    fn is_expanded(&self) -> bool {
+        self.expander
+            .as_ref()
+            .is_some_and(|expander| expander.is_expanded())
+    }
+
+    fn text_label(markup: &str) -> gtk::Label {
+        let label = gtk::Label::new(None);
+        label.set_markup(markup);
+        label.set_wrap(true);
+        label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+        label.set_halign(gtk::Align::Start);
+        label.set_xalign(0.0);
+        label.set_selectable(true);
+        label
     }
 }
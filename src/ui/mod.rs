@@ -1,3 +1,5 @@
+pub mod bookmarks;
+pub mod branch_marker;
 pub mod detail_context_pane;
 pub mod highlight;
 pub mod markdown;
@@ -7,3 +9,7 @@ pub mod session_detail;
 pub mod session_list;
 pub mod session_row;
 pub mod sidebar;
+pub mod statistics;
+pub mod syntax;
+pub mod tool_card;
+pub mod transcript;
@@ -1,13 +1,18 @@
 use adw::prelude::{
-    ActionRowExt, AdwDialogExt, AlertDialogExt, ComboRowExt, PreferencesDialogExt,
-    PreferencesGroupExt, PreferencesPageExt,
+    ActionRowExt, AdwDialogExt, AlertDialogExt, ComboRowExt, EntryRowExt, PreferencesDialogExt,
+    PreferencesGroupExt, PreferencesPageExt, PreferencesRowExt,
 };
-use gtk::gio;
-use gtk::prelude::{ButtonExt, SettingsExt};
+use gtk::prelude::{ButtonExt, EditableExt, SettingsExt, SettingsExtManual, WidgetExt};
+use gtk::{gio, glib};
 use relm4::{ComponentParts, ComponentSender, SimpleComponent, adw, gtk};
 
 use crate::config::APP_ID;
-use crate::utils::terminal::Terminal;
+use crate::models::Tool;
+use crate::redaction::Redactor;
+use crate::utils::command_template::{CommandTemplate, TemplateContext};
+use crate::utils::exclusions;
+use crate::utils::multiplexer::Multiplexer;
+use crate::utils::terminal::{self, Terminal};
 
 const TERMINALS: &[Terminal] = &[
     Terminal::Auto,
@@ -16,8 +21,26 @@ const TERMINALS: &[Terminal] = &[
     Terminal::Foot,
     Terminal::Alacritty,
     Terminal::Kitty,
+    Terminal::GnomeConsole,
+    Terminal::GnomeTerminal,
+    Terminal::Konsole,
+    Terminal::WezTerm,
+    Terminal::Tilix,
+    Terminal::Xterm,
+    Terminal::Custom,
 ];
 
+const MULTIPLEXERS: &[Multiplexer] = &[
+    Multiplexer::Off,
+    Multiplexer::Auto,
+    Multiplexer::Tmux,
+    Multiplexer::Zellij,
+];
+
+/// Shown by the custom command's Test button in place of a resumed session.
+const TEST_COMMAND: &str =
+    "echo 'Sessions Chronicle: the custom terminal command works.'; exec \"${SHELL:-sh}\"";
+
 pub struct PreferencesDialog {
     root: adw::PreferencesDialog,
 }
@@ -31,6 +54,7 @@ pub enum PreferencesInput {
 #[derive(Debug)]
 pub enum PreferencesOutput {
     ReindexRequested,
+    ExclusionsChanged,
 }
 
 impl SimpleComponent for PreferencesDialog {
@@ -50,6 +74,7 @@ impl SimpleComponent for PreferencesDialog {
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
         let settings = gio::Settings::new(APP_ID);
+        let terminal_settings = settings.clone();
         let current_terminal = settings.string("resume-terminal");
 
         let combo_model = gio::ListStore::new::<gtk::StringObject>();
@@ -75,16 +100,94 @@ impl SimpleComponent for PreferencesDialog {
             .selected(selected_index)
             .build();
 
+        let command_row = build_custom_command_row(&settings, &root);
+        command_row.set_sensitive(TERMINALS[selected_index as usize] == Terminal::Custom);
+
+        let custom_row = command_row.clone();
         combo_row.connect_selected_notify(move |row| {
             let selected = row.selected();
             if let Some(terminal) = TERMINALS.get(selected as usize) {
-                let _ = settings.set_string("resume-terminal", terminal.to_str());
+                let _ = terminal_settings.set_string("resume-terminal", terminal.to_str());
+                custom_row.set_sensitive(*terminal == Terminal::Custom);
+            }
+        });
+
+        let current_multiplexer = settings.string("resume-multiplexer");
+        let multiplexer_model = gio::ListStore::new::<gtk::StringObject>();
+        let mut multiplexer_index = 0u32;
+        for (i, multiplexer) in MULTIPLEXERS.iter().enumerate() {
+            multiplexer_model.append(&gtk::StringObject::new(multiplexer.display_name()));
+            if current_multiplexer.as_str() == multiplexer.to_str() {
+                multiplexer_index = i as u32;
+            }
+        }
+
+        let multiplexer_row = adw::ComboRow::builder()
+            .title("Multiplexer")
+            .subtitle("Open resumed sessions in a new tmux window or zellij tab when a server is running, else in the terminal")
+            .model(&multiplexer_model)
+            .selected(multiplexer_index)
+            .build();
+
+        let multiplexer_settings = settings.clone();
+        multiplexer_row.connect_selected_notify(move |row| {
+            if let Some(multiplexer) = MULTIPLEXERS.get(row.selected() as usize) {
+                let _ = multiplexer_settings.set_string("resume-multiplexer", multiplexer.to_str());
             }
         });
 
         resumption_group.add(&combo_row);
+        resumption_group.add(&command_row);
+        resumption_group.add(&multiplexer_row);
         page.add(&resumption_group);
 
+        // Privacy group
+        let privacy_group = adw::PreferencesGroup::builder()
+            .title("Privacy")
+            .description("Changes apply the next time the index is rebuilt")
+            .build();
+
+        let redact_row = adw::SwitchRow::builder()
+            .title("Redact secrets when indexing")
+            .subtitle("API keys, tokens and private keys are replaced before being stored. Exports are always redacted.")
+            .build();
+        settings
+            .bind("redact-on-index", &redact_row, "active")
+            .build();
+        privacy_group.add(&redact_row);
+        page.add(&privacy_group);
+
+        let patterns_group = build_string_list_group(
+            &settings,
+            "redaction-patterns",
+            "Custom Redaction Patterns",
+            "Regular expressions redacted in addition to the built-in detectors",
+            "Add pattern",
+            |pattern| {
+                Redactor::with_custom_patterns(&[pattern])
+                    .map(|_| ())
+                    .map_err(|err| err.to_string())
+            },
+            |_| {},
+        );
+        page.add(&patterns_group);
+
+        let output_sender = sender.output_sender().clone();
+        let exclusions_group = build_string_list_group(
+            &settings,
+            "excluded-paths",
+            "Excluded Paths",
+            "Sessions under these path globs (*, **, ?, ~) are never indexed. Matching sessions are removed when a rule is added; removed rules take effect after the next index rebuild.",
+            "Add path or glob",
+            |pattern| exclusions::validate_pattern(pattern).map_err(|err| err.to_string()),
+            move |_| {
+                output_sender
+                    .send(PreferencesOutput::ExclusionsChanged)
+                    .ok();
+            },
+        );
+        page.add(&exclusions_group);
+
         // Advanced group with reset button
         let advanced_group = adw::PreferencesGroup::builder().title("Advanced").build();
 
@@ -148,3 +251,159 @@ impl SimpleComponent for PreferencesDialog {
 
     fn update_view(&self, _widgets: &mut Self::Widgets, _sender: ComponentSender<Self>) {}
 }
+
+/// Build the entry row for the `resume-terminal-command` template, with a
+/// Test button that runs it on a harmless command instead of a session.
+fn build_custom_command_row(
+    settings: &gio::Settings,
+    dialog: &adw::PreferencesDialog,
+) -> adw::EntryRow {
+    let row = adw::EntryRow::builder()
+        .title("Custom command ({workdir}, {session_id}, {tool}, {resume_cmd})")
+        .text(settings.string("resume-terminal-command").as_str())
+        .show_apply_button(true)
+        .build();
+
+    let settings = settings.clone();
+    row.connect_apply(move |row| {
+        let template = row.text();
+        match CommandTemplate::parse(&template) {
+            Ok(_) => {
+                row.remove_css_class("error");
+                row.set_tooltip_text(None);
+                let _ = settings.set_string("resume-terminal-command", &template);
+            }
+            Err(err) => {
+                row.add_css_class("error");
+                row.set_tooltip_text(Some(&err.to_string()));
+            }
+        }
+    });
+
+    let test_button = gtk::Button::builder()
+        .label("Test")
+        .valign(gtk::Align::Center)
+        .tooltip_text("Open the terminal with a test command")
+        .build();
+    let entry = row.clone();
+    let dialog = dialog.clone();
+    test_button.connect_clicked(move |_| {
+        let workdir = glib::home_dir();
+        let resume_args = ["sh".to_string(), "-c".to_string(), TEST_COMMAND.to_string()];
+        let context = TemplateContext {
+            workdir: &workdir,
+            session_id: "test-session",
+            tool: Tool::ClaudeCode,
+            resume_args: &resume_args,
+        };
+        let message = match terminal::spawn_custom_terminal(&entry.text(), &context) {
+            Ok(()) => "Test command started".to_string(),
+            Err(err) => err.to_string(),
+        };
+        dialog.add_toast(adw::Toast::new(&message));
+    });
+    row.add_suffix(&test_button);
+
+    row
+}
+
+/// Build a preferences group editing a string-list GSettings key.
+///
+/// Each value gets a row with a remove button; new values are typed in an
+/// entry row and checked with `validate` before being stored. `on_changed`
+/// receives the full list after every edit.
+fn build_string_list_group(
+    settings: &gio::Settings,
+    key: &'static str,
+    title: &str,
+    description: &str,
+    entry_title: &str,
+    validate: impl Fn(&str) -> Result<(), String> + 'static,
+    on_changed: impl Fn(Vec<String>) + 'static,
+) -> adw::PreferencesGroup {
+    let group = adw::PreferencesGroup::builder()
+        .title(title)
+        .description(description)
+        .build();
+
+    let entry_row = adw::EntryRow::builder()
+        .title(entry_title)
+        .show_apply_button(true)
+        .build();
+    group.add(&entry_row);
+
+    let on_changed = std::rc::Rc::new(on_changed);
+
+    for value in settings.strv(key) {
+        add_string_list_row(&group, settings, key, value.as_str(), on_changed.clone());
+    }
+
+    let settings = settings.clone();
+    let group_ref = group.clone();
+    entry_row.connect_apply(move |row| {
+        let value = row.text().trim().to_string();
+        if value.is_empty() {
+            return;
+        }
+
+        if let Err(err) = validate(&value) {
+            row.add_css_class("error");
+            row.set_tooltip_text(Some(&err));
+            return;
+        }
+        row.remove_css_class("error");
+        row.set_tooltip_text(None);
+
+        let mut values: Vec<String> = settings.strv(key).iter().map(|v| v.to_string()).collect();
+        if values.contains(&value) {
+            row.set_text("");
+            return;
+        }
+        values.push(value.clone());
+        if settings.set_strv(key, values.as_slice()).is_ok() {
+            add_string_list_row(&group_ref, &settings, key, &value, on_changed.clone());
+            on_changed(values);
+        }
+        row.set_text("");
+    });
+
+    group
+}
+
+fn add_string_list_row(
+    group: &adw::PreferencesGroup,
+    settings: &gio::Settings,
+    key: &'static str,
+    value: &str,
+    on_changed: std::rc::Rc<dyn Fn(Vec<String>)>,
+) {
+    let row = adw::ActionRow::builder().title(value).build();
+    row.set_use_markup(false);
+
+    let remove_button = gtk::Button::builder()
+        .icon_name("user-trash-symbolic")
+        .tooltip_text("Remove")
+        .valign(gtk::Align::Center)
+        .css_classes(["flat"])
+        .build();
+
+    let settings = settings.clone();
+    let group_ref = group.clone();
+    let row_ref = row.clone();
+    let value = value.to_string();
+    remove_button.connect_clicked(move |_| {
+        let values: Vec<String> = settings
+            .strv(key)
+            .iter()
+            .map(|v| v.to_string())
+            .filter(|v| *v != value)
+            .collect();
+        if settings.set_strv(key, values.as_slice()).is_ok() {
+            group_ref.remove(&row_ref);
+            on_changed(values);
+        }
+    });
+
+    row.add_suffix(&remove_button);
+    group.add(&row);
+}
@@ -50,6 +50,14 @@ impl SimpleComponent for ShortcutsDialog {
             &gettext("Toggle utility pane"),
             "F9",
         ));
+        view.add(adw::ShortcutsItem::new(
+            &gettext("Statistics"),
+            "<Control>i",
+        ));
+        view.add(adw::ShortcutsItem::new(
+            &gettext("Bookmarked messages"),
+            "<Control>b",
+        ));
         widgets.add(view);
 
         widgets.present(Some(&relm4::main_adw_application().windows()[0]));
@@ -1,4 +1,5 @@
 use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
@@ -7,9 +8,14 @@ use gtk::prelude::*;
 use relm4::factory::FactoryVecDeque;
 use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, adw, gtk};
 
-use crate::database::load_message_previews_for_session;
-use crate::models::Session;
-use crate::ui::message_row::{MessageRow, MessageRowInit, MessageRowOutput};
+use crate::database::{
+    load_bookmarked_indexes, load_branches, load_child_sessions, load_continuation_chain,
+    load_message_content, load_message_previews_for_session, load_messages_for_session,
+};
+use crate::models::tool_call::call_id;
+use crate::models::{ConversationBranch, MessagePreview, Role, Session, ToolCall};
+use crate::parsers::normalize_prompt;
+use crate::ui::message_row::{MessageRow, MessageRowInit, MessageRowMsg, MessageRowOutput};
 
 #[derive(Debug)]
 pub struct SessionDetail {
@@ -21,6 +27,28 @@ pub struct SessionDetail {
     loaded_count: usize,
     has_more_messages: bool,
     search_query: Option<String>,
+    /// Message indexes the user expanded; kept expanded across re-renders.
+    expanded: HashSet<usize>,
+    /// Row position of each loaded tool call, by call id, so results can be
+    /// folded under their call.
+    tool_calls: HashMap<String, usize>,
+    show_tools: bool,
+    /// Abandoned branches of the session, shown above the message they
+    /// fork from.
+    branches: Vec<ConversationBranch>,
+    /// Subagent sessions spawned by the session, oldest first. Each is
+    /// attached to the first unclaimed tool call that spawned it.
+    children: Vec<Session>,
+    /// Ids of the children already attached to a loaded row.
+    claimed_children: HashSet<String>,
+    /// Parts of the continued conversation the session belongs to, in
+    /// order; empty or a single entry when it was never continued.
+    parts: Vec<Session>,
+    /// Indexes of the bookmarked messages of the session.
+    bookmarks: HashSet<usize>,
+    /// Message the session was opened at from a permalink or bookmark.
+    focus_message: Option<usize>,
+    /// Match count per row, by position in `messages`.
     match_counts: Vec<usize>,
     current_match: usize,
     total_matches: usize,
@@ -30,8 +58,10 @@ pub struct SessionDetail {
 #[derive(Debug)]
 pub enum SessionDetailMsg {
     SetSession {
-        session: Session,
+        session: Box<Session>,
         search_query: Option<String>,
+        /// Scroll to this message instead of the first search match.
+        focus_message: Option<usize>,
     },
     #[allow(dead_code)]
     UpdateSearchQuery(Option<String>),
@@ -39,16 +69,46 @@ pub enum SessionDetailMsg {
     PrevMatch,
     NextMatch,
     ClearSearch,
-    MatchCount(usize),
+    MatchCount {
+        position: usize,
+        count: usize,
+    },
+    ExpandMessage {
+        position: usize,
+        message_index: usize,
+    },
+    LoadSubagent {
+        position: usize,
+        session_id: String,
+    },
+    SetShowTools(bool),
+    BookmarkToggled {
+        message_index: usize,
+        bookmarked: bool,
+    },
+    /// A bookmark was saved, reverted or removed elsewhere.
+    SetBookmarked {
+        message_index: usize,
+        bookmarked: bool,
+    },
+    PreviousPart,
+    NextPart,
     #[allow(dead_code)]
     Clear,
 }
 
+#[derive(Debug)]
+pub enum SessionDetailOutput {
+    /// Show another part of a continued conversation.
+    OpenSession(String),
+    BookmarkToggled(String, usize, bool),
+}
+
 #[relm4::component(pub)]
 impl SimpleComponent for SessionDetail {
     type Init = PathBuf;
     type Input = SessionDetailMsg;
-    type Output = ();
+    type Output = SessionDetailOutput;
     type Widgets = SessionDetailWidgets;
 
     view! {
@@ -159,6 +219,84 @@ impl SimpleComponent for SessionDetail {
                                     },
                                 },
 
+                                gtk::Box {
+                                    set_orientation: gtk::Orientation::Horizontal,
+                                    set_spacing: 6,
+                                    set_halign: gtk::Align::Start,
+                                    #[watch]
+                                    set_visible: model.parts.len() > 1,
+
+                                    gtk::Button {
+                                        set_icon_name: "go-previous-symbolic",
+                                        set_tooltip_text: Some("Previous part"),
+                                        add_css_class: "flat",
+                                        #[watch]
+                                        set_sensitive: model.part_at(-1).is_some(),
+                                        connect_clicked => SessionDetailMsg::PreviousPart,
+                                    },
+
+                                    gtk::Label {
+                                        add_css_class: "dim-label",
+                                        #[watch]
+                                        set_label: &model.part_label(),
+                                    },
+
+                                    gtk::Button {
+                                        set_icon_name: "go-next-symbolic",
+                                        set_tooltip_text: Some("Next part"),
+                                        add_css_class: "flat",
+                                        #[watch]
+                                        set_sensitive: model.part_at(1).is_some(),
+                                        connect_clicked => SessionDetailMsg::NextPart,
+                                    },
+                                },
+
+                                #[name = "git_box"]
+                                gtk::Box {
+                                    set_orientation: gtk::Orientation::Horizontal,
+                                    set_spacing: 6,
+                                    set_halign: gtk::Align::Start,
+
+                                    gtk::Label {
+                                        set_label: "Git:",
+                                        add_css_class: "dim-label",
+                                    },
+
+                                    #[name = "git_label"]
+                                    gtk::Label {
+                                        add_css_class: "monospace",
+                                        set_selectable: true,
+                                    },
+
+                                    #[name = "git_remote_label"]
+                                    gtk::Label {
+                                        add_css_class: "dim-label",
+                                        set_selectable: true,
+                                        set_ellipsize: gtk::pango::EllipsizeMode::Middle,
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_orientation: gtk::Orientation::Horizontal,
+                                    set_spacing: 6,
+                                    set_halign: gtk::Align::Start,
+
+                                    gtk::Label {
+                                        set_label: "Show tool calls",
+                                        add_css_class: "dim-label",
+                                    },
+
+                                    gtk::Switch {
+                                        set_valign: gtk::Align::Center,
+                                        set_active: model.show_tools,
+                                        set_tooltip_text: Some("Show tool calls and their output"),
+                                        connect_state_set[sender] => move |_, active| {
+                                            sender.input(SessionDetailMsg::SetShowTools(active));
+                                            glib::Propagation::Proceed
+                                        },
+                                    },
+                                },
+
                             },
 
                             // Messages container — managed by factory
@@ -249,7 +387,30 @@ impl SimpleComponent for SessionDetail {
         let messages: FactoryVecDeque<MessageRow> = FactoryVecDeque::builder()
             .launch_default()
             .forward(sender.input_sender(), |output| match output {
-                MessageRowOutput::MatchCount { count } => SessionDetailMsg::MatchCount(count),
+                MessageRowOutput::MatchCount { position, count } => {
+                    SessionDetailMsg::MatchCount { position, count }
+                }
+                MessageRowOutput::ExpandRequested {
+                    position,
+                    message_index,
+                } => SessionDetailMsg::ExpandMessage {
+                    position,
+                    message_index,
+                },
+                MessageRowOutput::SubagentRequested {
+                    position,
+                    session_id,
+                } => SessionDetailMsg::LoadSubagent {
+                    position,
+                    session_id,
+                },
+                MessageRowOutput::BookmarkToggled {
+                    message_index,
+                    bookmarked,
+                } => SessionDetailMsg::BookmarkToggled {
+                    message_index,
+                    bookmarked,
+                },
             });
 
         let model = Self {
@@ -261,6 +422,15 @@ impl SimpleComponent for SessionDetail {
             loaded_count: 0,
             has_more_messages: false,
             search_query: None,
+            expanded: HashSet::new(),
+            tool_calls: HashMap::new(),
+            show_tools: true,
+            branches: Vec::new(),
+            children: Vec::new(),
+            claimed_children: HashSet::new(),
+            parts: Vec::new(),
+            bookmarks: HashSet::new(),
+            focus_message: None,
             match_counts: Vec::new(),
             current_match: 0,
             total_matches: 0,
@@ -277,11 +447,12 @@ impl SimpleComponent for SessionDetail {
         ComponentParts { model, widgets }
     }
 
-    fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>) {
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
         match message {
             SessionDetailMsg::SetSession {
                 session,
                 search_query,
+                focus_message,
             } => {
                 self.search_query = search_query;
                 self.match_counts.clear();
@@ -289,11 +460,36 @@ impl SimpleComponent for SessionDetail {
                 self.total_matches = 0;
 
                 let session_id = session.id.clone();
-                self.session = Some(session);
+                self.branches = load_branches(&self.db_path, &session_id).unwrap_or_else(|err| {
+                    tracing::warn!("Failed to load branches of {}: {}", session_id, err);
+                    Vec::new()
+                });
+                self.children =
+                    load_child_sessions(&self.db_path, &session_id).unwrap_or_else(|err| {
+                        tracing::warn!("Failed to load subagents of {}: {}", session_id, err);
+                        Vec::new()
+                    });
+                self.parts =
+                    load_continuation_chain(&self.db_path, &session_id).unwrap_or_else(|err| {
+                        tracing::warn!("Failed to load parts of {}: {}", session_id, err);
+                        Vec::new()
+                    });
+                self.bookmarks = load_bookmarked_indexes(&self.db_path, &session_id)
+                    .unwrap_or_else(|err| {
+                        tracing::warn!("Failed to load bookmarks of {}: {}", session_id, err);
+                        HashSet::new()
+                    });
+                self.focus_message = focus_message;
+                self.session = Some(*session);
+                self.expanded.clear();
                 self.load_first_page(&session_id);
+                if let Some(message_index) = focus_message {
+                    self.reveal_message(&session_id, message_index);
+                }
             }
             SessionDetailMsg::UpdateSearchQuery(query) => {
                 self.search_query = query;
+                self.focus_message = None;
                 self.match_counts.clear();
                 self.current_match = 0;
                 self.total_matches = 0;
@@ -307,31 +503,7 @@ impl SimpleComponent for SessionDetail {
             SessionDetailMsg::LoadMore => {
                 if let Some(session) = &self.session {
                     let session_id = session.id.clone();
-                    let offset = self.loaded_count;
-                    match load_message_previews_for_session(
-                        &self.db_path,
-                        &session_id,
-                        self.page_size,
-                        offset,
-                        self.preview_len,
-                    ) {
-                        Ok(previews) => {
-                            self.has_more_messages = previews.len() == self.page_size;
-                            self.loaded_count += previews.len();
-                            let highlight = self.search_query.clone();
-                            let mut guard = self.messages.guard();
-                            for preview in previews {
-                                guard.push_back(MessageRowInit {
-                                    preview,
-                                    highlight_query: highlight.clone(),
-                                });
-                            }
-                        }
-                        Err(err) => {
-                            tracing::error!("Failed to load more previews: {}", err);
-                            self.has_more_messages = false;
-                        }
-                    }
+                    self.load_next_page(&session_id);
                 }
             }
             SessionDetailMsg::PrevMatch => {
@@ -354,17 +526,117 @@ impl SimpleComponent for SessionDetail {
                     self.scroll_to_message.set(Some(msg_idx));
                 }
             }
-            SessionDetailMsg::MatchCount(count) => {
+            SessionDetailMsg::MatchCount { position, count } => {
                 let was_empty = self.total_matches == 0;
-                self.match_counts.push(count);
+                if self.match_counts.len() <= position {
+                    self.match_counts.resize(position + 1, 0);
+                }
+                self.match_counts[position] = count;
                 self.total_matches = self.match_counts.iter().sum();
-                // Auto-scroll to first match when results arrive
-                if was_empty && self.total_matches > 0 && self.search_query.is_some() {
+                if self.current_match >= self.total_matches {
+                    self.current_match = 0;
+                }
+                // Auto-scroll to first match when results arrive, unless
+                // the session was opened at a given message
+                if was_empty
+                    && self.total_matches > 0
+                    && self.search_query.is_some()
+                    && self.focus_message.is_none()
+                {
                     self.current_match = 0;
                     let (msg_idx, _) = Self::find_message_for_match(&self.match_counts, 0);
                     self.scroll_to_message.set(Some(msg_idx));
                 }
             }
+            SessionDetailMsg::ExpandMessage {
+                position,
+                message_index,
+            } => {
+                let Some(session) = &self.session else {
+                    return;
+                };
+                match load_message_content(&self.db_path, &session.id, message_index) {
+                    Ok(Some(content)) => {
+                        self.expanded.insert(message_index);
+                        self.messages.send(
+                            position,
+                            MessageRowMsg::SetFullContent {
+                                message_index,
+                                content,
+                            },
+                        );
+                    }
+                    Ok(None) => {
+                        tracing::warn!("Message {} not found in {}", message_index, session.id);
+                    }
+                    Err(err) => {
+                        tracing::error!("Failed to load message {}: {}", message_index, err);
+                    }
+                }
+            }
+            SessionDetailMsg::LoadSubagent {
+                position,
+                session_id,
+            } => match load_messages_for_session(&self.db_path, &session_id) {
+                Ok(messages) => {
+                    self.messages
+                        .send(position, MessageRowMsg::SetSubagentMessages(messages));
+                }
+                Err(err) => {
+                    tracing::error!("Failed to load subagent {}: {}", session_id, err);
+                }
+            },
+            SessionDetailMsg::SetShowTools(show_tools) => {
+                self.show_tools = show_tools;
+                self.messages
+                    .broadcast(MessageRowMsg::SetToolsVisible(show_tools));
+            }
+            SessionDetailMsg::BookmarkToggled {
+                message_index,
+                bookmarked,
+            } => {
+                let Some(session) = &self.session else {
+                    return;
+                };
+                if bookmarked {
+                    self.bookmarks.insert(message_index);
+                } else {
+                    self.bookmarks.remove(&message_index);
+                }
+                let _ = sender.output(SessionDetailOutput::BookmarkToggled(
+                    session.id.clone(),
+                    message_index,
+                    bookmarked,
+                ));
+            }
+            SessionDetailMsg::SetBookmarked {
+                message_index,
+                bookmarked,
+            } => {
+                if bookmarked {
+                    self.bookmarks.insert(message_index);
+                } else {
+                    self.bookmarks.remove(&message_index);
+                }
+                if let Some(position) = self
+                    .messages
+                    .iter()
+                    .position(|row| row.message_index() == message_index)
+                {
+                    self.messages
+                        .send(position, MessageRowMsg::SetBookmarked(bookmarked));
+                }
+            }
+            SessionDetailMsg::PreviousPart | SessionDetailMsg::NextPart => {
+                let step = if matches!(message, SessionDetailMsg::PreviousPart) {
+                    -1
+                } else {
+                    1
+                };
+                if let Some(part) = self.part_at(step) {
+                    let _ = sender.output(SessionDetailOutput::OpenSession(part.id.clone()));
+                }
+            }
             SessionDetailMsg::ClearSearch => {
                 self.search_query = None;
                 self.match_counts.clear();
@@ -379,6 +651,14 @@ impl SimpleComponent for SessionDetail {
             }
             SessionDetailMsg::Clear => {
                 self.session = None;
+                self.bookmarks.clear();
+                self.focus_message = None;
+                self.expanded.clear();
+                self.tool_calls.clear();
+                self.branches.clear();
+                self.children.clear();
+                self.claimed_children.clear();
+                self.parts.clear();
                 self.messages.guard().clear();
                 self.loaded_count = 0;
                 self.has_more_messages = false;
@@ -424,6 +704,18 @@ impl SimpleComponent for SessionDetail {
 
             widgets.session_id_label.set_label(&session.id);
 
+            let git_summary = session.git.summary();
+            widgets.git_box.set_visible(!session.git.is_empty());
+            widgets
+                .git_label
+                .set_label(git_summary.as_deref().unwrap_or(""));
+            widgets
+                .git_label
+                .set_tooltip_text(session.git.commit.as_deref());
+            widgets
+                .git_remote_label
+                .set_label(session.git.remote.as_deref().unwrap_or(""));
+
             widgets
                 .content_stack
                 .set_visible_child(&widgets.detail_overlay);
@@ -480,15 +772,10 @@ impl SessionDetail {
             Ok(previews) => {
                 self.has_more_messages = previews.len() == self.page_size;
                 self.loaded_count = previews.len();
-                let highlight = self.search_query.clone();
-                let mut guard = self.messages.guard();
-                guard.clear();
-                for preview in previews {
-                    guard.push_back(MessageRowInit {
-                        preview,
-                        highlight_query: highlight.clone(),
-                    });
-                }
+                self.messages.guard().clear();
+                self.tool_calls.clear();
+                self.claimed_children.clear();
+                self.push_previews(session_id, previews);
             }
             Err(err) => {
                 tracing::error!(
@@ -503,6 +790,148 @@ impl SessionDetail {
         }
     }
 
+    fn load_next_page(&mut self, session_id: &str) {
+        match load_message_previews_for_session(
+            &self.db_path,
+            session_id,
+            self.page_size,
+            self.loaded_count,
+            self.preview_len,
+        ) {
+            Ok(previews) => {
+                self.has_more_messages = previews.len() == self.page_size;
+                self.loaded_count += previews.len();
+                self.push_previews(session_id, previews);
+            }
+            Err(err) => {
+                tracing::error!("Failed to load more previews: {}", err);
+                self.has_more_messages = false;
+            }
+        }
+    }
+
+    /// Load pages until message `message_index` is shown, then scroll to
+    /// it, or to the tool call its result is folded under.
+    fn reveal_message(&mut self, session_id: &str, message_index: usize) {
+        while self.has_more_messages && self.loaded_count <= message_index {
+            self.load_next_page(session_id);
+        }
+        if let Some(position) = self
+            .messages
+            .iter()
+            .rposition(|row| row.message_index() <= message_index)
+        {
+            self.scroll_to_message.set(Some(position));
+        }
+    }
+
+    /// Append rows for `previews`, restoring messages the user expanded and
+    /// folding tool results under their call.
+    fn push_previews(&mut self, session_id: &str, previews: Vec<MessagePreview>) {
+        let highlight = self.search_query.clone();
+        let mut results = Vec::new();
+        let mut guard = self.messages.guard();
+        for mut preview in previews {
+            if preview.is_truncated()
+                && self.expanded.contains(&preview.index)
+                && let Ok(Some(content)) =
+                    load_message_content(&self.db_path, session_id, preview.index)
+            {
+                preview.expand(content);
+            }
+
+            let id = call_id(&preview.content_preview).map(str::to_string);
+            match (preview.role, id) {
+                (Role::ToolResult, Some(id)) if self.tool_calls.contains_key(&id) => {
+                    results.push((self.tool_calls[&id], preview));
+                    continue;
+                }
+                (Role::ToolCall, Some(id)) => {
+                    self.tool_calls.insert(id, guard.len());
+                }
+                _ => {}
+            }
+
+            let subagent =
+                Self::claim_subagent(&self.children, &mut self.claimed_children, &preview);
+            let branches = self
+                .branches
+                .iter()
+                .filter(|branch| branch.fork_index == preview.index)
+                .cloned()
+                .collect();
+            guard.push_back(MessageRowInit {
+                session_id: session_id.to_string(),
+                bookmarked: self.bookmarks.contains(&preview.index),
+                focused: self.focus_message == Some(preview.index),
+                preview,
+                highlight_query: highlight.clone(),
+                show_tools: self.show_tools,
+                branches,
+                subagent,
+            });
+        }
+        drop(guard);
+
+        for (position, result) in results {
+            self.messages
+                .send(position, MessageRowMsg::AttachResult(result));
+        }
+    }
+
+    /// The child session spawned by a subagent tool call: the unclaimed
+    /// child whose first prompt is the call's prompt, else the oldest
+    /// unclaimed child. The prompt is unreadable when the preview is
+    /// truncated, hence the fallback on order.
+    fn claim_subagent(
+        children: &[Session],
+        claimed: &mut HashSet<String>,
+        preview: &MessagePreview,
+    ) -> Option<Session> {
+        if preview.role != Role::ToolCall {
+            return None;
+        }
+        let call = ToolCall::from_content(&preview.content_preview)?;
+        if !matches!(call.name.to_ascii_lowercase().as_str(), "task" | "agent") {
+            return None;
+        }
+        let prompt = call
+            .input
+            .get("prompt")
+            .and_then(|prompt| prompt.as_str())
+            .map(normalize_prompt);
+        let unclaimed = || children.iter().filter(|child| !claimed.contains(&child.id));
+        let child = unclaimed()
+            .find(|child| prompt.is_some() && child.first_prompt == prompt)
+            .or_else(|| unclaimed().next())?
+            .clone();
+        claimed.insert(child.id.clone());
+        Some(child)
+    }
+
+    /// Position of the session among the parts of its conversation.
+    fn part_index(&self) -> Option<usize> {
+        let session = self.session.as_ref()?;
+        self.parts.iter().position(|part| part.id == session.id)
+    }
+
+    /// The part `step` places before (negative) or after the session.
+    fn part_at(&self, step: isize) -> Option<&Session> {
+        let index = self.part_index()?.checked_add_signed(step)?;
+        self.parts.get(index)
+    }
+
+    fn part_label(&self) -> String {
+        match self.part_index() {
+            Some(index) => format!(
+                "Continued conversation · part {} of {}",
+                index + 1,
+                self.parts.len()
+            ),
+            None => String::new(),
+        }
+    }
+
     /// Resolve a global match index to a (message_index, local_match_index) pair.
     fn find_message_for_match(counts: &[usize], global_index: usize) -> (usize, usize) {
         let mut remaining = global_index;
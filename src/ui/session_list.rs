@@ -4,14 +4,35 @@ use relm4::{ComponentParts, ComponentSender, SimpleComponent, adw, gtk};
 use std::path::{Path, PathBuf};
 
 use crate::database::{load_sessions, search_sessions};
-use crate::models::{Session, Tool};
-use crate::ui::session_row::{SessionRow, SessionRowInit, SessionRowOutput};
+use crate::models::{DateRange, Session, Tool};
+use crate::ui::session_row::{SessionRow, SessionRowInit, SessionRowMsg, SessionRowOutput};
+
+/// Sidebar filters on user data, applied to the fetched sessions.
+#[derive(Debug, Default)]
+struct SessionFilter {
+    starred_only: bool,
+    tag: Option<String>,
+}
+
+impl SessionFilter {
+    fn matches(&self, session: &Session) -> bool {
+        (session.starred || !self.starred_only)
+            && self
+                .tag
+                .as_ref()
+                .is_none_or(|tag| session.tags.contains(tag))
+    }
+}
 
 #[derive(Debug)]
 pub struct SessionList {
     db_path: PathBuf,
     active_tools: Vec<Tool>,
+    date_range: DateRange,
     search_query: String,
+    starred_only: bool,
+    /// Tag picked in the sidebar's tag cloud.
+    tag_filter: Option<String>,
     all_tools_selected: bool,
     sessions: FactoryVecDeque<SessionRow>,
 }
@@ -19,9 +40,16 @@ pub struct SessionList {
 #[derive(Debug)]
 pub enum SessionListMsg {
     SetTools(Vec<Tool>),
+    SetDateRange(DateRange),
     SetSearchQuery(String),
+    SetStarredOnly(bool),
+    SetTagFilter(Option<String>),
     SessionActivated(i32),
     ResumeRequested(String, Tool),
+    StarToggled(String, bool),
+    RenameRequested(String, String),
+    /// A session was starred or unstarred and the change saved.
+    SetStarred(String, bool),
     Reload,
 }
 
@@ -29,6 +57,8 @@ pub enum SessionListMsg {
 pub enum SessionListOutput {
     SessionSelected(String),
     ResumeRequested(String, Tool),
+    StarToggled(String, bool),
+    RenameRequested(String, String),
 }
 
 #[relm4::component(pub)]
@@ -84,7 +114,14 @@ impl SimpleComponent for SessionList {
             Tool::MistralVibe,
         ];
         let search_query = String::new();
-        let fetched = Self::fetch_sessions(&db_path, &active_tools, &search_query);
+        let date_range = DateRange::default();
+        let fetched = Self::fetch_sessions(
+            &db_path,
+            &active_tools,
+            date_range,
+            &SessionFilter::default(),
+            &search_query,
+        );
 
         let sessions: FactoryVecDeque<SessionRow> = FactoryVecDeque::builder()
             .launch_default()
@@ -92,12 +129,21 @@ impl SimpleComponent for SessionList {
                 SessionRowOutput::ResumeRequested(id, tool) => {
                     SessionListMsg::ResumeRequested(id, tool)
                 }
+                SessionRowOutput::StarToggled(id, starred) => {
+                    SessionListMsg::StarToggled(id, starred)
+                }
+                SessionRowOutput::RenameRequested(id, title) => {
+                    SessionListMsg::RenameRequested(id, title)
+                }
             });
 
         let mut model = Self {
             db_path,
             active_tools,
+            date_range,
             search_query,
+            starred_only: false,
+            tag_filter: None,
             all_tools_selected: true,
             sessions,
         };
@@ -138,10 +184,22 @@ impl SimpleComponent for SessionList {
                 self.all_tools_selected = tools.len() == Tool::ALL.len();
                 self.reload_sessions();
             }
+            SessionListMsg::SetDateRange(range) => {
+                self.date_range = range;
+                self.reload_sessions();
+            }
             SessionListMsg::SetSearchQuery(query) => {
                 self.search_query = query;
                 self.reload_sessions();
             }
+            SessionListMsg::SetStarredOnly(starred_only) => {
+                self.starred_only = starred_only;
+                self.reload_sessions();
+            }
+            SessionListMsg::SetTagFilter(tag) => {
+                self.tag_filter = tag;
+                self.reload_sessions();
+            }
             SessionListMsg::SessionActivated(index) => {
                 if let Some(row) = self.sessions.get(index as usize) {
                     let _ = sender.output(SessionListOutput::SessionSelected(
@@ -152,6 +210,22 @@ impl SimpleComponent for SessionList {
             SessionListMsg::ResumeRequested(id, tool) => {
                 let _ = sender.output(SessionListOutput::ResumeRequested(id, tool));
             }
+            SessionListMsg::StarToggled(id, starred) => {
+                let _ = sender.output(SessionListOutput::StarToggled(id, starred));
+            }
+            SessionListMsg::RenameRequested(id, title) => {
+                let _ = sender.output(SessionListOutput::RenameRequested(id, title));
+            }
+            SessionListMsg::SetStarred(id, starred) => {
+                if self.starred_only {
+                    self.reload_sessions();
+                } else if let Some(index) =
+                    self.sessions.iter().position(|row| row.session_id() == id)
+                {
+                    self.sessions
+                        .send(index, SessionRowMsg::SetStarred(starred));
+                }
+            }
             SessionListMsg::Reload => {
                 self.reload_sessions();
             }
@@ -165,7 +239,15 @@ impl SimpleComponent for SessionList {
                 widgets
                     .empty_state
                     .set_description(Some("Try a different query or adjust filters"));
-            } else if self.all_tools_selected {
+            } else if self.starred_only {
+                widgets.empty_state.set_title("No starred sessions");
+                widgets
+                    .empty_state
+                    .set_description(Some("Star a session to keep it at hand"));
+            } else if self.all_tools_selected
+                && self.date_range == DateRange::AllTime
+                && self.tag_filter.is_none()
+            {
                 widgets.empty_state.set_title("No Sessions Yet");
                 widgets
                     .empty_state
@@ -174,7 +256,7 @@ impl SimpleComponent for SessionList {
                 widgets.empty_state.set_title("No sessions match filters");
                 widgets
                     .empty_state
-                    .set_description(Some("Try adjusting the tool filters in the sidebar"));
+                    .set_description(Some("Try adjusting the filters in the sidebar"));
             }
             widgets
                 .content_stack
@@ -188,7 +270,13 @@ impl SimpleComponent for SessionList {
 }
 
 impl SessionList {
-    fn fetch_sessions(db_path: &Path, tools: &[Tool], query: &str) -> Vec<Session> {
+    fn fetch_sessions(
+        db_path: &Path,
+        tools: &[Tool],
+        date_range: DateRange,
+        filter: &SessionFilter,
+        query: &str,
+    ) -> Vec<Session> {
         let query = query.trim();
         let sessions = if query.is_empty() {
             load_sessions(db_path, tools)
@@ -197,7 +285,14 @@ impl SessionList {
         };
 
         match sessions {
-            Ok(sessions) => sessions,
+            Ok(sessions) => {
+                let now = chrono::Utc::now();
+                sessions
+                    .into_iter()
+                    .filter(|session| date_range.contains(session.last_updated, now))
+                    .filter(|session| filter.matches(session))
+                    .collect()
+            }
             Err(err) => {
                 tracing::error!("Failed to load sessions: {}", err);
                 Vec::new()
@@ -206,7 +301,16 @@ impl SessionList {
     }
 
     fn reload_sessions(&mut self) {
-        let fetched = Self::fetch_sessions(&self.db_path, &self.active_tools, &self.search_query);
+        let fetched = Self::fetch_sessions(
+            &self.db_path,
+            &self.active_tools,
+            self.date_range,
+            &SessionFilter {
+                starred_only: self.starred_only,
+                tag: self.tag_filter.clone(),
+            },
+            &self.search_query,
+        );
         let mut guard = self.sessions.guard();
         guard.clear();
         for session in fetched {
@@ -218,6 +322,7 @@ impl SessionList {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{Continuation, GitInfo, SessionUsage};
     use gtk::glib::prelude::ObjectExt;
     use relm4::Component;
     use relm4::ComponentController;
@@ -280,6 +385,14 @@ mod tests {
             file_path: "/tmp/session.jsonl".to_string(),
             last_updated: chrono::Utc::now(),
             first_prompt: None,
+            usage: SessionUsage::default(),
+            git: GitInfo::default(),
+            parent_session_id: None,
+            continuation: Continuation::default(),
+            starred: false,
+            tags: Vec::new(),
+            title: None,
+            custom_title: None,
         };
 
         {
@@ -326,6 +439,14 @@ mod tests {
             file_path: "/tmp/session.jsonl".to_string(),
             last_updated: chrono::Utc::now(),
             first_prompt: None,
+            usage: SessionUsage::default(),
+            git: GitInfo::default(),
+            parent_session_id: None,
+            continuation: Continuation::default(),
+            starred: false,
+            tags: Vec::new(),
+            title: None,
+            custom_title: None,
         };
 
         {
@@ -6,6 +6,7 @@ use relm4::{adw, gtk};
 
 use adw::prelude::ActionRowExt;
 
+use crate::models::tag::format_tags;
 use crate::models::{Session, Tool};
 use gtk::glib;
 
@@ -21,9 +22,29 @@ pub struct SessionRow {
     context_menu: Option<gtk::PopoverMenu>,
 }
 
+#[derive(Debug)]
+pub enum SessionRowMsg {
+    /// The star button was toggled.
+    StarToggled(bool),
+    /// The session was starred or unstarred elsewhere.
+    SetStarred(bool),
+}
+
 #[derive(Debug)]
 pub enum SessionRowOutput {
     ResumeRequested(String, Tool),
+    StarToggled(String, bool),
+    /// Rename the session; carries the title currently shown.
+    RenameRequested(String, String),
+}
+
+/// Icon of a star toggle.
+pub fn star_icon(starred: bool) -> &'static str {
+    if starred {
+        "starred-symbolic"
+    } else {
+        "non-starred-symbolic"
+    }
 }
 
 fn emit_resume(sender: &relm4::Sender<SessionRowOutput>, id: &str, tool: Tool) {
@@ -33,7 +54,7 @@ fn emit_resume(sender: &relm4::Sender<SessionRowOutput>, id: &str, tool: Tool) {
 #[relm4::factory(pub)]
 impl FactoryComponent for SessionRow {
     type Init = SessionRowInit;
-    type Input = ();
+    type Input = SessionRowMsg;
     type Output = SessionRowOutput;
     type CommandOutput = ();
     type ParentWidget = gtk::ListBox;
@@ -54,6 +75,20 @@ impl FactoryComponent for SessionRow {
                     set_pixel_size: 16,
                 },
 
+                add_suffix = &gtk::ToggleButton {
+                    add_css_class: "flat",
+                    set_valign: gtk::Align::Center,
+                    #[watch]
+                    set_active: self.session.starred,
+                    #[watch]
+                    set_icon_name: star_icon(self.session.starred),
+                    #[watch]
+                    set_tooltip_text: Some(if self.session.starred { "Unstar" } else { "Star" }),
+                    connect_toggled[sender] => move |button| {
+                        sender.input(SessionRowMsg::StarToggled(button.is_active()));
+                    },
+                },
+
                 add_suffix = &gtk::Image::from_icon_name("go-next-symbolic") {
                     add_css_class: "dim-label",
                 },
@@ -80,6 +115,7 @@ impl FactoryComponent for SessionRow {
 
         let menu = gio::Menu::new();
         menu.append(Some("Resume in Terminal"), Some("row.resume"));
+        menu.append(Some("Rename…"), Some("row.rename"));
 
         let action_group = gio::SimpleActionGroup::new();
         let resume_action = gio::SimpleAction::new("resume", None);
@@ -91,7 +127,19 @@ impl FactoryComponent for SessionRow {
             emit_resume(&output_sender, &session_id, tool);
         });
 
+        let rename_action = gio::SimpleAction::new("rename", None);
+        let output_sender = sender.output_sender().clone();
+        let session_id = self.session.id.clone();
+        let title = self.session.display_title().unwrap_or_default().to_string();
+        rename_action.connect_activate(move |_, _| {
+            let _ = output_sender.send(SessionRowOutput::RenameRequested(
+                session_id.clone(),
+                title.clone(),
+            ));
+        });
+
         action_group.add_action(&resume_action);
+        action_group.add_action(&rename_action);
         root_for_actions.insert_action_group("row", Some(&action_group));
 
         let popover = gtk::PopoverMenu::from_model(Some(&menu));
@@ -109,6 +157,22 @@ impl FactoryComponent for SessionRow {
         widgets
     }
 
+    fn update(&mut self, message: Self::Input, sender: FactorySender<Self>) {
+        match message {
+            SessionRowMsg::StarToggled(starred) => {
+                // Also fired when `SetStarred` updates the button.
+                if starred != self.session.starred {
+                    self.session.starred = starred;
+                    let _ = sender.output(SessionRowOutput::StarToggled(
+                        self.session.id.clone(),
+                        starred,
+                    ));
+                }
+            }
+            SessionRowMsg::SetStarred(starred) => self.session.starred = starred,
+        }
+    }
+
     fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
         if let Some(popover) = self.context_menu.take() {
             popover.unparent();
@@ -122,15 +186,9 @@ impl SessionRow {
     }
 
     fn session_title(session: &Session) -> String {
-        let raw = if let Some(prompt) = session
-            .first_prompt
-            .as_deref()
-            .map(str::trim)
-            .filter(|prompt| !prompt.is_empty())
-        {
-            prompt.to_string()
-        } else {
-            Self::project_name(session).unwrap_or_else(|| "Unknown project".to_string())
+        let raw = match session.display_title() {
+            Some(title) => title.to_string(),
+            None => Self::project_name(session).unwrap_or_else(|| "Unknown project".to_string()),
         };
 
         // ActionRow interprets title as Pango markup by default.
@@ -148,12 +206,7 @@ impl SessionRow {
     }
 
     fn session_subtitle(session: &Session) -> String {
-        let has_prompt = session
-            .first_prompt
-            .as_deref()
-            .is_some_and(|p| !p.trim().is_empty());
-
-        let location = if has_prompt {
+        let location = if session.display_title().is_some() {
             Self::project_name(session).unwrap_or_else(|| "Unknown project".to_string())
         } else {
             session
@@ -163,10 +216,14 @@ impl SessionRow {
         };
 
         let relative_time = Self::format_relative_time(session.last_updated);
-        let raw = format!(
+        let mut raw = format!(
             "{} · {} messages · {}",
             location, session.message_count, relative_time
         );
+        if !session.tags.is_empty() {
+            raw.push_str(" · ");
+            raw.push_str(&format_tags(&session.tags));
+        }
 
         // Escape for Pango markup (ActionRow subtitle also uses markup).
         glib::markup_escape_text(&raw).to_string()
@@ -193,6 +250,7 @@ impl SessionRow {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{Continuation, GitInfo, SessionUsage};
 
     fn build_session(
         project_path: Option<&str>,
@@ -209,6 +267,14 @@ mod tests {
             file_path: "/tmp/session.jsonl".to_string(),
             last_updated: now - ChronoDuration::minutes(minutes_ago),
             first_prompt: first_prompt.map(str::to_string),
+            usage: SessionUsage::default(),
+            git: GitInfo::default(),
+            parent_session_id: None,
+            continuation: Continuation::default(),
+            starred: false,
+            tags: Vec::new(),
+            title: None,
+            custom_title: None,
         }
     }
 
@@ -226,6 +292,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn session_title_prefers_custom_then_recorded_title() {
+        let mut session = build_session(None, Some("continue"), 10);
+        session.title = Some("Parser fix".to_string());
+        assert_eq!(SessionRow::session_title(&session), "Parser fix");
+
+        session.custom_title = Some("Release prep".to_string());
+        assert_eq!(SessionRow::session_title(&session), "Release prep");
+    }
+
     #[test]
     fn session_title_falls_back_to_project_name_then_unknown_project() {
         let with_project = build_session(Some("/home/user/work/my-project"), None, 10);
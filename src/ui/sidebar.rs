@@ -1,6 +1,7 @@
 use gtk::prelude::*;
 use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, gtk};
 
+use crate::models::DateRange;
 use crate::models::session::Tool;
 
 #[derive(Debug)]
@@ -9,16 +10,28 @@ pub struct Sidebar {
     opencode_enabled: bool,
     codex_enabled: bool,
     mistral_vibe_enabled: bool,
+    /// Every tag with its session count, most used first.
+    tags: Vec<(String, usize)>,
+    selected_tag: Option<String>,
+    tag_box: gtk::FlowBox,
+    tag_buttons: Vec<(String, gtk::ToggleButton)>,
 }
 
 #[derive(Debug)]
 pub enum SidebarMsg {
     ToolToggled(Tool, bool),
+    DateRangeSelected(u32),
+    StarredOnlyToggled(bool),
+    SetTags(Vec<(String, usize)>),
+    TagToggled(String, bool),
 }
 
 #[derive(Debug)]
 pub enum SidebarOutput {
     FiltersChanged(Vec<Tool>),
+    DateRangeChanged(DateRange),
+    ShowStarredOnly(bool),
+    TagSelected(Option<String>),
 }
 
 #[relm4::component(pub)]
@@ -99,6 +112,72 @@ impl SimpleComponent for Sidebar {
                 set_margin_bottom: 12,
             },
 
+            gtk::Label {
+                set_label: "Period",
+                set_halign: gtk::Align::Start,
+                add_css_class: "heading",
+                set_margin_bottom: 6,
+            },
+
+            #[name = "period_dropdown"]
+            gtk::DropDown {
+                set_model: Some(&gtk::StringList::new(
+                    &DateRange::ALL.iter().map(|range| range.label()).collect::<Vec<_>>(),
+                )),
+                connect_selected_notify[sender] => move |dropdown| {
+                    sender.input(SidebarMsg::DateRangeSelected(dropdown.selected()));
+                },
+            },
+
+            #[name = "starred_check"]
+            gtk::CheckButton {
+                set_label: Some("Starred only"),
+                set_margin_top: 6,
+                connect_toggled[sender] => move |btn| {
+                    sender.input(SidebarMsg::StarredOnlyToggled(btn.is_active()));
+                },
+            },
+
+            gtk::Separator {
+                set_margin_top: 12,
+                set_margin_bottom: 12,
+            },
+
+            gtk::Separator {
+                set_margin_top: 12,
+                set_margin_bottom: 12,
+            },
+
+            gtk::Label {
+                set_label: "Tags",
+                set_halign: gtk::Align::Start,
+                add_css_class: "heading",
+                set_margin_bottom: 6,
+            },
+
+            gtk::Label {
+                set_label: "No tags yet",
+                set_halign: gtk::Align::Start,
+                add_css_class: "dim-label",
+                #[watch]
+                set_visible: model.tags.is_empty(),
+            },
+
+            #[local_ref]
+            tag_box -> gtk::FlowBox {
+                set_selection_mode: gtk::SelectionMode::None,
+                set_column_spacing: 4,
+                set_row_spacing: 4,
+                set_max_children_per_line: 8,
+                #[watch]
+                set_visible: !model.tags.is_empty(),
+            },
+
+            gtk::Separator {
+                set_margin_top: 12,
+                set_margin_bottom: 12,
+            },
+
             gtk::Label {
                 set_label: "Projects",
                 set_halign: gtk::Align::Start,
@@ -134,7 +213,12 @@ impl SimpleComponent for Sidebar {
             opencode_enabled: true,
             codex_enabled: true,
             mistral_vibe_enabled: true,
+            tags: Vec::new(),
+            selected_tag: None,
+            tag_box: gtk::FlowBox::new(),
+            tag_buttons: Vec::new(),
         };
+        let tag_box = &model.tag_box;
         let widgets = view_output!();
 
         let _ = sender.output(SidebarOutput::FiltersChanged(vec![
@@ -173,6 +257,69 @@ impl SimpleComponent for Sidebar {
 
                 let _ = sender.output(SidebarOutput::FiltersChanged(tools));
             }
+            SidebarMsg::DateRangeSelected(index) => {
+                if let Some(range) = DateRange::ALL.get(index as usize) {
+                    let _ = sender.output(SidebarOutput::DateRangeChanged(*range));
+                }
+            }
+            SidebarMsg::StarredOnlyToggled(starred_only) => {
+                let _ = sender.output(SidebarOutput::ShowStarredOnly(starred_only));
+            }
+            SidebarMsg::SetTags(tags) => {
+                if let Some(selected) = &self.selected_tag
+                    && !tags.iter().any(|(tag, _)| tag == selected)
+                {
+                    self.selected_tag = None;
+                    let _ = sender.output(SidebarOutput::TagSelected(None));
+                }
+                self.tags = tags;
+                self.rebuild_tag_buttons(&sender);
+            }
+            SidebarMsg::TagToggled(tag, true) => {
+                // One tag at a time: untoggling the others queues messages
+                // that the check below ignores.
+                for (other, button) in &self.tag_buttons {
+                    if *other != tag {
+                        button.set_active(false);
+                    }
+                }
+                self.selected_tag = Some(tag.clone());
+                let _ = sender.output(SidebarOutput::TagSelected(Some(tag)));
+            }
+            SidebarMsg::TagToggled(tag, false) => {
+                if self.selected_tag.as_ref() == Some(&tag) {
+                    self.selected_tag = None;
+                    let _ = sender.output(SidebarOutput::TagSelected(None));
+                }
+            }
+        }
+    }
+}
+
+impl Sidebar {
+    fn rebuild_tag_buttons(&mut self, sender: &ComponentSender<Self>) {
+        self.tag_box.remove_all();
+        self.tag_buttons.clear();
+        for (tag, count) in &self.tags {
+            let button = gtk::ToggleButton::with_label(&format!("#{}", tag));
+            button.add_css_class("flat");
+            button.add_css_class("caption");
+            button.set_tooltip_text(Some(&match count {
+                1 => "1 session".to_string(),
+                count => format!("{} sessions", count),
+            }));
+            button.set_active(self.selected_tag.as_ref() == Some(tag));
+
+            let input = sender.input_sender().clone();
+            let toggled_tag = tag.clone();
+            button.connect_toggled(move |button| {
+                input.emit(SidebarMsg::TagToggled(
+                    toggled_tag.clone(),
+                    button.is_active(),
+                ));
+            });
+            self.tag_box.append(&button);
+            self.tag_buttons.push((tag.clone(), button));
         }
     }
 }
@@ -0,0 +1,621 @@
+use adw::prelude::*;
+use chrono::{Datelike, NaiveDate, Utc};
+use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, adw, gtk};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::database::stats::{
+    self, HEATMAP_LEVELS, HeatmapCell, Statistics, StatsFilter, load_statistics,
+};
+use crate::models::usage::format_token_count;
+use crate::models::{DateRange, Tool};
+use crate::pricing::format_cost;
+
+/// Bars shown by the sessions chart for each granularity.
+const MAX_DAY_BUCKETS: usize = 90;
+const MAX_WEEK_BUCKETS: usize = 52;
+
+/// Heatmap geometry: a year of weeks, in pixels.
+const HEATMAP_WEEKS: usize = 53;
+const HEATMAP_CELL: f64 = 12.0;
+const HEATMAP_GAP: f64 = 3.0;
+const HEATMAP_TOP: f64 = 16.0;
+
+/// Adwaita green 1–5, lightest to darkest.
+const HEATMAP_COLORS: [&str; HEATMAP_LEVELS as usize] =
+    ["#8ff0a4", "#57e389", "#2ec27e", "#26a269"];
+
+/// Data shared with the drawing callbacks.
+#[derive(Debug, Default)]
+struct ChartData {
+    buckets: Vec<(NaiveDate, Vec<(Tool, usize)>)>,
+    heatmap: Vec<Vec<HeatmapCell>>,
+}
+
+#[derive(Debug)]
+pub struct StatisticsView {
+    db_path: PathBuf,
+    tools: Vec<Tool>,
+    date_range: DateRange,
+    weekly: bool,
+    stats: Statistics,
+    chart_data: Rc<RefCell<ChartData>>,
+    projects_list: gtk::ListBox,
+}
+
+#[derive(Debug)]
+pub enum StatisticsMsg {
+    SetTools(Vec<Tool>),
+    SetDateRange(DateRange),
+    SetWeekly(bool),
+    Refresh,
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for StatisticsView {
+    type Init = PathBuf;
+    type Input = StatisticsMsg;
+    type Output = ();
+    type Widgets = StatisticsViewWidgets;
+
+    view! {
+        gtk::ScrolledWindow {
+            set_vexpand: true,
+            set_hscrollbar_policy: gtk::PolicyType::Never,
+
+            adw::Clamp {
+                set_maximum_size: 960,
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 18,
+                    set_margin_all: 18,
+
+                    gtk::Label {
+                        set_halign: gtk::Align::Start,
+                        add_css_class: "dim-label",
+                        #[watch]
+                        set_label: &format!(
+                            "{} · {}",
+                            model.date_range.label(),
+                            tools_label(&model.tools)
+                        ),
+                    },
+
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 12,
+                        set_homogeneous: true,
+
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 4,
+                            add_css_class: "card",
+
+                            gtk::Label {
+                                add_css_class: "title-2",
+                                add_css_class: "numeric",
+                                set_margin_top: 12,
+                                #[watch]
+                                set_label: &model.stats.totals.sessions.to_string(),
+                            },
+
+                            gtk::Label {
+                                set_label: "Sessions",
+                                add_css_class: "caption",
+                                add_css_class: "dim-label",
+                                set_margin_bottom: 12,
+                            },
+                        },
+
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 4,
+                            add_css_class: "card",
+
+                            gtk::Label {
+                                add_css_class: "title-2",
+                                add_css_class: "numeric",
+                                set_margin_top: 12,
+                                #[watch]
+                                set_label: &average_session_label(&model.stats),
+                            },
+
+                            gtk::Label {
+                                set_label: "Average session",
+                                add_css_class: "caption",
+                                add_css_class: "dim-label",
+                                set_margin_bottom: 12,
+                            },
+                        },
+
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 4,
+                            add_css_class: "card",
+
+                            gtk::Label {
+                                add_css_class: "title-2",
+                                add_css_class: "numeric",
+                                set_margin_top: 12,
+                                #[watch]
+                                set_label: &format_token_count(model.stats.totals.tokens.total()),
+                            },
+
+                            gtk::Label {
+                                set_label: "Tokens",
+                                add_css_class: "caption",
+                                add_css_class: "dim-label",
+                                set_margin_bottom: 12,
+                            },
+                        },
+
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 4,
+                            add_css_class: "card",
+
+                            gtk::Label {
+                                add_css_class: "title-2",
+                                add_css_class: "numeric",
+                                set_margin_top: 12,
+                                #[watch]
+                                set_label: &cost_label(&model.stats),
+                            },
+
+                            gtk::Label {
+                                set_label: "Estimated cost",
+                                add_css_class: "caption",
+                                add_css_class: "dim-label",
+                                set_margin_bottom: 12,
+                            },
+                        },
+                    },
+
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 12,
+
+                        gtk::Label {
+                            set_label: "Sessions",
+                            set_halign: gtk::Align::Start,
+                            set_hexpand: true,
+                            add_css_class: "heading",
+                        },
+
+                        gtk::DropDown {
+                            set_model: Some(&gtk::StringList::new(&["Per day", "Per week"])),
+                            connect_selected_notify[sender] => move |dropdown| {
+                                sender.input(StatisticsMsg::SetWeekly(dropdown.selected() == 1));
+                            },
+                        },
+                    },
+
+                    #[name = "sessions_chart"]
+                    gtk::DrawingArea {
+                        set_content_height: 200,
+                        set_hexpand: true,
+                    },
+
+                    gtk::Label {
+                        set_label: "Activity",
+                        set_halign: gtk::Align::Start,
+                        add_css_class: "heading",
+                    },
+
+                    #[name = "heatmap"]
+                    gtk::DrawingArea {
+                        set_content_height: (HEATMAP_TOP + 7.0 * (HEATMAP_CELL + HEATMAP_GAP)) as i32,
+                        set_content_width: (HEATMAP_WEEKS as f64 * (HEATMAP_CELL + HEATMAP_GAP)) as i32,
+                        set_halign: gtk::Align::Start,
+                        set_has_tooltip: true,
+                    },
+
+                    gtk::Label {
+                        set_label: "Busiest projects",
+                        set_halign: gtk::Align::Start,
+                        add_css_class: "heading",
+                    },
+
+                    #[local_ref]
+                    projects_list -> gtk::ListBox {
+                        add_css_class: "boxed-list",
+                        set_selection_mode: gtk::SelectionMode::None,
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        db_path: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let projects_list = gtk::ListBox::new();
+        let chart_data = Rc::new(RefCell::new(ChartData::default()));
+
+        let mut model = Self {
+            db_path,
+            tools: Tool::ALL.to_vec(),
+            date_range: DateRange::default(),
+            weekly: false,
+            stats: Statistics::default(),
+            chart_data: chart_data.clone(),
+            projects_list: projects_list.clone(),
+        };
+        model.reload();
+
+        let projects_list = &model.projects_list;
+        let widgets = view_output!();
+
+        {
+            let data = chart_data.clone();
+            widgets
+                .sessions_chart
+                .set_draw_func(move |area, cr, width, height| {
+                    draw_sessions_chart(area, cr, width as f64, height as f64, &data.borrow());
+                });
+        }
+        {
+            let data = chart_data.clone();
+            widgets.heatmap.set_draw_func(move |area, cr, _, _| {
+                draw_heatmap(area, cr, &data.borrow().heatmap);
+            });
+        }
+        {
+            let data = chart_data;
+            widgets
+                .heatmap
+                .connect_query_tooltip(move |_, x, y, _, tooltip| {
+                    let data = data.borrow();
+                    let Some(cell) = heatmap_cell_at(&data.heatmap, x as f64, y as f64) else {
+                        return false;
+                    };
+                    tooltip.set_text(Some(&format!(
+                        "{} session{} on {}",
+                        cell.sessions,
+                        if cell.sessions == 1 { "" } else { "s" },
+                        cell.date.format("%a %Y-%m-%d")
+                    )));
+                    true
+                });
+        }
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>) {
+        match message {
+            StatisticsMsg::SetTools(tools) => self.tools = tools,
+            StatisticsMsg::SetDateRange(range) => self.date_range = range,
+            StatisticsMsg::SetWeekly(weekly) => self.weekly = weekly,
+            StatisticsMsg::Refresh => {}
+        }
+        self.reload();
+    }
+
+    fn post_view(&self, widgets: &mut Self::Widgets) {
+        widgets.sessions_chart.queue_draw();
+        widgets.heatmap.queue_draw();
+    }
+}
+
+impl StatisticsView {
+    fn reload(&mut self) {
+        let now = Utc::now();
+        let since = self.date_range.since(now);
+        let filter = StatsFilter {
+            tools: self.tools.clone(),
+            since,
+        };
+
+        self.stats = match load_statistics(&self.db_path, &filter) {
+            Ok(stats) => stats,
+            Err(err) => {
+                tracing::error!("Failed to load statistics: {}", err);
+                Statistics::default()
+            }
+        };
+
+        let today = now.date_naive();
+        let (counts, max_buckets) = if self.weekly {
+            (&self.stats.per_week, MAX_WEEK_BUCKETS)
+        } else {
+            (&self.stats.per_day, MAX_DAY_BUCKETS)
+        };
+
+        let mut data = self.chart_data.borrow_mut();
+        data.buckets = stats::chart_buckets(
+            counts,
+            self.weekly,
+            since.map(|since| since.date_naive()),
+            today,
+            max_buckets,
+        );
+        data.heatmap = stats::heatmap(
+            &stats::totals_by_bucket(&self.stats.per_day),
+            today,
+            HEATMAP_WEEKS,
+        );
+        drop(data);
+
+        self.rebuild_projects();
+    }
+
+    fn rebuild_projects(&self) {
+        while let Some(child) = self.projects_list.first_child() {
+            self.projects_list.remove(&child);
+        }
+
+        if self.stats.busiest_projects.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title("No projects in this period")
+                .build();
+            row.add_css_class("dim-label");
+            self.projects_list.append(&row);
+            return;
+        }
+
+        let max = self
+            .stats
+            .busiest_projects
+            .iter()
+            .map(|project| project.sessions)
+            .max()
+            .unwrap_or(1) as f64;
+
+        for project in &self.stats.busiest_projects {
+            let name = Path::new(&project.project_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(&project.project_path);
+
+            let row = adw::ActionRow::builder()
+                .title(name)
+                .subtitle(&project.project_path)
+                .use_markup(false)
+                .build();
+
+            let level = gtk::LevelBar::builder()
+                .min_value(0.0)
+                .max_value(max)
+                .value(project.sessions as f64)
+                .width_request(120)
+                .valign(gtk::Align::Center)
+                .build();
+            row.add_suffix(&level);
+
+            let count = gtk::Label::builder()
+                .label(format!(
+                    "{} sessions · {} messages",
+                    project.sessions, project.messages
+                ))
+                .css_classes(["dim-label", "numeric"])
+                .build();
+            row.add_suffix(&count);
+
+            self.projects_list.append(&row);
+        }
+    }
+}
+
+fn tools_label(tools: &[Tool]) -> String {
+    if tools.len() == Tool::ALL.len() {
+        "All tools".to_string()
+    } else if tools.is_empty() {
+        "No tools".to_string()
+    } else {
+        tools
+            .iter()
+            .map(|tool| tool.display_name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn cost_label(stats: &Statistics) -> String {
+    stats
+        .totals
+        .cost_usd
+        .map(format_cost)
+        .unwrap_or_else(|| "—".to_string())
+}
+
+fn average_session_label(stats: &Statistics) -> String {
+    if stats.totals.sessions == 0 {
+        return "—".to_string();
+    }
+    let minutes = (stats.totals.avg_duration_secs / 60.0).round() as i64;
+    let duration = if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    };
+    format!("{} · {:.0} msgs", duration, stats.totals.avg_messages)
+}
+
+fn set_source_hex(cr: &gtk::cairo::Context, hex: &str) {
+    if let Ok(color) = gtk::gdk::RGBA::parse(hex) {
+        cr.set_source_rgba(
+            color.red() as f64,
+            color.green() as f64,
+            color.blue() as f64,
+            color.alpha() as f64,
+        );
+    }
+}
+
+fn set_source_foreground(cr: &gtk::cairo::Context, widget: &gtk::DrawingArea, alpha: f64) {
+    let color = widget.color();
+    cr.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        alpha,
+    );
+}
+
+/// Stacked bars of sessions per bucket, one colour per tool.
+fn draw_sessions_chart(
+    area: &gtk::DrawingArea,
+    cr: &gtk::cairo::Context,
+    width: f64,
+    height: f64,
+    data: &ChartData,
+) {
+    const AXIS_HEIGHT: f64 = 18.0;
+    const LEGEND_HEIGHT: f64 = 18.0;
+
+    cr.set_font_size(11.0);
+
+    // Legend
+    let mut x = 0.0;
+    for tool in Tool::ALL {
+        set_source_hex(cr, tool.color());
+        cr.rectangle(x, 4.0, 10.0, 10.0);
+        let _ = cr.fill();
+        set_source_foreground(cr, area, 0.8);
+        cr.move_to(x + 14.0, 13.0);
+        let _ = cr.show_text(tool.display_name());
+        x += 14.0
+            + cr.text_extents(tool.display_name())
+                .map(|extents| extents.x_advance())
+                .unwrap_or(60.0)
+            + 16.0;
+    }
+
+    let max = data
+        .buckets
+        .iter()
+        .map(|(_, per_tool)| per_tool.iter().map(|(_, n)| n).sum::<usize>())
+        .max()
+        .unwrap_or(0);
+
+    let plot_top = LEGEND_HEIGHT + 8.0;
+    let plot_height = (height - plot_top - AXIS_HEIGHT).max(1.0);
+    let baseline = plot_top + plot_height;
+
+    set_source_foreground(cr, area, 0.15);
+    cr.rectangle(0.0, baseline, width, 1.0);
+    let _ = cr.fill();
+
+    if data.buckets.is_empty() || max == 0 {
+        set_source_foreground(cr, area, 0.5);
+        cr.move_to(0.0, plot_top + plot_height / 2.0);
+        let _ = cr.show_text("No sessions in this period");
+        return;
+    }
+
+    set_source_foreground(cr, area, 0.5);
+    cr.move_to(width - 40.0, plot_top + 10.0);
+    let _ = cr.show_text(&format!("max {}", max));
+
+    let slot = width / data.buckets.len() as f64;
+    let bar_width = (slot * 0.7).max(1.0);
+
+    for (i, (bucket, per_tool)) in data.buckets.iter().enumerate() {
+        let x = i as f64 * slot + (slot - bar_width) / 2.0;
+        let mut y = baseline;
+        for (tool, sessions) in per_tool {
+            if *sessions == 0 {
+                continue;
+            }
+            let bar_height = plot_height * *sessions as f64 / max as f64;
+            y -= bar_height;
+            set_source_hex(cr, tool.color());
+            cr.rectangle(x, y, bar_width, bar_height);
+            let _ = cr.fill();
+        }
+
+        // Label the first bucket and each change of month.
+        let label = match i.checked_sub(1).map(|prev| data.buckets[prev].0.month()) {
+            None => Some(bucket.format("%b %d").to_string()),
+            Some(month) if month != bucket.month() => Some(bucket.format("%b").to_string()),
+            Some(_) => None,
+        };
+        if let Some(label) = label {
+            set_source_foreground(cr, area, 0.6);
+            cr.move_to(i as f64 * slot, height - 4.0);
+            let _ = cr.show_text(&label);
+        }
+    }
+}
+
+/// GitHub-style calendar: one column per week, Monday on top.
+fn draw_heatmap(area: &gtk::DrawingArea, cr: &gtk::cairo::Context, columns: &[Vec<HeatmapCell>]) {
+    cr.set_font_size(10.0);
+    let pitch = HEATMAP_CELL + HEATMAP_GAP;
+
+    let mut previous_month = None;
+    for (week, cells) in columns.iter().enumerate() {
+        let x = week as f64 * pitch;
+
+        if let Some(first) = cells.first()
+            && previous_month != Some(first.date.month())
+        {
+            if previous_month.is_some() {
+                set_source_foreground(cr, area, 0.6);
+                cr.move_to(x, HEATMAP_TOP - 4.0);
+                let _ = cr.show_text(&first.date.format("%b").to_string());
+            }
+            previous_month = Some(first.date.month());
+        }
+
+        for (day, cell) in cells.iter().enumerate() {
+            if cell.level == 0 {
+                set_source_foreground(cr, area, 0.08);
+            } else {
+                set_source_hex(cr, HEATMAP_COLORS[cell.level as usize - 1]);
+            }
+            cr.rectangle(
+                x,
+                HEATMAP_TOP + day as f64 * pitch,
+                HEATMAP_CELL,
+                HEATMAP_CELL,
+            );
+            let _ = cr.fill();
+        }
+    }
+}
+
+fn heatmap_cell_at(columns: &[Vec<HeatmapCell>], x: f64, y: f64) -> Option<HeatmapCell> {
+    let pitch = HEATMAP_CELL + HEATMAP_GAP;
+    if x < 0.0 || y < HEATMAP_TOP {
+        return None;
+    }
+    let week = (x / pitch) as usize;
+    let day = ((y - HEATMAP_TOP) / pitch) as usize;
+    columns.get(week)?.get(day).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::stats::StatsTotals;
+
+    #[test]
+    fn average_session_label_formats_duration_and_messages() {
+        let stats = Statistics {
+            totals: StatsTotals {
+                sessions: 3,
+                avg_duration_secs: 4_500.0,
+                avg_messages: 12.4,
+                ..StatsTotals::default()
+            },
+            ..Statistics::default()
+        };
+        assert_eq!(average_session_label(&stats), "1h 15m · 12 msgs");
+        assert_eq!(average_session_label(&Statistics::default()), "—");
+    }
+
+    #[test]
+    fn tools_label_summarizes_selection() {
+        assert_eq!(tools_label(Tool::ALL), "All tools");
+        assert_eq!(
+            tools_label(&[Tool::Codex, Tool::OpenCode]),
+            "Codex, OpenCode"
+        );
+    }
+}
@@ -0,0 +1,773 @@
+use crate::ui::markdown::pango_escape;
+
+/// Token colors, mid-tone Adwaita palette entries so they read on both the
+/// light and dark code block backgrounds.
+const KEYWORD_FG: &str = "#c061cb";
+const TYPE_FG: &str = "#3584e4";
+const STRING_FG: &str = "#26a269";
+const NUMBER_FG: &str = "#e66100";
+const ADDED_FG: &str = "#26a269";
+const REMOVED_FG: &str = "#e01b24";
+
+/// Languages with a dedicated highlighter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Python,
+    TypeScript,
+    Shell,
+    Json,
+    Toml,
+    Yaml,
+    Diff,
+    Sql,
+}
+
+impl Language {
+    /// Resolve a fence info string (`rust`, `py`, `ts title="x"`, `rust,ignore`).
+    pub fn from_fence(info: &str) -> Option<Self> {
+        let name = info
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .next()?
+            .trim_start_matches('.')
+            .to_ascii_lowercase();
+
+        match name.as_str() {
+            "rust" | "rs" => Some(Language::Rust),
+            "python" | "py" | "python3" => Some(Language::Python),
+            "typescript" | "ts" | "tsx" | "javascript" | "js" | "jsx" | "mjs" => {
+                Some(Language::TypeScript)
+            }
+            "sh" | "bash" | "shell" | "zsh" | "console" | "shellsession" => Some(Language::Shell),
+            "json" | "jsonc" | "json5" => Some(Language::Json),
+            "toml" => Some(Language::Toml),
+            "yaml" | "yml" => Some(Language::Yaml),
+            "diff" | "patch" => Some(Language::Diff),
+            "sql" | "sqlite" | "postgresql" | "mysql" => Some(Language::Sql),
+            _ => None,
+        }
+    }
+
+    fn spec(self) -> &'static Spec {
+        match self {
+            Language::Rust => &RUST,
+            Language::Python => &PYTHON,
+            Language::TypeScript => &TYPESCRIPT,
+            Language::Shell => &SHELL,
+            Language::Json => &JSON,
+            Language::Toml => &TOML,
+            Language::Yaml => &YAML,
+            Language::Sql => &SQL,
+            // Diff is line-based and never reaches the generic scanner.
+            Language::Diff => &PLAIN,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Plain,
+    Keyword,
+    Type,
+    String,
+    Number,
+    Comment,
+    Key,
+}
+
+impl Token {
+    fn open_tag(self) -> Option<String> {
+        let color = match self {
+            Token::Plain => return None,
+            Token::Comment => return Some("<span fgalpha=\"55%\" style=\"italic\">".to_string()),
+            Token::Keyword => KEYWORD_FG,
+            Token::Type | Token::Key => TYPE_FG,
+            Token::String => STRING_FG,
+            Token::Number => NUMBER_FG,
+        };
+        Some(format!("<span foreground=\"{}\">", color))
+    }
+}
+
+/// Lexical rules for one language.
+struct Spec {
+    keywords: &'static [&'static str],
+    /// Constants such as `true` or `None`, colored like numbers.
+    literals: &'static [&'static str],
+    /// Builtin type names, in addition to capitalized identifiers when
+    /// `capitalized_types` is set.
+    types: &'static [&'static str],
+    capitalized_types: bool,
+    case_insensitive: bool,
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    /// String delimiters, longest first.
+    strings: &'static [&'static str],
+    multiline_strings: bool,
+    /// Rust-style `'c'` literals that must not be confused with lifetimes.
+    char_literals: bool,
+    /// Strings directly followed by `:` are object keys (JSON).
+    string_keys: bool,
+    /// Bare keys at the start of a line, before this separator (YAML, TOML).
+    line_keys: Option<char>,
+    /// `[section]` headers at the start of a line (TOML).
+    sections: bool,
+    /// `$NAME` / `${NAME}` expansions (shell).
+    variables: bool,
+}
+
+const PLAIN: Spec = Spec {
+    keywords: &[],
+    literals: &[],
+    types: &[],
+    capitalized_types: false,
+    case_insensitive: false,
+    line_comments: &[],
+    block_comment: None,
+    strings: &[],
+    multiline_strings: false,
+    char_literals: false,
+    string_keys: false,
+    line_keys: None,
+    sections: false,
+    variables: false,
+};
+
+const RUST: Spec = Spec {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while",
+    ],
+    literals: &["true", "false"],
+    types: &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        "f32", "f64", "bool", "char", "str",
+    ],
+    capitalized_types: true,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["\""],
+    multiline_strings: true,
+    char_literals: true,
+    ..PLAIN
+};
+
+const PYTHON: Spec = Spec {
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is",
+        "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with",
+        "yield", "self",
+    ],
+    literals: &["True", "False", "None"],
+    types: &[
+        "int", "float", "str", "bytes", "bool", "list", "dict", "set", "tuple", "object",
+    ],
+    capitalized_types: true,
+    line_comments: &["#"],
+    strings: &["\"\"\"", "'''", "\"", "'"],
+    ..PLAIN
+};
+
+const TYPESCRIPT: Spec = Spec {
+    keywords: &[
+        "abstract",
+        "as",
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "debugger",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "of",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "try",
+        "type",
+        "typeof",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    literals: &["true", "false", "null", "undefined"],
+    types: &[
+        "string", "number", "boolean", "any", "unknown", "never", "object", "bigint", "symbol",
+    ],
+    capitalized_types: true,
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["`", "\"", "'"],
+    ..PLAIN
+};
+
+const SHELL: Spec = Spec {
+    keywords: &[
+        "if", "then", "else", "elif", "fi", "for", "while", "until", "do", "done", "case", "esac",
+        "in", "function", "return", "local", "export", "readonly", "declare", "unset", "break",
+        "continue", "exit", "source", "alias", "sudo",
+    ],
+    literals: &["true", "false"],
+    line_comments: &["#"],
+    strings: &["\"", "'"],
+    variables: true,
+    ..PLAIN
+};
+
+const JSON: Spec = Spec {
+    literals: &["true", "false", "null"],
+    line_comments: &["//"],
+    strings: &["\""],
+    string_keys: true,
+    ..PLAIN
+};
+
+const TOML: Spec = Spec {
+    literals: &["true", "false"],
+    line_comments: &["#"],
+    strings: &["\"\"\"", "'''", "\"", "'"],
+    line_keys: Some('='),
+    sections: true,
+    ..PLAIN
+};
+
+const YAML: Spec = Spec {
+    literals: &["true", "false", "null", "yes", "no", "on", "off"],
+    line_comments: &["#"],
+    strings: &["\"", "'"],
+    line_keys: Some(':'),
+    ..PLAIN
+};
+
+const SQL: Spec = Spec {
+    keywords: &[
+        "select",
+        "from",
+        "where",
+        "and",
+        "or",
+        "not",
+        "insert",
+        "into",
+        "values",
+        "update",
+        "set",
+        "delete",
+        "create",
+        "table",
+        "index",
+        "view",
+        "drop",
+        "alter",
+        "add",
+        "column",
+        "join",
+        "left",
+        "right",
+        "inner",
+        "outer",
+        "full",
+        "cross",
+        "on",
+        "as",
+        "group",
+        "by",
+        "order",
+        "having",
+        "limit",
+        "offset",
+        "union",
+        "all",
+        "distinct",
+        "case",
+        "when",
+        "then",
+        "else",
+        "end",
+        "is",
+        "like",
+        "in",
+        "exists",
+        "between",
+        "primary",
+        "key",
+        "foreign",
+        "references",
+        "default",
+        "begin",
+        "commit",
+        "rollback",
+        "transaction",
+        "with",
+        "returning",
+        "if",
+        "virtual",
+        "using",
+        "asc",
+        "desc",
+        "unique",
+        "constraint",
+    ],
+    literals: &["null", "true", "false"],
+    types: &[
+        "integer",
+        "int",
+        "text",
+        "real",
+        "blob",
+        "varchar",
+        "char",
+        "boolean",
+        "date",
+        "timestamp",
+        "numeric",
+        "bigint",
+        "serial",
+    ],
+    case_insensitive: true,
+    line_comments: &["--"],
+    block_comment: Some(("/*", "*/")),
+    strings: &["'", "\""],
+    ..PLAIN
+};
+
+/// Render `code` as Pango markup, colored according to `language`.
+///
+/// Unknown languages are only escaped. The result is valid input for
+/// `highlight_in_markup`, so search matches can be layered on top.
+pub fn highlight_code(code: &str, language: Option<Language>) -> String {
+    match language {
+        None => pango_escape(code),
+        Some(Language::Diff) => highlight_diff(code),
+        Some(language) => Scanner::new(code, language.spec()).run(),
+    }
+}
+
+fn push_token(out: &mut String, token: Token, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match token.open_tag() {
+        Some(open) => {
+            out.push_str(&open);
+            out.push_str(&pango_escape(text));
+            out.push_str("</span>");
+        }
+        None => out.push_str(&pango_escape(text)),
+    }
+}
+
+fn highlight_diff(code: &str) -> String {
+    let mut out = String::with_capacity(code.len() * 2);
+    for (i, line) in code.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let markup = pango_escape(line);
+        if line.starts_with("+++") || line.starts_with("---") || line.starts_with("diff ") {
+            out.push_str(&format!("<b>{}</b>", markup));
+        } else if line.starts_with("@@") {
+            out.push_str(&format!(
+                "<span foreground=\"{}\">{}</span>",
+                KEYWORD_FG, markup
+            ));
+        } else if line.starts_with('+') {
+            out.push_str(&format!(
+                "<span foreground=\"{}\">{}</span>",
+                ADDED_FG, markup
+            ));
+        } else if line.starts_with('-') {
+            out.push_str(&format!(
+                "<span foreground=\"{}\">{}</span>",
+                REMOVED_FG, markup
+            ));
+        } else {
+            out.push_str(&markup);
+        }
+    }
+    out
+}
+
+struct Scanner<'a> {
+    code: &'a str,
+    spec: &'static Spec,
+    pos: usize,
+    out: String,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(code: &'a str, spec: &'static Spec) -> Self {
+        Self {
+            code,
+            spec,
+            pos: 0,
+            out: String::with_capacity(code.len() * 2),
+        }
+    }
+
+    fn run(mut self) -> String {
+        while self.pos < self.code.len() {
+            if self.at_line_start() && self.line_prefix() {
+                continue;
+            }
+            self.next_token();
+        }
+        self.out
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.code[self.pos..]
+    }
+
+    fn emit(&mut self, token: Token, len: usize) {
+        let text = &self.code[self.pos..self.pos + len];
+        push_token(&mut self.out, token, text);
+        self.pos += len;
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.pos == 0 || self.code[..self.pos].ends_with('\n')
+    }
+
+    fn previous_char(&self) -> Option<char> {
+        self.code[..self.pos].chars().next_back()
+    }
+
+    /// Indentation, TOML sections and bare keys. Returns `true` if anything
+    /// was consumed.
+    fn line_prefix(&mut self) -> bool {
+        let line = self.rest().split('\n').next().unwrap_or_default();
+        let indent = line.len() - line.trim_start().len();
+        let body = &line[indent..];
+
+        if self.spec.sections && body.starts_with('[') {
+            let len = body.find(']').map_or(body.len(), |end| end + 1);
+            self.emit(Token::Plain, indent);
+            self.emit(Token::Keyword, len);
+            return true;
+        }
+
+        let Some(separator) = self.spec.line_keys else {
+            return false;
+        };
+        // YAML list items may carry a key: `- name: value`.
+        let item = if body.starts_with("- ") { 2 } else { 0 };
+        let candidate = &body[item..];
+        let Some(key_len) = candidate.find(separator) else {
+            return false;
+        };
+        let key = &candidate[..key_len];
+        let after = &candidate[key_len + separator.len_utf8()..];
+        let is_key = !key.trim().is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_alphanumeric() || "_-. \"'".contains(c))
+            && (separator != ':' || after.is_empty() || after.starts_with(' '));
+        if !is_key {
+            return false;
+        }
+
+        self.emit(Token::Plain, indent + item);
+        self.emit(Token::Key, key_len);
+        true
+    }
+
+    fn next_token(&mut self) {
+        let rest = self.rest();
+        let spec = self.spec;
+
+        let comment_allowed = |prefix: &str| {
+            // `#` only starts a comment at a word boundary (`${#x}`, `a#b`).
+            prefix != "#" || self.previous_char().is_none_or(char::is_whitespace)
+        };
+        if spec
+            .line_comments
+            .iter()
+            .any(|prefix| rest.starts_with(prefix) && comment_allowed(prefix))
+        {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            self.emit(Token::Comment, len);
+            return;
+        }
+
+        if let Some((open, close)) = spec.block_comment
+            && rest.starts_with(open)
+        {
+            let len = rest[open.len()..]
+                .find(close)
+                .map_or(rest.len(), |end| open.len() + end + close.len());
+            self.emit(Token::Comment, len);
+            return;
+        }
+
+        if spec.char_literals && rest.starts_with('\'') {
+            if let Some(len) = char_literal_len(rest) {
+                self.emit(Token::String, len);
+            } else {
+                // A lifetime or label: `'a`, `'static`.
+                let len = 1 + ident_len(&rest[1..]);
+                self.emit(Token::Type, len);
+            }
+            return;
+        }
+
+        if let Some(delimiter) = spec.strings.iter().find(|d| rest.starts_with(**d)) {
+            let multiline = spec.multiline_strings || delimiter.len() == 3 || *delimiter == "`";
+            let len = string_len(rest, delimiter, multiline);
+            let token = if spec.string_keys && rest[len..].trim_start().starts_with(':') {
+                Token::Key
+            } else {
+                Token::String
+            };
+            self.emit(token, len);
+            return;
+        }
+
+        if spec.variables && rest.starts_with('$') {
+            let len = variable_len(rest);
+            if len > 1 {
+                self.emit(Token::Type, len);
+                return;
+            }
+        }
+
+        let Some(c) = rest.chars().next() else {
+            return;
+        };
+
+        if c.is_ascii_digit() {
+            self.emit(Token::Number, number_len(rest));
+            return;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let len = ident_len(rest);
+            let word = &rest[..len];
+            let token = self.classify(word);
+            self.emit(token, len);
+            return;
+        }
+
+        self.emit(Token::Plain, c.len_utf8());
+    }
+
+    fn classify(&self, word: &str) -> Token {
+        let spec = self.spec;
+        let contains = |list: &[&str]| {
+            if spec.case_insensitive {
+                list.iter().any(|w| w.eq_ignore_ascii_case(word))
+            } else {
+                list.contains(&word)
+            }
+        };
+
+        if contains(spec.keywords) {
+            Token::Keyword
+        } else if contains(spec.literals) {
+            Token::Number
+        } else if contains(spec.types)
+            || (spec.capitalized_types && word.starts_with(|c: char| c.is_uppercase()))
+        {
+            Token::Type
+        } else {
+            Token::Plain
+        }
+    }
+}
+
+fn ident_len(text: &str) -> usize {
+    text.char_indices()
+        .find(|(_, c)| !(c.is_alphanumeric() || *c == '_'))
+        .map_or(text.len(), |(i, _)| i)
+}
+
+fn number_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() {
+        let b = bytes[len];
+        let is_fraction = b == b'.' && bytes.get(len + 1).is_some_and(u8::is_ascii_digit);
+        if b.is_ascii_alphanumeric() || b == b'_' || is_fraction {
+            len += 1;
+        } else {
+            break;
+        }
+    }
+    len
+}
+
+/// Length of a string starting with `delimiter`, including both delimiters.
+/// Unterminated strings run to the end of the line (or text, if multiline).
+fn string_len(text: &str, delimiter: &str, multiline: bool) -> usize {
+    let mut chars = text[delimiter.len()..].char_indices();
+    while let Some((i, c)) = chars.next() {
+        let offset = delimiter.len() + i;
+        if c == '\\' && delimiter.len() == 1 {
+            chars.next();
+        } else if text[offset..].starts_with(delimiter) {
+            return offset + delimiter.len();
+        } else if c == '\n' && !multiline {
+            return offset;
+        }
+    }
+    text.len()
+}
+
+/// Length of a Rust char literal (`'a'`, `'\n'`, `'\u{1F600}'`), if `text`
+/// starts with one.
+fn char_literal_len(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().skip(1);
+    let (_, first) = chars.next()?;
+    if first == '\\' {
+        let close = text[2..].find('\'')?;
+        let len = 2 + close + 1;
+        return (close <= 10).then_some(len);
+    }
+    let (i, second) = chars.next()?;
+    (second == '\'' && first != '\'').then_some(i + 1)
+}
+
+fn variable_len(text: &str) -> usize {
+    let after = &text[1..];
+    if after.starts_with('{') {
+        return after.find('}').map_or(1, |end| end + 2);
+    }
+    match after.chars().next() {
+        Some(c) if c.is_ascii_digit() || "@#?$!*-".contains(c) => 2,
+        _ => 1 + ident_len(after),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::highlight::highlight_in_markup;
+
+    fn span(color: &str, text: &str) -> String {
+        format!("<span foreground=\"{}\">{}</span>", color, text)
+    }
+
+    #[test]
+    fn from_fence_resolves_aliases_and_attributes() {
+        assert_eq!(Language::from_fence("rust"), Some(Language::Rust));
+        assert_eq!(Language::from_fence("rust,ignore"), Some(Language::Rust));
+        assert_eq!(
+            Language::from_fence("ts title=\"a.ts\""),
+            Some(Language::TypeScript)
+        );
+        assert_eq!(Language::from_fence("YML"), Some(Language::Yaml));
+        assert_eq!(Language::from_fence("bash"), Some(Language::Shell));
+        assert_eq!(Language::from_fence("brainfuck"), None);
+    }
+
+    #[test]
+    fn rust_keywords_strings_comments_and_lifetimes() {
+        let markup = highlight_code(
+            "fn main<'a>() { let c = 'x'; // hi\n    \"s\" }",
+            Some(Language::Rust),
+        );
+        assert!(markup.starts_with(&span(KEYWORD_FG, "fn")));
+        assert!(markup.contains(&span(TYPE_FG, "&apos;a")));
+        assert!(markup.contains(&span(STRING_FG, "&apos;x&apos;")));
+        assert!(markup.contains("<span fgalpha=\"55%\" style=\"italic\">// hi</span>"));
+        assert!(markup.contains(&span(STRING_FG, "&quot;s&quot;")));
+    }
+
+    #[test]
+    fn unknown_language_is_only_escaped() {
+        assert_eq!(highlight_code("a < b && c", None), "a &lt; b &amp;&amp; c");
+    }
+
+    #[test]
+    fn json_distinguishes_keys_from_values() {
+        let markup = highlight_code(r#"{"name": "x", "n": 1, "ok": true}"#, Some(Language::Json));
+        assert!(markup.contains(&span(TYPE_FG, "&quot;name&quot;")));
+        assert!(markup.contains(&span(STRING_FG, "&quot;x&quot;")));
+        assert!(markup.contains(&span(NUMBER_FG, "1")));
+        assert!(markup.contains(&span(NUMBER_FG, "true")));
+    }
+
+    #[test]
+    fn yaml_and_toml_keys_and_sections() {
+        let yaml = highlight_code("jobs:\n  - name: build # ci", Some(Language::Yaml));
+        assert!(yaml.starts_with(&span(TYPE_FG, "jobs")));
+        assert!(yaml.contains(&format!("  - {}", span(TYPE_FG, "name"))));
+        assert!(yaml.contains("# ci</span>"));
+
+        let toml = highlight_code("[package]\nname = \"demo\"", Some(Language::Toml));
+        assert!(toml.starts_with(&span(KEYWORD_FG, "[package]")));
+        assert!(toml.contains(&span(TYPE_FG, "name ")));
+        assert!(toml.contains(&span(STRING_FG, "&quot;demo&quot;")));
+    }
+
+    #[test]
+    fn shell_variables_and_hash_inside_words() {
+        let markup = highlight_code("echo ${#HOME} $USER # done", Some(Language::Shell));
+        assert!(markup.contains(&span(TYPE_FG, "${#HOME}")));
+        assert!(markup.contains(&span(TYPE_FG, "$USER")));
+        assert!(markup.ends_with("# done</span>"));
+    }
+
+    #[test]
+    fn sql_keywords_are_case_insensitive() {
+        let markup = highlight_code("SELECT id FROM t -- all", Some(Language::Sql));
+        assert!(markup.starts_with(&span(KEYWORD_FG, "SELECT")));
+        assert!(markup.contains(&span(KEYWORD_FG, "FROM")));
+        assert!(markup.contains("-- all</span>"));
+    }
+
+    #[test]
+    fn diff_colors_added_and_removed_lines() {
+        let markup = highlight_code("@@ -1 +1 @@\n-old\n+new\n ctx", Some(Language::Diff));
+        assert!(markup.contains(&span(REMOVED_FG, "-old")));
+        assert!(markup.contains(&span(ADDED_FG, "+new")));
+        assert!(markup.ends_with("\n ctx"));
+    }
+
+    #[test]
+    fn python_triple_quoted_strings_span_lines() {
+        let markup = highlight_code("x = \"\"\"a\nb\"\"\"\nNone", Some(Language::Python));
+        assert!(markup.contains(&span(STRING_FG, "&quot;&quot;&quot;a\nb&quot;&quot;&quot;")));
+        assert!(markup.ends_with(&span(NUMBER_FG, "None")));
+    }
+
+    #[test]
+    fn search_highlighting_applies_on_top_of_syntax_markup() {
+        let markup = highlight_code("let needle = \"needle\";", Some(Language::Rust));
+        let (highlighted, count) = highlight_in_markup(&markup, "needle");
+        assert_eq!(count, 2);
+        assert!(highlighted.contains(&span(KEYWORD_FG, "let")));
+    }
+}
@@ -0,0 +1,148 @@
+use relm4::gtk;
+use relm4::gtk::prelude::*;
+use serde_json::Value;
+
+use crate::models::file_change::file_changes;
+use crate::models::{ToolCall, ToolResult};
+use crate::ui::markdown::{pango_escape, style_redaction_markers};
+use crate::ui::syntax::{Language, highlight_code};
+
+/// Widgets of a collapsible tool card.
+pub struct ToolCard {
+    pub expander: gtk::Expander,
+    /// Box holding the output section, for appending a "Show full output"
+    /// button when the result is truncated.
+    pub output_box: gtk::Box,
+}
+
+/// Build a card for a tool call and the result folded under it.
+///
+/// Either half may be missing: a call still running has no result, and a
+/// result whose call is on another page is shown on its own.
+pub fn build(call: Option<&ToolCall>, result: Option<&ToolResult>, expanded: bool) -> ToolCard {
+    let is_error = result.is_some_and(|result| result.is_error);
+
+    let header = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let icon = gtk::Image::from_icon_name(
+        call.map_or("emblem-system-symbolic", |call| icon_name(&call.name)),
+    );
+    header.append(&icon);
+
+    let summary = match call {
+        Some(call) => call.summary(),
+        None => "Tool output".to_string(),
+    };
+    let summary_label = gtk::Label::new(Some(&summary));
+    summary_label.add_css_class("monospace");
+    summary_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+    summary_label.set_xalign(0.0);
+    summary_label.set_hexpand(true);
+    summary_label.set_tooltip_text(Some(&summary));
+    header.append(&summary_label);
+
+    if is_error {
+        let error_icon = gtk::Image::from_icon_name("dialog-error-symbolic");
+        error_icon.add_css_class("error");
+        error_icon.set_tooltip_text(Some("The tool reported an error"));
+        header.append(&error_icon);
+    } else if call.is_some() && result.is_none() {
+        let pending = gtk::Label::new(Some("no output"));
+        pending.add_css_class("caption");
+        pending.add_css_class("dim-label");
+        header.append(&pending);
+    }
+
+    let body = gtk::Box::new(gtk::Orientation::Vertical, 6);
+    body.set_margin_top(6);
+
+    if let Some(call) = call
+        && !call.input.is_null()
+    {
+        body.append(&code_label(&input_markup(call)));
+    }
+
+    let output_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
+    if let Some(result) = result {
+        let output = result.output.trim_end();
+        let markup = if output.is_empty() {
+            "<i>(no output)</i>".to_string()
+        } else {
+            style_redaction_markers(&pango_escape(output))
+        };
+        let label = code_label(&markup);
+        if result.is_error {
+            label.add_css_class("tool-output-error");
+        }
+        output_box.append(&label);
+    }
+    body.append(&output_box);
+
+    let expander = gtk::Expander::new(None);
+    expander.set_label_widget(Some(&header));
+    expander.set_child(Some(&body));
+    expander.set_expanded(expanded);
+
+    ToolCard {
+        expander,
+        output_box,
+    }
+}
+
+/// Symbolic icon for a tool, by the kind of work it does.
+fn icon_name(tool: &str) -> &'static str {
+    match tool.to_ascii_lowercase().as_str() {
+        "bash" | "bashoutput" | "shell" | "local_shell" | "exec_command" | "run_command" => {
+            "utilities-terminal-symbolic"
+        }
+        "edit" | "multiedit" | "write" | "notebookedit" | "apply_patch" | "patch"
+        | "write_file" | "edit_file" => "document-edit-symbolic",
+        "read" | "view" | "read_file" | "list" | "ls" | "list_files" => "document-open-symbolic",
+        "grep" | "glob" | "search" | "find" | "codesearch" => "system-search-symbolic",
+        "webfetch" | "websearch" | "web_search" | "fetch" => "web-browser-symbolic",
+        _ => "emblem-system-symbolic",
+    }
+}
+
+/// File edits are shown as unified diffs, shell commands as scripts,
+/// anything else as JSON.
+fn input_markup(call: &ToolCall) -> String {
+    let changes = file_changes(call);
+    if !changes.is_empty() {
+        let diff: String = changes
+            .iter()
+            .map(|change| change.to_unified_diff())
+            .collect();
+        return highlight_code(diff.trim_end(), Some(Language::Diff));
+    }
+
+    let input = &call.input;
+    if let Some(text) = input.as_str() {
+        let language = text
+            .starts_with("*** Begin Patch")
+            .then_some(Language::Diff);
+        return highlight_code(text, language);
+    }
+    if let Some(command) = input.get("command").and_then(Value::as_str)
+        && input.as_object().is_some_and(|fields| {
+            fields
+                .keys()
+                .all(|key| matches!(key.as_str(), "command" | "description" | "timeout"))
+        })
+    {
+        return highlight_code(command, Some(Language::Shell));
+    }
+    let json = serde_json::to_string_pretty(input).unwrap_or_default();
+    highlight_code(&json, Some(Language::Json))
+}
+
+fn code_label(markup: &str) -> gtk::Label {
+    let label = gtk::Label::new(None);
+    label.set_markup(markup);
+    label.add_css_class("code-block");
+    label.set_wrap(true);
+    label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+    label.set_xalign(0.0);
+    label.set_halign(gtk::Align::Fill);
+    label.set_selectable(true);
+    label
+}
@@ -0,0 +1,53 @@
+use relm4::gtk;
+use relm4::gtk::prelude::*;
+
+use crate::models::{Message, Role, ToolCall};
+use crate::ui::markdown::pango_escape;
+
+/// Longest message excerpt shown in a transcript, in characters.
+const EXCERPT_MAX_CHARS: usize = 600;
+
+/// Compact read-only transcript: one line per message with the role, then
+/// an excerpt of the text or the tool call summary. Reasoning, tool output
+/// and system text are left out.
+pub fn build(messages: &[Message]) -> gtk::Box {
+    let transcript = gtk::Box::new(gtk::Orientation::Vertical, 4);
+    for message in messages {
+        if let Some(markup) = message_markup(message.role, &message.content) {
+            transcript.append(&excerpt_label(&markup));
+        }
+    }
+    transcript
+}
+
+fn message_markup(role: Role, content: &str) -> Option<String> {
+    let text = match role {
+        Role::User | Role::Assistant => excerpt(content),
+        Role::ToolCall => ToolCall::from_content(content)?.summary(),
+        Role::Thinking | Role::ToolResult | Role::System => return None,
+    };
+    Some(format!(
+        "<b>{}</b>  {}",
+        role.label(),
+        pango_escape(text.trim())
+    ))
+}
+
+fn excerpt(content: &str) -> String {
+    if content.chars().count() <= EXCERPT_MAX_CHARS {
+        return content.to_string();
+    }
+    let mut excerpt: String = content.chars().take(EXCERPT_MAX_CHARS).collect();
+    excerpt.push('\u{2026}');
+    excerpt
+}
+
+fn excerpt_label(markup: &str) -> gtk::Label {
+    let label = gtk::Label::new(None);
+    label.set_markup(markup);
+    label.set_wrap(true);
+    label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+    label.set_xalign(0.0);
+    label.set_selectable(true);
+    label
+}
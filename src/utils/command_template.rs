@@ -0,0 +1,274 @@
+use std::path::Path;
+
+use crate::models::Tool;
+
+const PLACEHOLDERS: &[&str] = &["workdir", "session_id", "tool", "resume_cmd"];
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum TemplateError {
+    #[error("Command template is empty")]
+    Empty,
+    #[error("Command template has an unterminated quote")]
+    UnterminatedQuote,
+    #[error("Command template has an unclosed placeholder")]
+    UnclosedPlaceholder,
+    #[error("Unknown placeholder {{{0}}}")]
+    UnknownPlaceholder(String),
+    #[error("Command template must use {{resume_cmd}} or {{session_id}}")]
+    MissingSession,
+}
+
+/// Values substituted into a [`CommandTemplate`] for the session being
+/// resumed.
+pub struct TemplateContext<'a> {
+    pub workdir: &'a Path,
+    pub session_id: &'a str,
+    pub tool: Tool,
+    /// Resume command as built by `build_resume_command`.
+    pub resume_args: &'a [String],
+}
+
+/// A user-supplied command line for resuming sessions in a terminal the app
+/// has no built-in support for.
+///
+/// The template is split into words like a shell would, honoring single and
+/// double quotes and backslashes, but nothing is expanded. Placeholders are
+/// then filled into each word, so a substituted value stays one argument
+/// whatever it contains. `{resume_cmd}` is the whole resume command quoted
+/// for `sh -c`; `{{` and `}}` are literal braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandTemplate {
+    words: Vec<String>,
+}
+
+impl CommandTemplate {
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        let words = split_words(template)?;
+        if words.is_empty() {
+            return Err(TemplateError::Empty);
+        }
+
+        let mut names = Vec::new();
+        for word in &words {
+            names.extend(placeholders(word)?);
+        }
+        if let Some(name) = names.iter().find(|name| !PLACEHOLDERS.contains(name)) {
+            return Err(TemplateError::UnknownPlaceholder(name.to_string()));
+        }
+        if !names
+            .iter()
+            .any(|name| matches!(*name, "resume_cmd" | "session_id"))
+        {
+            return Err(TemplateError::MissingSession);
+        }
+
+        Ok(Self { words })
+    }
+
+    /// The command line with placeholders filled in from `context`.
+    pub fn expand(&self, context: &TemplateContext) -> Vec<String> {
+        let resume_cmd = context
+            .resume_args
+            .iter()
+            .map(|arg| shell_quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let workdir = context.workdir.to_string_lossy();
+        let tool = context.tool.to_storage();
+
+        self.words
+            .iter()
+            .map(|word| {
+                substitute(word, |name| match name {
+                    "workdir" => &workdir,
+                    "session_id" => context.session_id,
+                    "tool" => &tool,
+                    "resume_cmd" => &resume_cmd,
+                    _ => unreachable!("placeholders are checked when parsing"),
+                })
+            })
+            .collect()
+    }
+}
+
+/// `arg` quoted for a POSIX shell.
+pub fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || "-_./=:@%+,".contains(ch))
+    {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+fn split_words(template: &str) -> Result<Vec<String>, TemplateError> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = template.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            ch if ch.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(ch) => word.push(ch),
+                        None => return Err(TemplateError::UnterminatedQuote),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(next @ ('"' | '\\')) => word.push(next),
+                            Some(next) => {
+                                word.push('\\');
+                                word.push(next);
+                            }
+                            None => return Err(TemplateError::UnterminatedQuote),
+                        },
+                        Some(ch) => word.push(ch),
+                        None => return Err(TemplateError::UnterminatedQuote),
+                    }
+                }
+            }
+            '\\' => {
+                let word = word.get_or_insert_with(String::new);
+                if let Some(next) = chars.next() {
+                    word.push(next);
+                }
+            }
+            ch => word.get_or_insert_with(String::new).push(ch),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Names of the placeholders in `word`.
+fn placeholders(word: &str) -> Result<Vec<&str>, TemplateError> {
+    let mut names = Vec::new();
+    let mut rest = word;
+    while let Some(start) = rest.find(['{', '}']) {
+        let after = &rest[start + 1..];
+        if let Some(escaped) = after.strip_prefix(&rest[start..start + 1]) {
+            rest = escaped;
+            continue;
+        }
+        if rest[start..].starts_with('}') {
+            return Err(TemplateError::UnclosedPlaceholder);
+        }
+        let end = after.find('}').ok_or(TemplateError::UnclosedPlaceholder)?;
+        names.push(&after[..end]);
+        rest = &after[end + 1..];
+    }
+    Ok(names)
+}
+
+/// `word` with each placeholder replaced by `value(name)`. The word must
+/// have been checked by `placeholders`.
+fn substitute<'a>(word: &str, value: impl Fn(&str) -> &'a str) -> String {
+    let mut result = String::new();
+    let mut rest = word;
+    while let Some(start) = rest.find(['{', '}']) {
+        result.push_str(&rest[..start]);
+        let brace = &rest[start..start + 1];
+        let after = &rest[start + 1..];
+        if let Some(escaped) = after.strip_prefix(brace) {
+            result.push_str(brace);
+            rest = escaped;
+            continue;
+        }
+        let end = after.find('}').unwrap_or(after.len());
+        result.push_str(value(&after[..end]));
+        rest = after.get(end + 1..).unwrap_or("");
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(template: &str) -> Vec<String> {
+        let resume_args: Vec<String> = ["bash", "-lc", "cd \"$1\" && claude -r \"$2\"", "--"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        CommandTemplate::parse(template)
+            .unwrap()
+            .expand(&TemplateContext {
+                workdir: Path::new("/home/me/my project"),
+                session_id: "abc; rm -rf ~",
+                tool: Tool::ClaudeCode,
+                resume_args: &resume_args,
+            })
+    }
+
+    #[test]
+    fn test_expand_keeps_values_as_single_arguments() {
+        assert_eq!(
+            expand("wrapper --dir={workdir} --tool {tool} -- {session_id}"),
+            vec![
+                "wrapper",
+                "--dir=/home/me/my project",
+                "--tool",
+                "claude_code",
+                "--",
+                "abc; rm -rf ~",
+            ]
+        );
+        assert_eq!(
+            expand("'my term' --profile \"AI agents\" -e sh -c {resume_cmd}"),
+            vec![
+                "my term",
+                "--profile",
+                "AI agents",
+                "-e",
+                "sh",
+                "-c",
+                "bash -lc 'cd \"$1\" && claude -r \"$2\"' --",
+            ]
+        );
+        assert_eq!(
+            expand("echo {{literal}} {session_id}"),
+            vec!["echo", "{literal}", "abc; rm -rf ~"]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_templates() {
+        assert_eq!(CommandTemplate::parse("  "), Err(TemplateError::Empty));
+        assert_eq!(
+            CommandTemplate::parse("term -e 'sh {resume_cmd}"),
+            Err(TemplateError::UnterminatedQuote)
+        );
+        assert_eq!(
+            CommandTemplate::parse("term -e {resume_cmd"),
+            Err(TemplateError::UnclosedPlaceholder)
+        );
+        assert_eq!(
+            CommandTemplate::parse("term -e {command}"),
+            Err(TemplateError::UnknownPlaceholder("command".to_string()))
+        );
+        assert_eq!(
+            CommandTemplate::parse("term --cwd {workdir}"),
+            Err(TemplateError::MissingSession)
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("plain-arg_1.0"), "plain-arg_1.0");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
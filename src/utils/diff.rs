@@ -0,0 +1,187 @@
+//! Line-based unified diffs for rendering file edits.
+
+/// Above this many line pairs the LCS table gets too large; the change is
+/// shown as a full replacement instead.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Unified diff hunks (without `---`/`+++` headers) turning `old` into `new`,
+/// with `context` unchanged lines around each change.
+///
+/// Line numbers in hunk headers are relative to the snippets, since edit
+/// tools only send the replaced text, not its position in the file.
+pub fn unified_diff(old: &str, new: &str, context: usize) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut out = String::new();
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, Op::Equal(_)))
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut i = 0;
+    while i < changed.len() {
+        // Grow the hunk while the next change is within two contexts.
+        let start = changed[i].saturating_sub(context);
+        let mut end = changed[i];
+        while i + 1 < changed.len() && changed[i + 1] <= end + 2 * context + 1 {
+            i += 1;
+            end = changed[i];
+        }
+        let end = (end + context + 1).min(ops.len());
+        i += 1;
+
+        let (old_start, new_start) = positions(&ops[..start]);
+        let hunk = &ops[start..end];
+        let old_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, Op::Insert(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, Op::Delete(_)))
+            .count();
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_count),
+            range(new_start, new_count)
+        ));
+        for op in hunk {
+            let (prefix, line) = match op {
+                Op::Equal(line) => (' ', line),
+                Op::Delete(line) => ('-', line),
+                Op::Insert(line) => ('+', line),
+            };
+            out.push(prefix);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// `start,count` in unified diff notation (1-based; an empty range points at
+/// the line before it).
+fn range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, count),
+    }
+}
+
+/// Old and new line offsets reached after `ops`.
+fn positions(ops: &[Op]) -> (usize, usize) {
+    ops.iter().fold((0, 0), |(old, new), op| match op {
+        Op::Equal(_) => (old + 1, new + 1),
+        Op::Delete(_) => (old + 1, new),
+        Op::Insert(_) => (old, new + 1),
+    })
+}
+
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    // Common prefix and suffix keep the LCS table small for typical edits.
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<Op> = old[..prefix].iter().map(|line| Op::Equal(line)).collect();
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_LCS_CELLS {
+        ops.extend(old_mid.iter().map(|line| Op::Delete(line)));
+        ops.extend(new_mid.iter().map(|line| Op::Insert(line)));
+    } else {
+        ops.extend(lcs_ops(old_mid, new_mid));
+    }
+    ops.extend(old[old.len() - suffix..].iter().map(|line| Op::Equal(line)));
+    ops
+}
+
+fn lcs_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let (n, m) = (old.len(), new.len());
+    // lengths[i][j]: LCS length of old[i..] and new[j..].
+    let mut lengths = vec![0u32; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[at(i, j)] = if old[i] == new[j] {
+                lengths[at(i + 1, j + 1)] + 1
+            } else {
+                lengths[at(i + 1, j)].max(lengths[at(i, j + 1)])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(Op::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[at(i + 1, j)] >= lengths[at(i, j + 1)] {
+            ops.push(Op::Delete(old[i]));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|line| Op::Delete(line)));
+    ops.extend(new[j..].iter().map(|line| Op::Insert(line)));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line_change_keeps_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng";
+        let new = "a\nb\nc\nD\ne\nf\ng";
+        assert_eq!(
+            unified_diff(old, new, 2),
+            "@@ -2,5 +2,5 @@\n b\n c\n-d\n+D\n e\n f\n"
+        );
+    }
+
+    #[test]
+    fn distant_changes_get_separate_hunks() {
+        let lines: Vec<String> = (1..=20).map(|n| n.to_string()).collect();
+        let old = lines.join("\n");
+        let mut edited = lines.clone();
+        edited[1] = "two".to_string();
+        edited[18] = "nineteen".to_string();
+        let new = edited.join("\n");
+        let diff = unified_diff(&old, &new, 1);
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("-19\n+nineteen\n"));
+    }
+
+    #[test]
+    fn new_file_is_all_insertions() {
+        assert_eq!(unified_diff("", "x\ny", 3), "@@ -0,0 +1,2 @@\n+x\n+y\n");
+        assert_eq!(unified_diff("same", "same", 3), "");
+    }
+}
@@ -0,0 +1,192 @@
+use regex::Regex;
+use std::env;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExclusionError {
+    #[error("Exclusion pattern is empty")]
+    Empty,
+    #[error("Pattern '{0}' would exclude every session")]
+    MatchesEverything(String),
+}
+
+/// A path glob from the exclusion list.
+///
+/// `*` and `?` stay within one path segment, `**` crosses segments and a
+/// leading `~` expands to the home directory. Absolute patterns are anchored
+/// at the filesystem root; relative ones may match at any depth. A pattern
+/// matching a directory also matches everything below it.
+#[derive(Debug, Clone)]
+struct PathGlob {
+    regex: Regex,
+}
+
+impl PathGlob {
+    fn new(pattern: &str) -> Result<Self, ExclusionError> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err(ExclusionError::Empty);
+        }
+        if pattern.chars().all(|c| matches!(c, '*' | '?' | '/')) {
+            return Err(ExclusionError::MatchesEverything(pattern.to_string()));
+        }
+
+        let expanded = expand_home(pattern);
+        let expanded = expanded.trim_end_matches('/');
+
+        let mut source = String::from("^");
+        if !expanded.starts_with('/') {
+            source.push_str("(?:.*/)?");
+        }
+        source.push_str(&glob_to_regex(expanded));
+        source.push_str("(?:/.*)?$");
+
+        let regex = Regex::new(&source).expect("escaped glob must compile");
+        Ok(Self { regex })
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        self.regex.is_match(path.trim_end_matches('/'))
+    }
+}
+
+/// `pattern` with a leading `~` replaced by the home directory.
+pub(crate) fn expand_home(pattern: &str) -> String {
+    if (pattern == "~" || pattern.starts_with("~/"))
+        && let Ok(home) = env::var("HOME")
+    {
+        return format!("{}{}", home.trim_end_matches('/'), &pattern[1..]);
+    }
+    pattern.to_string()
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut source = String::new();
+    let mut chars = glob.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    source.push_str("(?:.*/)?");
+                } else {
+                    source.push_str(".*");
+                }
+            }
+            '*' => source.push_str("[^/]*"),
+            '?' => source.push_str("[^/]"),
+            _ => source.push_str(&regex::escape(&ch.to_string())),
+        }
+    }
+
+    source
+}
+
+/// Path globs whose sessions must never be indexed.
+///
+/// Rules apply to both a session's project directory and its source file.
+#[derive(Debug, Clone, Default)]
+pub struct ExclusionRules {
+    globs: Vec<PathGlob>,
+}
+
+impl ExclusionRules {
+    /// Compile `patterns`, skipping (and logging) invalid entries.
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        let globs = patterns
+            .iter()
+            .filter_map(|pattern| match PathGlob::new(pattern.as_ref()) {
+                Ok(glob) => Some(glob),
+                Err(err) => {
+                    tracing::warn!("Ignoring exclusion pattern: {}", err);
+                    None
+                }
+            })
+            .collect();
+
+        Self { globs }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.globs.is_empty()
+    }
+
+    /// Whether `path` falls under any exclusion rule.
+    pub fn matches(&self, path: &str) -> bool {
+        self.globs.iter().any(|glob| glob.matches(path))
+    }
+
+    /// Whether a session with this project directory and source file is excluded.
+    pub fn excludes_session(&self, project_path: Option<&str>, file_path: &str) -> bool {
+        project_path.is_some_and(|path| self.matches(path)) || self.matches(file_path)
+    }
+}
+
+/// Check a single pattern before it is added to the exclusion list.
+pub fn validate_pattern(pattern: &str) -> Result<(), ExclusionError> {
+    PathGlob::new(pattern).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute_pattern_matches_directory_and_descendants() {
+        let rules = ExclusionRules::new(&["/home/user/clients/acme"]);
+        assert!(rules.matches("/home/user/clients/acme"));
+        assert!(rules.matches("/home/user/clients/acme/"));
+        assert!(rules.matches("/home/user/clients/acme/src/main.rs"));
+        assert!(!rules.matches("/home/user/clients/acme-other"));
+        assert!(!rules.matches("/srv/home/user/clients/acme"));
+    }
+
+    #[test]
+    fn single_star_stays_within_segment() {
+        let rules = ExclusionRules::new(&["/home/*/notes"]);
+        assert!(rules.matches("/home/user/notes"));
+        assert!(!rules.matches("/home/user/work/notes"));
+    }
+
+    #[test]
+    fn double_star_crosses_segments() {
+        let rules = ExclusionRules::new(&["/home/**/secret-*"]);
+        assert!(rules.matches("/home/secret-project"));
+        assert!(rules.matches("/home/user/work/secret-project/file.jsonl"));
+        assert!(!rules.matches("/home/user/public"));
+    }
+
+    #[test]
+    fn relative_pattern_matches_at_any_depth() {
+        let rules = ExclusionRules::new(&["client-?"]);
+        assert!(rules.matches("/home/user/client-a"));
+        assert!(rules.matches("/home/user/client-b/notes"));
+        assert!(!rules.matches("/home/user/client-ab"));
+        assert!(!rules.matches("/home/user/myclient-a"));
+    }
+
+    #[test]
+    fn excludes_session_checks_project_and_file_paths() {
+        let rules = ExclusionRules::new(&["/work/private"]);
+        assert!(rules.excludes_session(Some("/work/private"), "/tmp/a.jsonl"));
+        assert!(rules.excludes_session(None, "/work/private/.claude/a.jsonl"));
+        assert!(!rules.excludes_session(Some("/work/public"), "/tmp/a.jsonl"));
+    }
+
+    #[test]
+    fn validate_pattern_rejects_empty_and_catch_all() {
+        assert!(matches!(validate_pattern("  "), Err(ExclusionError::Empty)));
+        assert!(matches!(
+            validate_pattern("/**"),
+            Err(ExclusionError::MatchesEverything(_))
+        ));
+        assert!(validate_pattern("~/notes").is_ok());
+    }
+
+    #[test]
+    fn invalid_patterns_are_skipped() {
+        let rules = ExclusionRules::new(&["", "**"]);
+        assert!(rules.is_empty());
+    }
+}
@@ -1 +1,6 @@
+pub mod command_template;
+pub mod diff;
+pub mod exclusions;
+pub mod git;
+pub mod multiplexer;
 pub mod terminal;
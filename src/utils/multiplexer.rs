@@ -0,0 +1,299 @@
+use anyhow::{Context, Result, bail};
+use std::env;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+use crate::models::Session;
+use crate::utils::command_template::shell_quote;
+use crate::utils::terminal::{host_command, is_installed};
+
+/// Longest window or tab name taken from a session title.
+const NAME_MAX_CHARS: usize = 30;
+
+/// Where to resume sessions before falling back to a terminal window: a new
+/// window or tab in a multiplexer server that is already running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplexer {
+    Off,
+    /// tmux if a server is running, else zellij.
+    Auto,
+    Tmux,
+    Zellij,
+}
+
+impl Multiplexer {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Multiplexer::Off => "off",
+            Multiplexer::Auto => "auto",
+            Multiplexer::Tmux => "tmux",
+            Multiplexer::Zellij => "zellij",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Multiplexer::Off => "Off",
+            Multiplexer::Auto => "Automatic",
+            Multiplexer::Tmux => "tmux",
+            Multiplexer::Zellij => "zellij",
+        }
+    }
+
+    /// Run `args` in a new window or tab named `name`, in `workdir`.
+    ///
+    /// Returns the multiplexer used, or `None` when none is enabled or has a
+    /// server running, leaving the caller to open a terminal instead.
+    pub fn open(self, name: &str, workdir: &Path, args: &[String]) -> Result<Option<Self>> {
+        let candidates: &[Multiplexer] = match self {
+            Multiplexer::Off => &[],
+            Multiplexer::Auto => &[Multiplexer::Tmux, Multiplexer::Zellij],
+            Multiplexer::Tmux => &[Multiplexer::Tmux],
+            Multiplexer::Zellij => &[Multiplexer::Zellij],
+        };
+
+        for multiplexer in candidates {
+            match multiplexer {
+                Multiplexer::Tmux if tmux_running() => {
+                    run(host_command("tmux").args(tmux_args(name, workdir, args)))?;
+                    return Ok(Some(Multiplexer::Tmux));
+                }
+                Multiplexer::Zellij => {
+                    if let Some(zellij_session) = zellij_session() {
+                        for zellij_args in zellij_args(&zellij_session, name, workdir, args) {
+                            run(host_command("zellij").args(zellij_args))?;
+                        }
+                        return Ok(Some(Multiplexer::Zellij));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl FromStr for Multiplexer {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Multiplexer::Off),
+            "auto" => Ok(Multiplexer::Auto),
+            "tmux" => Ok(Multiplexer::Tmux),
+            "zellij" => Ok(Multiplexer::Zellij),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Window or tab name for `session`: the start of its title, or of its id.
+pub fn window_name(session: &Session) -> String {
+    let title = session
+        .display_title()
+        .and_then(|title| title.lines().next())
+        .unwrap_or(&session.id);
+    let name: String = title.chars().take(NAME_MAX_CHARS).collect();
+    if name.len() < title.len() {
+        format!("{}…", name.trim_end())
+    } else {
+        name
+    }
+}
+
+fn tmux_running() -> bool {
+    // Inside tmux the client's own server is the one to use.
+    if env::var_os("TMUX").is_some() {
+        return true;
+    }
+    is_installed("tmux") && succeeds(host_command("tmux").arg("has-session"))
+}
+
+/// tmux runs trailing arguments as the window's command without a shell.
+fn tmux_args(name: &str, workdir: &Path, args: &[String]) -> Vec<String> {
+    let mut tmux_args = vec![
+        "new-window".to_string(),
+        "-n".to_string(),
+        name.to_string(),
+        "-c".to_string(),
+        workdir.to_string_lossy().to_string(),
+    ];
+    tmux_args.extend(args.iter().cloned());
+    tmux_args
+}
+
+/// The zellij session to open the tab in: the one we run inside, or else
+/// the first one still running.
+fn zellij_session() -> Option<String> {
+    if let Ok(name) = env::var("ZELLIJ_SESSION_NAME") {
+        return Some(name);
+    }
+    if !is_installed("zellij") {
+        return None;
+    }
+    let output = host_command("zellij")
+        .args(["list-sessions", "--no-formatting"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    first_running_zellij_session(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// First session in `zellij list-sessions` output that has not exited.
+/// Each line is the session name followed by its details, such as
+/// `main [Created 2h ago] (current)`; exited sessions are kept for
+/// resurrection and marked `EXITED`.
+fn first_running_zellij_session(list: &str) -> Option<String> {
+    list.lines()
+        .filter(|line| !line.contains("EXITED"))
+        .find_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+}
+
+/// zellij cannot start a tab on a command, so the tab is opened on the
+/// default shell and the command typed into it.
+fn zellij_args(session: &str, name: &str, workdir: &Path, args: &[String]) -> [Vec<String>; 2] {
+    let command = args
+        .iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let action = |action: &[&str]| {
+        ["--session", session, "action"]
+            .iter()
+            .chain(action)
+            .map(|arg| arg.to_string())
+            .collect()
+    };
+    [
+        action(&[
+            "new-tab",
+            "--name",
+            name,
+            "--cwd",
+            &workdir.to_string_lossy(),
+        ]),
+        action(&["write-chars", &format!("{}\n", command)]),
+    ]
+}
+
+fn succeeds(command: &mut Command) -> bool {
+    command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let output = command
+        .stdout(Stdio::null())
+        .output()
+        .context("Failed to run the multiplexer")?;
+    if !output.status.success() {
+        bail!(
+            "Multiplexer command failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resume_args() -> Vec<String> {
+        [
+            "bash",
+            "-lc",
+            "cd \"$1\" && claude -r \"$2\"",
+            "--",
+            "/work/my app",
+            "abc",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_multiplexer_from_str() {
+        for multiplexer in [
+            Multiplexer::Off,
+            Multiplexer::Auto,
+            Multiplexer::Tmux,
+            Multiplexer::Zellij,
+        ] {
+            assert_eq!(Multiplexer::from_str(multiplexer.to_str()), Ok(multiplexer));
+        }
+        assert_eq!(Multiplexer::from_str("screen"), Err(()));
+    }
+
+    #[test]
+    fn test_tmux_args_pass_the_resume_command_as_argv() {
+        let args = tmux_args("fix tests", Path::new("/work/my app"), &resume_args());
+        assert_eq!(
+            args[..5],
+            ["new-window", "-n", "fix tests", "-c", "/work/my app"]
+        );
+        assert_eq!(args[5..], resume_args()[..]);
+    }
+
+    #[test]
+    fn test_zellij_args_type_the_quoted_resume_command() {
+        let [new_tab, write] = zellij_args("main", "fix tests", Path::new("/work"), &resume_args());
+        assert_eq!(
+            new_tab,
+            [
+                "--session",
+                "main",
+                "action",
+                "new-tab",
+                "--name",
+                "fix tests",
+                "--cwd",
+                "/work"
+            ]
+        );
+        assert_eq!(
+            write,
+            [
+                "--session",
+                "main",
+                "action",
+                "write-chars",
+                "bash -lc 'cd \"$1\" && claude -r \"$2\"' -- '/work/my app' abc\n"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_first_running_zellij_session_skips_exited_sessions() {
+        let list = "\
+old-work [Created 3days ago] (EXITED - attach to resurrect)
+main [Created 2h ago] (current)
+scratch [Created 10m ago]
+";
+        assert_eq!(first_running_zellij_session(list).as_deref(), Some("main"));
+        assert_eq!(
+            first_running_zellij_session("gone [Created 1h ago] (EXITED - attach to resurrect)\n"),
+            None
+        );
+        assert_eq!(first_running_zellij_session(""), None);
+    }
+
+    #[test]
+    fn test_off_never_opens_a_window() {
+        assert_eq!(
+            Multiplexer::Off
+                .open("name", Path::new("/"), &resume_args())
+                .unwrap(),
+            None
+        );
+    }
+}
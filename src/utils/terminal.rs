@@ -1,7 +1,11 @@
-use crate::models::Tool;
+use crate::models::{Session, Tool};
+use crate::utils::command_template::{
+    CommandTemplate, TemplateContext, TemplateError, shell_quote,
+};
+use crate::utils::multiplexer::{Multiplexer, window_name};
 use anyhow::{Context, Result};
 use std::env;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
 
@@ -9,6 +13,34 @@ fn is_flatpak() -> bool {
     Path::new("/.flatpak-info").exists() || env::var("FLATPAK_ID").is_ok()
 }
 
+/// Whether `exe` can be run on the host, looked up through `flatpak-spawn`
+/// inside the sandbox.
+pub(crate) fn is_installed(exe: &str) -> bool {
+    if is_flatpak() {
+        Command::new("flatpak-spawn")
+            .arg("--host")
+            .arg("which")
+            .arg(exe)
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    } else {
+        which::which(exe).is_ok()
+    }
+}
+
+/// `program` as a command on the host, through `flatpak-spawn` inside the
+/// sandbox.
+pub(crate) fn host_command(program: &str) -> Command {
+    if is_flatpak() {
+        let mut cmd = Command::new("flatpak-spawn");
+        cmd.arg("--host").arg(program);
+        cmd
+    } else {
+        Command::new(program)
+    }
+}
+
 /// Error type for terminal spawning operations
 #[derive(Debug)]
 pub enum TerminalSpawnError {
@@ -16,6 +48,8 @@ pub enum TerminalSpawnError {
     NoTerminalFound,
     /// The specified terminal is not available
     NotAvailable(String),
+    /// The custom terminal command template is invalid
+    InvalidTemplate(TemplateError),
     /// Other error occurred during terminal spawn
     Other(anyhow::Error),
 }
@@ -25,6 +59,9 @@ impl std::fmt::Display for TerminalSpawnError {
         match self {
             TerminalSpawnError::NoTerminalFound => write!(f, "No terminal emulator found"),
             TerminalSpawnError::NotAvailable(name) => write!(f, "{} is not available", name),
+            TerminalSpawnError::InvalidTemplate(err) => {
+                write!(f, "Invalid custom terminal command: {}", err)
+            }
             TerminalSpawnError::Other(err) => write!(f, "{}", err),
         }
     }
@@ -34,6 +71,7 @@ impl std::error::Error for TerminalSpawnError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             TerminalSpawnError::Other(err) => Some(err.as_ref()),
+            TerminalSpawnError::InvalidTemplate(err) => Some(err),
             _ => None,
         }
     }
@@ -44,11 +82,15 @@ impl TerminalSpawnError {
     pub fn should_show_preferences(&self) -> bool {
         matches!(
             self,
-            TerminalSpawnError::NoTerminalFound | TerminalSpawnError::NotAvailable(_)
+            TerminalSpawnError::NoTerminalFound
+                | TerminalSpawnError::NotAvailable(_)
+                | TerminalSpawnError::InvalidTemplate(_)
         )
     }
 }
 
+// `GnomeTerminal` is named after the emulator, not the enum.
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Terminal {
     Auto,
@@ -57,6 +99,15 @@ pub enum Terminal {
     Foot,
     Alacritty,
     Kitty,
+    GnomeConsole,
+    GnomeTerminal,
+    Konsole,
+    WezTerm,
+    Tilix,
+    Xterm,
+    /// The user's own command template, from the `resume-terminal-command`
+    /// setting.
+    Custom,
 }
 
 impl Terminal {
@@ -66,6 +117,13 @@ impl Terminal {
         Terminal::Foot,
         Terminal::Alacritty,
         Terminal::Kitty,
+        Terminal::GnomeConsole,
+        Terminal::GnomeTerminal,
+        Terminal::Konsole,
+        Terminal::WezTerm,
+        Terminal::Tilix,
+        // Last: present on many machines, but the least pleasant to use.
+        Terminal::Xterm,
     ];
 
     pub fn to_str(self) -> &'static str {
@@ -76,6 +134,13 @@ impl Terminal {
             Terminal::Foot => "foot",
             Terminal::Alacritty => "alacritty",
             Terminal::Kitty => "kitty",
+            Terminal::GnomeConsole => "kgx",
+            Terminal::GnomeTerminal => "gnome-terminal",
+            Terminal::Konsole => "konsole",
+            Terminal::WezTerm => "wezterm",
+            Terminal::Tilix => "tilix",
+            Terminal::Xterm => "xterm",
+            Terminal::Custom => "custom",
         }
     }
 
@@ -87,37 +152,35 @@ impl Terminal {
             Terminal::Foot => "Foot",
             Terminal::Alacritty => "Alacritty",
             Terminal::Kitty => "Kitty",
+            Terminal::GnomeConsole => "GNOME Console",
+            Terminal::GnomeTerminal => "GNOME Terminal",
+            Terminal::Konsole => "Konsole",
+            Terminal::WezTerm => "WezTerm",
+            Terminal::Tilix => "Tilix",
+            Terminal::Xterm => "xterm",
+            Terminal::Custom => "Custom Command",
         }
     }
 
     pub fn executable(&self) -> Option<&'static str> {
         match self {
-            Terminal::Auto => None,
+            Terminal::Auto | Terminal::Custom => None,
             Terminal::Ptyxis => Some("ptyxis"),
             Terminal::Ghostty => Some("ghostty"),
             Terminal::Foot => Some("foot"),
             Terminal::Alacritty => Some("alacritty"),
             Terminal::Kitty => Some("kitty"),
+            Terminal::GnomeConsole => Some("kgx"),
+            Terminal::GnomeTerminal => Some("gnome-terminal"),
+            Terminal::Konsole => Some("konsole"),
+            Terminal::WezTerm => Some("wezterm"),
+            Terminal::Tilix => Some("tilix"),
+            Terminal::Xterm => Some("xterm"),
         }
     }
 
     fn is_available(&self) -> bool {
-        match self.executable() {
-            Some(exe) => {
-                if is_flatpak() {
-                    Command::new("flatpak-spawn")
-                        .arg("--host")
-                        .arg("which")
-                        .arg(exe)
-                        .status()
-                        .map(|status| status.success())
-                        .unwrap_or(false)
-                } else {
-                    which::which(exe).is_ok()
-                }
-            }
-            None => false,
-        }
+        self.executable().is_some_and(is_installed)
     }
 
     pub fn resolve_auto(&self) -> Result<Self, TerminalSpawnError> {
@@ -133,6 +196,38 @@ impl Terminal {
 
         Err(TerminalSpawnError::NoTerminalFound)
     }
+
+    /// Arguments that make this terminal run `args` as its command.
+    fn command_args(&self, args: &[String]) -> Vec<String> {
+        // Each terminal has different syntax for specifying the command to run
+        let prefix: &[&str] = match self {
+            Terminal::Ghostty
+            | Terminal::Alacritty
+            | Terminal::Kitty
+            | Terminal::Konsole
+            | Terminal::Xterm => &["-e"],
+            Terminal::Ptyxis | Terminal::GnomeConsole | Terminal::GnomeTerminal => &["--"],
+            Terminal::WezTerm => &["start", "--"],
+            // Tilix parses its command from a single shell-quoted string.
+            Terminal::Tilix => {
+                let command = args
+                    .iter()
+                    .map(|arg| shell_quote(arg))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                return vec!["-e".to_string(), command];
+            }
+            // Foot takes the command directly without a separator
+            Terminal::Foot => &[],
+            Terminal::Auto => unreachable!("Auto should be resolved"),
+            Terminal::Custom => unreachable!("Custom terminals are run from their template"),
+        };
+        prefix
+            .iter()
+            .map(|arg| arg.to_string())
+            .chain(args.iter().cloned())
+            .collect()
+    }
 }
 
 impl FromStr for Terminal {
@@ -146,12 +241,51 @@ impl FromStr for Terminal {
             "foot" => Ok(Terminal::Foot),
             "alacritty" => Ok(Terminal::Alacritty),
             "kitty" => Ok(Terminal::Kitty),
+            "kgx" => Ok(Terminal::GnomeConsole),
+            "gnome-terminal" => Ok(Terminal::GnomeTerminal),
+            "konsole" => Ok(Terminal::Konsole),
+            "wezterm" => Ok(Terminal::WezTerm),
+            "tilix" => Ok(Terminal::Tilix),
+            "xterm" => Ok(Terminal::Xterm),
+            "custom" => Ok(Terminal::Custom),
             _ => Err(()),
         }
     }
 }
 
+/// Directory to resume a session in: its project, or else the directory
+/// holding its session file.
+pub fn resume_workdir(session: &Session) -> Option<PathBuf> {
+    match &session.project_path {
+        Some(project_path) => Some(PathBuf::from(project_path)),
+        None => Path::new(&session.file_path)
+            .parent()
+            .map(Path::to_path_buf),
+    }
+}
+
+/// Resume command for a new terminal window, which is left on a shell once
+/// the tool exits.
 pub fn build_resume_command(tool: Tool, session_id: &str, workdir: &Path) -> Result<Vec<String>> {
+    resume_command(tool, session_id, workdir, true)
+}
+
+/// Resume command for the terminal we run in, which returns to the caller
+/// once the tool exits.
+pub fn build_in_place_resume_command(
+    tool: Tool,
+    session_id: &str,
+    workdir: &Path,
+) -> Result<Vec<String>> {
+    resume_command(tool, session_id, workdir, false)
+}
+
+fn resume_command(
+    tool: Tool,
+    session_id: &str,
+    workdir: &Path,
+    keep_shell: bool,
+) -> Result<Vec<String>> {
     let workdir = workdir
         .canonicalize()
         .context("Failed to canonicalize workdir")?;
@@ -163,7 +297,10 @@ pub fn build_resume_command(tool: Tool, session_id: &str, workdir: &Path) -> Res
         Tool::MistralVibe => "vibe --resume \"$2\"".to_string(),
     };
 
-    let shell_cmd = format!("cd \"$1\" && {}; exec bash", tool_cmd);
+    let mut shell_cmd = format!("cd \"$1\" && {}", tool_cmd);
+    if keep_shell {
+        shell_cmd.push_str("; exec bash");
+    }
 
     Ok(vec![
         "bash".to_string(),
@@ -188,39 +325,75 @@ pub fn spawn_terminal(terminal: Terminal, args: &[String]) -> Result<(), Termina
         .executable()
         .ok_or_else(|| TerminalSpawnError::Other(anyhow::anyhow!("Terminal has no executable")))?;
 
-    let mut command = if is_flatpak() {
-        let mut cmd = Command::new("flatpak-spawn");
-        cmd.arg("--host").arg(executable);
-        cmd
-    } else {
-        Command::new(executable)
-    };
+    let mut command = host_command(executable);
+    command.args(resolved.command_args(args));
 
-    let mut final_args: Vec<String> = Vec::new();
+    command.spawn().map_err(|e| {
+        TerminalSpawnError::Other(
+            anyhow::Error::from(e).context("Failed to spawn terminal process"),
+        )
+    })?;
 
-    // Each terminal has different syntax for specifying the command to run
-    match resolved {
-        Terminal::Ghostty | Terminal::Alacritty | Terminal::Kitty => {
-            final_args.push("-e".to_string());
-        }
-        Terminal::Ptyxis => {
-            final_args.push("--".to_string());
-        }
-        Terminal::Foot => {
-            // Foot takes the command directly without a separator
-        }
-        Terminal::Auto => unreachable!("Auto should be resolved"),
-    }
+    Ok(())
+}
 
-    final_args.extend(args.iter().cloned());
+/// Run the resume command `args` in the current terminal, from a
+/// command-line mode, and wait for it to exit.
+pub fn run_in_current_terminal(args: &[String]) -> Result<()> {
+    let (program, args) = args.split_first().context("Empty resume command")?;
+    Command::new(program)
+        .args(args)
+        .status()
+        .with_context(|| format!("Failed to run {}", program))?;
+    Ok(())
+}
 
-    for arg in &final_args {
-        command.arg(arg);
+/// Resume `session` from a command-line mode: in a new window of
+/// `multiplexer` when it has a server running, else through `in_place` in
+/// this terminal, which is handed a command that exits with the tool.
+///
+/// Returns the multiplexer the session was opened in.
+pub fn resume_from_terminal(
+    session: &Session,
+    multiplexer: Multiplexer,
+    in_place: impl FnOnce(&[String]) -> Result<()>,
+) -> Result<Option<Multiplexer>> {
+    let workdir = resume_workdir(session).context("No directory to resume the session in")?;
+    let args = build_resume_command(session.tool, &session.id, &workdir)?;
+
+    match multiplexer.open(&window_name(session), &workdir, &args) {
+        Ok(Some(used)) => return Ok(Some(used)),
+        Ok(None) => {}
+        Err(err) => tracing::warn!(
+            "Failed to resume in {}, running here instead: {:#}",
+            multiplexer.display_name(),
+            err
+        ),
     }
+    let args = build_in_place_resume_command(session.tool, &session.id, &workdir)?;
+    in_place(&args)?;
+    Ok(None)
+}
 
-    command.spawn().map_err(|e| {
+/// Run the custom terminal command `template` with its placeholders filled
+/// in from `context`.
+pub fn spawn_custom_terminal(
+    template: &str,
+    context: &TemplateContext,
+) -> Result<(), TerminalSpawnError> {
+    let template = CommandTemplate::parse(template).map_err(TerminalSpawnError::InvalidTemplate)?;
+    let argv = template.expand(context);
+    let (program, args) = argv
+        .split_first()
+        .expect("parsed templates have at least one word");
+
+    if !is_installed(program) {
+        return Err(TerminalSpawnError::NotAvailable(program.clone()));
+    }
+
+    host_command(program).args(args).spawn().map_err(|e| {
         TerminalSpawnError::Other(
-            anyhow::Error::from(e).context("Failed to spawn terminal process"),
+            anyhow::Error::from(e).context("Failed to spawn custom terminal command"),
         )
     })?;
 
@@ -240,6 +413,16 @@ mod tests {
         assert_eq!(Terminal::from_str("foot"), Ok(Terminal::Foot));
         assert_eq!(Terminal::from_str("alacritty"), Ok(Terminal::Alacritty));
         assert_eq!(Terminal::from_str("kitty"), Ok(Terminal::Kitty));
+        assert_eq!(Terminal::from_str("kgx"), Ok(Terminal::GnomeConsole));
+        assert_eq!(
+            Terminal::from_str("gnome-terminal"),
+            Ok(Terminal::GnomeTerminal)
+        );
+        assert_eq!(Terminal::from_str("konsole"), Ok(Terminal::Konsole));
+        assert_eq!(Terminal::from_str("wezterm"), Ok(Terminal::WezTerm));
+        assert_eq!(Terminal::from_str("tilix"), Ok(Terminal::Tilix));
+        assert_eq!(Terminal::from_str("xterm"), Ok(Terminal::Xterm));
+        assert_eq!(Terminal::from_str("custom"), Ok(Terminal::Custom));
         assert_eq!(Terminal::from_str("invalid"), Err(()));
     }
 
@@ -251,6 +434,38 @@ mod tests {
         assert_eq!(Terminal::Foot.to_str(), "foot");
         assert_eq!(Terminal::Alacritty.to_str(), "alacritty");
         assert_eq!(Terminal::Kitty.to_str(), "kitty");
+        assert_eq!(Terminal::GnomeConsole.to_str(), "kgx");
+        assert_eq!(Terminal::GnomeTerminal.to_str(), "gnome-terminal");
+        assert_eq!(Terminal::Konsole.to_str(), "konsole");
+        assert_eq!(Terminal::WezTerm.to_str(), "wezterm");
+        assert_eq!(Terminal::Tilix.to_str(), "tilix");
+        assert_eq!(Terminal::Xterm.to_str(), "xterm");
+        assert_eq!(Terminal::Custom.to_str(), "custom");
+    }
+
+    #[test]
+    fn test_terminal_command_args() {
+        let args: Vec<String> = [
+            "bash",
+            "-lc",
+            "cd \"$1\" && claude",
+            "--",
+            "/tmp/my project",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+
+        assert_eq!(Terminal::Foot.command_args(&args), args);
+        assert_eq!(Terminal::Xterm.command_args(&args)[0], "-e");
+        assert_eq!(Terminal::Konsole.command_args(&args)[1..], args[..]);
+        assert_eq!(Terminal::GnomeConsole.command_args(&args)[0], "--");
+        assert_eq!(Terminal::GnomeTerminal.command_args(&args)[0], "--");
+        assert_eq!(Terminal::WezTerm.command_args(&args)[..2], ["start", "--"]);
+        assert_eq!(
+            Terminal::Tilix.command_args(&args),
+            vec!["-e", "bash -lc 'cd \"$1\" && claude' -- '/tmp/my project'"]
+        );
     }
 
     #[test]
@@ -272,6 +487,18 @@ mod tests {
         assert_eq!(cmd[5], "test-session-id");
     }
 
+    #[test]
+    fn test_build_in_place_resume_command_does_not_start_a_shell() {
+        let project_dir = std::env::temp_dir();
+
+        let window = build_resume_command(Tool::ClaudeCode, "id", &project_dir).unwrap();
+        assert!(window[2].ends_with("; exec bash"));
+
+        let in_place = build_in_place_resume_command(Tool::ClaudeCode, "id", &project_dir).unwrap();
+        assert_eq!(in_place[2], "cd \"$1\" && claude -r \"$2\"");
+        assert_eq!(in_place[3..], window[3..]);
+    }
+
     #[test]
     fn test_build_resume_command_opencode() {
         let temp_dir = std::env::temp_dir();
@@ -349,6 +576,9 @@ mod tests {
         let err = TerminalSpawnError::NotAvailable("Ptyxis".to_string());
         assert!(err.should_show_preferences());
 
+        let err = TerminalSpawnError::InvalidTemplate(TemplateError::Empty);
+        assert!(err.should_show_preferences());
+
         let err = TerminalSpawnError::Other(anyhow::anyhow!("Custom error"));
         assert!(!err.should_show_preferences());
     }
@@ -0,0 +1,264 @@
+use relm4::gtk::gio::{self, prelude::*};
+use relm4::gtk::glib::{self, Variant};
+use rusqlite::Connection;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sessions_chronicle::database::schema::initialize_database;
+use sessions_chronicle::dbus_api::{INTERFACE, OBJECT_PATH, SessionsApi, emit_sessions_changed};
+
+struct TempDatabase {
+    path: PathBuf,
+}
+
+impl TempDatabase {
+    fn new() -> Self {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        path.push(format!(
+            "sessions-chronicle-test-dbus-api-{}-{}.db",
+            std::process::id(),
+            nanos
+        ));
+        let connection = Connection::open(&path).expect("Failed to open temp database");
+        initialize_database(&connection).expect("Failed to initialize database");
+        connection
+            .execute_batch(
+                "INSERT INTO sessions (id, tool, project_path, start_time, message_count, file_path, last_updated, first_prompt)
+                 VALUES ('session-a', 'claude_code', '/projects/alpha', 10, 1, '/tmp/session-a.jsonl', 30, 'migrate the indexer to sqlite'),
+                        ('session-b', 'opencode', '/projects/beta', 20, 1, '/tmp/session-b.jsonl', 40, 'fix the flaky tests');
+                 INSERT INTO messages (session_id, message_index, role, content, timestamp)
+                 VALUES ('session-a', 0, 'user', 'migrate the indexer to sqlite', 10),
+                        ('session-a', 1, 'assistant', 'the indexer now writes to sqlite', 11),
+                        ('session-a', 2, 'user', 'thanks', 12),
+                        ('session-b', 0, 'user', 'fix the flaky indexer tests', 20);",
+            )
+            .expect("Failed to seed database");
+
+        Self { path }
+    }
+}
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn connect(address: &str) -> gio::DBusConnection {
+    gio::DBusConnection::for_address_sync(
+        address,
+        gio::DBusConnectionFlags::AUTHENTICATION_CLIENT
+            | gio::DBusConnectionFlags::MESSAGE_BUS_CONNECTION,
+        None,
+        gio::Cancellable::NONE,
+    )
+    .expect("Failed to connect to the test bus")
+}
+
+fn call(
+    client: &gio::DBusConnection,
+    service: &str,
+    method: &str,
+    parameters: Variant,
+    reply_type: &str,
+) -> Result<Variant, glib::Error> {
+    let reply_type = glib::VariantTy::new(reply_type).expect("Invalid reply type");
+    glib::MainContext::ref_thread_default().block_on(client.call_future(
+        Some(service),
+        OBJECT_PATH,
+        INTERFACE,
+        method,
+        Some(&parameters),
+        Some(reply_type),
+        gio::DBusCallFlags::NONE,
+        5000,
+    ))
+}
+
+/// Ids of `sessions`, sorted: search results are ordered by relevance.
+fn ids(sessions: &[HashMap<String, Variant>]) -> Vec<String> {
+    let mut ids: Vec<_> = sessions
+        .iter()
+        .map(|session| session["id"].get::<String>().unwrap())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn dbus_api_answers_on_a_private_bus() {
+    let db = TempDatabase::new();
+    let context = glib::MainContext::new();
+    context
+        .with_thread_default(|| {
+            let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+            bus.up();
+            let address = bus.bus_address().expect("Test bus has no address");
+
+            let resumed = Rc::new(RefCell::new(Vec::new()));
+            let api = SessionsApi::new(db.path.clone(), {
+                let resumed = resumed.clone();
+                move |session| resumed.borrow_mut().push(session.id.clone())
+            });
+            let server = connect(&address);
+            let _registration = api.register(&server).expect("Failed to export the API");
+            let service = server.unique_name().expect("Server has no unique name");
+            let client = connect(&address);
+
+            let no_filters = HashMap::<String, Variant>::new();
+            let reply = call(
+                &client,
+                &service,
+                "Search",
+                ("indexer".to_string(), no_filters).to_variant(),
+                "(aa{sv})",
+            )
+            .expect("Search failed");
+            let (sessions,) = reply
+                .get::<(Vec<HashMap<String, Variant>>,)>()
+                .expect("Invalid sessions");
+            assert_eq!(ids(&sessions), vec!["session-a", "session-b"]);
+
+            let filters = HashMap::from([(
+                "tools".to_string(),
+                vec!["opencode".to_string()].to_variant(),
+            )]);
+            let reply = call(
+                &client,
+                &service,
+                "Search",
+                ("indexer".to_string(), filters).to_variant(),
+                "(aa{sv})",
+            )
+            .expect("Filtered search failed");
+            let (sessions,) = reply
+                .get::<(Vec<HashMap<String, Variant>>,)>()
+                .expect("Invalid sessions");
+            assert_eq!(ids(&sessions), vec!["session-b"]);
+
+            let filters = HashMap::from([("color".to_string(), "red".to_variant())]);
+            let err = call(
+                &client,
+                &service,
+                "Search",
+                ("indexer".to_string(), filters).to_variant(),
+                "(aa{sv})",
+            )
+            .expect_err("Unknown filters are rejected");
+            assert!(err.matches(gio::DBusError::InvalidArgs), "{}", err);
+
+            let reply = call(
+                &client,
+                &service,
+                "GetSession",
+                ("session-a".to_string(),).to_variant(),
+                "(a{sv})",
+            )
+            .expect("GetSession failed");
+            let (session,) = reply
+                .get::<(HashMap<String, Variant>,)>()
+                .expect("Invalid session");
+            assert_eq!(session["tool"].get::<String>().unwrap(), "claude_code");
+            assert_eq!(
+                session["project_path"].get::<String>().unwrap(),
+                "/projects/alpha"
+            );
+            assert_eq!(session["start_time"].get::<i64>().unwrap(), 10);
+            assert!(!session.contains_key("git_branch"));
+
+            let err = call(
+                &client,
+                &service,
+                "GetSession",
+                ("missing".to_string(),).to_variant(),
+                "(a{sv})",
+            )
+            .expect_err("Unknown sessions are an error");
+            assert!(err.matches(gio::DBusError::InvalidArgs), "{}", err);
+
+            let reply = call(
+                &client,
+                &service,
+                "GetMessages",
+                ("session-a".to_string(), 1_u32, 1_u32).to_variant(),
+                "(aa{sv})",
+            )
+            .expect("GetMessages failed");
+            let (messages,) = reply
+                .get::<(Vec<HashMap<String, Variant>>,)>()
+                .expect("Invalid messages");
+            assert_eq!(messages.len(), 1);
+            assert_eq!(messages[0]["index"].get::<u32>().unwrap(), 1);
+            assert_eq!(messages[0]["role"].get::<String>().unwrap(), "assistant");
+            assert_eq!(
+                messages[0]["content"].get::<String>().unwrap(),
+                "the indexer now writes to sqlite"
+            );
+
+            // A limit of 0 returns every message from the offset on.
+            let reply = call(
+                &client,
+                &service,
+                "GetMessages",
+                ("session-a".to_string(), 1_u32, 0_u32).to_variant(),
+                "(aa{sv})",
+            )
+            .expect("GetMessages failed");
+            let (messages,) = reply
+                .get::<(Vec<HashMap<String, Variant>>,)>()
+                .expect("Invalid messages");
+            assert_eq!(messages.len(), 2);
+
+            call(
+                &client,
+                &service,
+                "Resume",
+                ("session-b".to_string(),).to_variant(),
+                "()",
+            )
+            .expect("Resume failed");
+            assert_eq!(*resumed.borrow(), vec!["session-b".to_string()]);
+
+            let changed = Rc::new(RefCell::new(0));
+            let _subscription = client.subscribe_to_signal(
+                None,
+                Some(INTERFACE),
+                Some("SessionsChanged"),
+                Some(OBJECT_PATH),
+                None,
+                gio::DBusSignalFlags::NONE,
+                {
+                    let changed = changed.clone();
+                    move |_| *changed.borrow_mut() += 1
+                },
+            );
+            // Round trip so the match rule is in place before emitting.
+            call(
+                &client,
+                &service,
+                "GetSession",
+                ("session-a".to_string(),).to_variant(),
+                "(a{sv})",
+            )
+            .expect("GetSession failed");
+            emit_sessions_changed(&server).expect("Failed to emit SessionsChanged");
+            let context = glib::MainContext::ref_thread_default();
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+            while *changed.borrow() == 0 && std::time::Instant::now() < deadline {
+                context.iteration(true);
+            }
+            assert_eq!(*changed.borrow(), 1);
+
+            client.close_sync(gio::Cancellable::NONE).ok();
+            server.close_sync(gio::Cancellable::NONE).ok();
+            bus.down();
+        })
+        .expect("Failed to acquire the main context");
+}
@@ -2,8 +2,8 @@ use rusqlite::Connection;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use sessions_chronicle::database::load_session;
 use sessions_chronicle::database::schema::initialize_database;
+use sessions_chronicle::database::{load_changed_files, load_session};
 use sessions_chronicle::models::Role;
 
 struct TempDatabase {
@@ -131,6 +131,95 @@ fn load_session_returns_existing_session() {
     );
 }
 
+#[test]
+fn load_session_reads_usage_columns() {
+    let db = TempDatabase::new();
+    db.seed_with_messages();
+    db.connection
+        .execute(
+            "UPDATE sessions
+             SET models = 'claude-sonnet-4-5,claude-haiku-4-5',
+                 input_tokens = 1200, output_tokens = 300,
+                 cache_read_tokens = 5000, cache_write_tokens = 80,
+                 cost_usd = 0.05
+             WHERE id = 'test-session'",
+            [],
+        )
+        .expect("Failed to update usage");
+
+    let session = load_session(&db.path, "test-session")
+        .expect("Failed to load session")
+        .expect("Session should exist");
+
+    assert_eq!(
+        session.usage.models,
+        vec!["claude-sonnet-4-5", "claude-haiku-4-5"]
+    );
+    assert_eq!(session.usage.tokens.input, 1200);
+    assert_eq!(session.usage.tokens.output, 300);
+    assert_eq!(session.usage.tokens.cache_read, 5000);
+    assert_eq!(session.usage.tokens.cache_write, 80);
+    assert_eq!(session.usage.cost_usd, Some(0.05));
+}
+
+#[test]
+fn load_session_reads_git_columns() {
+    let db = TempDatabase::new();
+    db.seed_with_messages();
+    db.connection
+        .execute(
+            "UPDATE sessions
+             SET git_branch = 'feature-x', git_commit = 'abc123',
+                 git_remote = 'git@github.com:me/repo.git'
+             WHERE id = 'test-session'",
+            [],
+        )
+        .expect("Failed to update git info");
+
+    let session = load_session(&db.path, "test-session")
+        .expect("Failed to load session")
+        .expect("Session should exist");
+
+    assert_eq!(session.git.branch.as_deref(), Some("feature-x"));
+    assert_eq!(session.git.commit.as_deref(), Some("abc123"));
+    assert_eq!(
+        session.git.remote.as_deref(),
+        Some("git@github.com:me/repo.git")
+    );
+}
+
+#[test]
+fn load_changed_files_collects_edited_paths() {
+    let db = TempDatabase::new();
+    db.seed_with_messages();
+    for (index, content) in [
+        (
+            4_i64,
+            "Edit\tt1\n{\"file_path\":\"/projects/test/src/a.rs\",\"old_string\":\"a\",\"new_string\":\"b\"}",
+        ),
+        (5, "Read\tt2\n{\"file_path\":\"/projects/test/src/b.rs\"}"),
+        (
+            6,
+            "Edit\tt3\n{\"file_path\":\"/projects/test/src/a.rs\",\"old_string\":\"b\",\"new_string\":\"c\"}",
+        ),
+    ] {
+        db.connection
+            .execute(
+                "INSERT INTO messages (session_id, message_index, role, content, timestamp)
+                 VALUES (?1, ?2, 'toolcall', ?3, ?4)",
+                rusqlite::params!["test-session", index, content, 1400_i64 + index],
+            )
+            .expect("Failed to insert tool call");
+    }
+
+    let files = load_changed_files(&db.path, "test-session").expect("Failed to load files");
+
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path, "/projects/test/src/a.rs");
+    assert_eq!(files[0].changes, 2);
+    assert!(!files[0].deleted);
+}
+
 #[test]
 fn load_session_returns_none_for_nonexistent() {
     let db = TempDatabase::new();
@@ -0,0 +1,253 @@
+use rusqlite::Connection;
+use serde_json::{Value, json};
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sessions_chronicle::database::schema::initialize_database;
+use sessions_chronicle::mcp::serve;
+
+struct TempDatabase {
+    path: PathBuf,
+}
+
+impl TempDatabase {
+    fn new() -> Self {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        path.push(format!(
+            "sessions-chronicle-test-mcp-{}-{}.db",
+            std::process::id(),
+            nanos
+        ));
+        let connection = Connection::open(&path).expect("Failed to open temp database");
+        initialize_database(&connection).expect("Failed to initialize database");
+        connection
+            .execute_batch(
+                "INSERT INTO sessions (id, tool, project_path, start_time, message_count, file_path, last_updated, first_prompt)
+                 VALUES ('session-a', 'claude_code', '/home/me/work/chronicle', 10, 2, '/tmp/session-a.jsonl', 30, 'speed up the indexer'),
+                        ('session-b', 'codex', '/home/me/work/other', 20, 1, '/tmp/session-b.jsonl', 40, 'fix the flaky tests');
+                 INSERT INTO messages (session_id, message_index, role, content, timestamp)
+                 VALUES ('session-a', 0, 'user', 'speed up the indexer', 10),
+                        ('session-a', 1, 'assistant', 'batched the inserts in one transaction', 11),
+                        ('session-b', 0, 'user', 'fix the flaky tests', 20);
+                 INSERT INTO session_files (session_id, path, operation, message_index, timestamp)
+                 VALUES ('session-a', '/home/me/work/chronicle/src/database/indexer.rs', 'edit', 1, 11);",
+            )
+            .expect("Failed to seed database");
+
+        Self { path }
+    }
+}
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Send `requests` as one client session and return the responses by id.
+fn exchange(db: &TempDatabase, requests: &[Value]) -> Vec<Value> {
+    let input: String = requests
+        .iter()
+        .map(|request| format!("{}\n", request))
+        .collect();
+    let mut output = Vec::new();
+    serve(&db.path, Cursor::new(input), &mut output).expect("Server failed");
+    String::from_utf8(output)
+        .expect("Responses are UTF-8")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Responses are JSON"))
+        .collect()
+}
+
+fn call(id: u64, tool: &str, arguments: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": tool, "arguments": arguments },
+    })
+}
+
+/// The JSON a successful tool call returned as text.
+fn tool_output(response: &Value) -> Value {
+    assert_eq!(response["result"]["isError"], false, "{}", response);
+    let text = response["result"]["content"][0]["text"]
+        .as_str()
+        .expect("Tool output is text");
+    serde_json::from_str(text).expect("Tool output is JSON")
+}
+
+fn session_ids(output: &Value) -> Vec<&str> {
+    output
+        .as_array()
+        .expect("A list of sessions")
+        .iter()
+        .map(|session| session["session_id"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn mcp_server_handshake_lists_tools() {
+    let db = TempDatabase::new();
+    let responses = exchange(
+        &db,
+        &[
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": {
+                    "protocolVersion": "2025-03-26",
+                    "capabilities": {},
+                    "clientInfo": { "name": "test", "version": "1" },
+                },
+            }),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+        ],
+    );
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(responses[0]["result"]["protocolVersion"], "2025-03-26");
+    assert!(responses[0]["result"]["capabilities"]["tools"].is_object());
+
+    let names: Vec<&str> = responses[1]["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "search_sessions",
+            "get_session",
+            "list_recent_sessions",
+            "find_sessions_touching_file"
+        ]
+    );
+}
+
+#[test]
+fn mcp_tools_answer_from_the_index() {
+    let db = TempDatabase::new();
+    let responses = exchange(
+        &db,
+        &[
+            call(1, "search_sessions", json!({ "query": "transaction" })),
+            call(
+                2,
+                "search_sessions",
+                json!({ "query": "tests", "tools": ["claude_code"] }),
+            ),
+            call(
+                3,
+                "get_session",
+                json!({ "session_id": "session-a", "message_offset": 1 }),
+            ),
+            call(4, "list_recent_sessions", json!({})),
+            call(5, "list_recent_sessions", json!({ "project": "chronicle" })),
+            call(
+                6,
+                "find_sessions_touching_file",
+                json!({ "path": "database/indexer.rs" }),
+            ),
+        ],
+    );
+
+    assert_eq!(session_ids(&tool_output(&responses[0])), vec!["session-a"]);
+    assert!(session_ids(&tool_output(&responses[1])).is_empty());
+
+    let session = tool_output(&responses[2]);
+    assert_eq!(session["title"], "speed up the indexer");
+    assert_eq!(session["project_path"], "/home/me/work/chronicle");
+    assert_eq!(
+        session["messages"],
+        json!([{
+            "index": 1,
+            "role": "assistant",
+            "timestamp": "1970-01-01T00:00:11+00:00",
+            "content": "batched the inserts in one transaction",
+            "truncated": false,
+        }])
+    );
+
+    assert_eq!(
+        session_ids(&tool_output(&responses[3])),
+        vec!["session-b", "session-a"]
+    );
+    assert_eq!(session_ids(&tool_output(&responses[4])), vec!["session-a"]);
+
+    let touching = tool_output(&responses[5]);
+    assert_eq!(session_ids(&touching), vec!["session-a"]);
+    assert_eq!(touching[0]["operations"], json!(["edit"]));
+}
+
+#[test]
+fn mcp_tool_failures_are_reported_to_the_agent() {
+    let db = TempDatabase::new();
+    let responses = exchange(
+        &db,
+        &[
+            call(1, "get_session", json!({ "session_id": "missing" })),
+            call(2, "search_sessions", json!({})),
+            call(3, "delete_everything", json!({})),
+        ],
+    );
+
+    assert_eq!(responses[0]["result"]["isError"], true);
+    assert!(
+        responses[0]["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("No session with id missing")
+    );
+    assert_eq!(responses[1]["result"]["isError"], true);
+    assert_eq!(responses[2]["error"]["code"], -32602);
+}
+
+#[test]
+fn mcp_get_session_cuts_long_tool_call_fields() {
+    let db = TempDatabase::new();
+    let written = "x".repeat(10_000);
+    let call_content = format!(
+        "Write\ttoolu_01\n{}",
+        json!({ "content": written, "file_path": "src/main.rs" })
+    );
+    Connection::open(&db.path)
+        .unwrap()
+        .execute(
+            "INSERT INTO messages (session_id, message_index, role, content, timestamp)
+             VALUES ('session-a', 2, 'toolcall', ?1, 12)",
+            [&call_content],
+        )
+        .unwrap();
+
+    let responses = exchange(
+        &db,
+        &[call(
+            1,
+            "get_session",
+            json!({ "session_id": "session-a", "message_offset": 2 }),
+        )],
+    );
+
+    let message = &tool_output(&responses[0])["messages"][0];
+    assert_eq!(message["role"], "toolcall");
+    assert_eq!(message["truncated"], true);
+    let (header, input) = message["content"]
+        .as_str()
+        .unwrap()
+        .split_once('\n')
+        .unwrap();
+    assert_eq!(header, "Write\ttoolu_01");
+    let input: Value = serde_json::from_str(input).unwrap();
+    assert_eq!(input["file_path"], "src/main.rs");
+    assert_eq!(input["content"].as_str().unwrap().len(), 4000);
+}
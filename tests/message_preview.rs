@@ -2,8 +2,12 @@ use rusqlite::Connection;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use sessions_chronicle::database::load_message_previews_for_session;
 use sessions_chronicle::database::schema::initialize_database;
+use sessions_chronicle::database::{
+    load_bookmarked_indexes, load_bookmarks, load_message_content,
+    load_message_previews_for_session, set_message_bookmarked,
+};
+use sessions_chronicle::models::ToolCall;
 
 struct TempDatabase {
     path: PathBuf,
@@ -104,6 +108,69 @@ fn load_message_previews_truncates_long_content() {
     assert!(preview.is_truncated());
 }
 
+#[test]
+fn load_message_previews_cut_long_tool_call_fields() {
+    let db = TempDatabase::new();
+    db.insert_session("test-session");
+
+    let written = "fn main() {}\n".repeat(1_000);
+    let call = format!(
+        "Write\ttoolu_01\n{}",
+        serde_json::to_string_pretty(
+            &serde_json::json!({ "content": written, "file_path": "src/main.rs" })
+        )
+        .unwrap()
+    );
+    db.insert_message("test-session", 1, "toolcall", &call);
+    db.insert_message(
+        "test-session",
+        2,
+        "toolcall",
+        "Bash\ttoolu_02\n{\"command\": \"ls\"}",
+    );
+
+    let previews = load_message_previews_for_session(&db.path, "test-session", 100, 0, 2000)
+        .expect("Failed to load previews");
+
+    let (header, input) = previews[0].content_preview.split_once('\n').unwrap();
+    assert_eq!(header, "Write\ttoolu_01");
+    let input: serde_json::Value = serde_json::from_str(input).unwrap();
+    assert_eq!(input["file_path"], "src/main.rs");
+    assert_eq!(input["content"].as_str().unwrap(), &written[..2000]);
+    assert!(previews[0].is_truncated());
+    assert_eq!(
+        previews[1].content_preview,
+        "Bash\ttoolu_02\n{\"command\": \"ls\"}"
+    );
+    assert!(!previews[1].is_truncated());
+}
+
+#[test]
+fn load_message_previews_keep_a_long_bash_command() {
+    let db = TempDatabase::new();
+    db.insert_session("test-session");
+
+    let command = format!("cargo test {}", "--features full ".repeat(200));
+    let call = format!(
+        "Bash\ttoolu_01\n{}",
+        serde_json::to_string_pretty(&serde_json::json!({ "command": command })).unwrap()
+    );
+    db.insert_message("test-session", 1, "toolcall", &call);
+    let array_call = format!("Batch\ttoolu_02\n{:?}", vec!["x".repeat(50); 100]);
+    db.insert_message("test-session", 2, "toolcall", &array_call);
+
+    let previews = load_message_previews_for_session(&db.path, "test-session", 100, 0, 2000)
+        .expect("Failed to load previews");
+
+    let call = ToolCall::from_content(&previews[0].content_preview).unwrap();
+    assert_eq!(call.name, "Bash");
+    assert!(call.summary().contains("cargo test --features full"));
+    assert!(previews[0].is_truncated());
+
+    // Inputs that are not objects are cut like any other message.
+    assert_eq!(previews[1].content_preview, &array_call[..2000]);
+}
+
 #[test]
 fn load_message_previews_respects_pagination() {
     let db = TempDatabase::new();
@@ -137,3 +204,64 @@ fn load_message_previews_respects_pagination() {
     assert_eq!(page3.len(), 1);
     assert_eq!(page3[0].content_preview, "Message 4");
 }
+
+#[test]
+fn load_message_content_expands_truncated_preview() {
+    let db = TempDatabase::new();
+    db.insert_session("test-session");
+    db.insert_message("test-session", 0, "user", "Short question");
+    let long_content = format!("{}needle", "b".repeat(5_000));
+    db.insert_message("test-session", 7, "assistant", &long_content);
+
+    let previews = load_message_previews_for_session(&db.path, "test-session", 100, 0, 100)
+        .expect("Failed to load previews");
+    let mut preview = previews[1].clone();
+    assert_eq!(preview.index, 7);
+    assert!(preview.is_truncated());
+
+    let content = load_message_content(&db.path, "test-session", preview.index)
+        .expect("Failed to load content")
+        .expect("Message should exist");
+    preview.expand(content);
+
+    assert!(!preview.is_truncated());
+    assert!(preview.content_preview.ends_with("needle"));
+    assert_eq!(
+        load_message_content(&db.path, "test-session", 3).expect("Query failed"),
+        None
+    );
+}
+
+#[test]
+fn bookmarks_load_the_message_with_its_session() {
+    let db = TempDatabase::new();
+    db.insert_session("test-session");
+    db.insert_message("test-session", 0, "user", "Question");
+    db.insert_message("test-session", 57, "assistant", "The answer worth keeping");
+
+    set_message_bookmarked(&db.path, "test-session", 57, true).unwrap();
+    set_message_bookmarked(&db.path, "test-session", 57, true).unwrap();
+    set_message_bookmarked(&db.path, "gone-session", 3, true).unwrap();
+
+    let bookmarks = load_bookmarks(&db.path, 10).unwrap();
+    assert_eq!(
+        bookmarks.len(),
+        1,
+        "bookmarks of unindexed sessions are hidden"
+    );
+    assert_eq!(bookmarks[0].session.id, "test-session");
+    assert_eq!(bookmarks[0].message.index, 57);
+    assert_eq!(bookmarks[0].message.content_preview, "The answer");
+    assert!(bookmarks[0].message.is_truncated());
+    assert_eq!(
+        bookmarks[0].permalink().to_string(),
+        "sessions-chronicle://session/test-session#57"
+    );
+
+    set_message_bookmarked(&db.path, "test-session", 57, false).unwrap();
+    assert!(
+        load_bookmarked_indexes(&db.path, "test-session")
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -47,7 +47,10 @@ fn opencode_search_finds_text_part_content() {
         .index_opencode_sessions(&storage_root)
         .expect("Failed to index OpenCode sessions");
 
-    assert_eq!(indexed_count, 2, "Should index 2 non-subagent sessions");
+    assert_eq!(
+        indexed_count, 3,
+        "Should index 2 sessions and the subagent of one"
+    );
 
     let sessions = search_sessions(&db.path, &[Tool::OpenCode], "I can help you with that task")
         .expect("Search failed");
@@ -78,7 +81,10 @@ fn opencode_search_excludes_tool_output() {
         .index_opencode_sessions(&storage_root)
         .expect("Failed to index OpenCode sessions");
 
-    assert_eq!(indexed_count, 2, "Should index 2 non-subagent sessions");
+    assert_eq!(
+        indexed_count, 3,
+        "Should index 2 sessions and the subagent of one"
+    );
 
     // Search for content that exists only in tool output (now excluded)
     let sessions = search_sessions(&db.path, &[Tool::OpenCode], "total").expect("Search failed");
@@ -90,6 +96,27 @@ fn opencode_search_excludes_tool_output() {
     );
 }
 
+#[test]
+fn opencode_search_finds_tool_output_on_request() {
+    let db = TempDatabase::new();
+    let storage_root = PathBuf::from("tests/fixtures/opencode_storage");
+
+    let mut indexer = SessionIndexer::new(&db.path).expect("Failed to create indexer");
+    indexer
+        .index_opencode_sessions(&storage_root)
+        .expect("Failed to index OpenCode sessions");
+
+    let sessions =
+        search_sessions(&db.path, &[Tool::OpenCode], "total tools:include").expect("Search failed");
+
+    assert_eq!(
+        sessions.len(),
+        1,
+        "Should find the session whose tool output mentions 'total'"
+    );
+    assert_eq!(sessions[0].id, "session-001");
+}
+
 #[test]
 fn opencode_search_respects_tool_filter() {
     let db = TempDatabase::new();
@@ -0,0 +1,189 @@
+use relm4::gtk::gio::{self, prelude::*};
+use relm4::gtk::glib::{self, Variant};
+use rusqlite::Connection;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sessions_chronicle::database::schema::initialize_database;
+use sessions_chronicle::models::{Permalink, search_uri};
+use sessions_chronicle::search_provider::{OBJECT_PATH, SearchProvider};
+
+const INTERFACE: &str = "org.gnome.Shell.SearchProvider2";
+
+struct TempDatabase {
+    path: PathBuf,
+}
+
+impl TempDatabase {
+    fn new() -> Self {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        path.push(format!(
+            "sessions-chronicle-test-search-provider-{}-{}.db",
+            std::process::id(),
+            nanos
+        ));
+        let connection = Connection::open(&path).expect("Failed to open temp database");
+        initialize_database(&connection).expect("Failed to initialize database");
+        connection
+            .execute_batch(
+                "INSERT INTO sessions (id, tool, project_path, start_time, message_count, file_path, last_updated, first_prompt)
+                 VALUES ('session-a', 'claude_code', '/projects/alpha', 10, 1, '/tmp/session-a.jsonl', 30, 'migrate the indexer to sqlite'),
+                        ('session-b', 'opencode', '/projects/beta', 20, 1, '/tmp/session-b.jsonl', 40, 'fix the flaky tests');
+                 INSERT INTO messages (session_id, message_index, role, content, timestamp)
+                 VALUES ('session-a', 0, 'user', 'migrate the indexer to sqlite', 10),
+                        ('session-b', 0, 'user', 'fix the flaky tests', 20);",
+            )
+            .expect("Failed to seed database");
+
+        Self { path }
+    }
+}
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn connect(address: &str) -> gio::DBusConnection {
+    gio::DBusConnection::for_address_sync(
+        address,
+        gio::DBusConnectionFlags::AUTHENTICATION_CLIENT
+            | gio::DBusConnectionFlags::MESSAGE_BUS_CONNECTION,
+        None,
+        gio::Cancellable::NONE,
+    )
+    .expect("Failed to connect to the test bus")
+}
+
+fn call(
+    client: &gio::DBusConnection,
+    service: &str,
+    method: &str,
+    parameters: Variant,
+    reply_type: &str,
+) -> Variant {
+    let reply_type = glib::VariantTy::new(reply_type).expect("Invalid reply type");
+    glib::MainContext::ref_thread_default()
+        .block_on(client.call_future(
+            Some(service),
+            OBJECT_PATH,
+            INTERFACE,
+            method,
+            Some(&parameters),
+            Some(reply_type),
+            gio::DBusCallFlags::NONE,
+            5000,
+        ))
+        .unwrap_or_else(|err| panic!("{} failed: {}", method, err))
+}
+
+#[test]
+fn search_provider_answers_on_a_private_bus() {
+    let db = TempDatabase::new();
+    let context = glib::MainContext::new();
+    context
+        .with_thread_default(|| {
+            let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+            bus.up();
+            let address = bus.bus_address().expect("Test bus has no address");
+
+            let activated = Rc::new(RefCell::new(Vec::new()));
+            let provider = SearchProvider::new(db.path.clone(), {
+                let activated = activated.clone();
+                move |uri: String| activated.borrow_mut().push(uri)
+            });
+            let server = connect(&address);
+            let _registration = provider
+                .register(&server)
+                .expect("Failed to export the provider");
+            let service = server.unique_name().expect("Server has no unique name");
+            let client = connect(&address);
+
+            let terms = vec!["indexer".to_string()];
+            let reply = call(
+                &client,
+                &service,
+                "GetInitialResultSet",
+                (terms.clone(),).to_variant(),
+                "(as)",
+            );
+            assert_eq!(
+                reply.get::<(Vec<String>,)>(),
+                Some((vec!["session-a".to_string()],))
+            );
+
+            let reply = call(
+                &client,
+                &service,
+                "GetSubsearchResultSet",
+                (vec!["session-a".to_string()], vec!["sqlite".to_string()]).to_variant(),
+                "(as)",
+            );
+            assert_eq!(
+                reply.get::<(Vec<String>,)>(),
+                Some((vec!["session-a".to_string()],))
+            );
+
+            // A subsearch never brings back sessions the first search dropped.
+            let reply = call(
+                &client,
+                &service,
+                "GetSubsearchResultSet",
+                (vec!["session-a".to_string()], vec!["flaky".to_string()]).to_variant(),
+                "(as)",
+            );
+            assert_eq!(reply.get::<(Vec<String>,)>(), Some((Vec::new(),)));
+
+            let reply = call(
+                &client,
+                &service,
+                "GetResultMetas",
+                (vec!["session-a".to_string(), "missing".to_string()],).to_variant(),
+                "(aa{sv})",
+            );
+            let (metas,) = reply
+                .get::<(Vec<HashMap<String, Variant>>,)>()
+                .expect("Invalid metas");
+            assert_eq!(metas.len(), 1);
+            let meta = |key: &str| metas[0][key].get::<String>().unwrap();
+            assert_eq!(meta("id"), "session-a");
+            assert_eq!(meta("name"), "migrate the indexer to sqlite");
+            assert_eq!(meta("description"), "alpha");
+            assert_eq!(meta("gicon"), "claude-code-symbolic");
+
+            call(
+                &client,
+                &service,
+                "ActivateResult",
+                ("session-a".to_string(), terms, 0_u32).to_variant(),
+                "()",
+            );
+            call(
+                &client,
+                &service,
+                "LaunchSearch",
+                (vec!["flaky".to_string(), "tests".to_string()], 0_u32).to_variant(),
+                "()",
+            );
+            assert_eq!(
+                *activated.borrow(),
+                vec![
+                    Permalink::to_session("session-a").to_string(),
+                    search_uri("flaky tests"),
+                ]
+            );
+
+            client.close_sync(gio::Cancellable::NONE).ok();
+            server.close_sync(gio::Cancellable::NONE).ok();
+            bus.down();
+        })
+        .expect("Failed to acquire the main context");
+}
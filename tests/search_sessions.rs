@@ -3,7 +3,11 @@ use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use sessions_chronicle::database::schema::initialize_database;
-use sessions_chronicle::database::search_sessions;
+use sessions_chronicle::database::{
+    load_child_sessions, load_session_note, load_tag_counts, search_sessions,
+    sessions_touching_file, set_session_note, set_session_tags,
+};
+use sessions_chronicle::models::FileOperation;
 use sessions_chronicle::models::Tool;
 
 struct TempDatabase {
@@ -144,3 +148,166 @@ fn search_sessions_sanitizes_invalid_query() {
     assert_eq!(sessions.len(), 1);
     assert_eq!(sessions[0].id, "session-a");
 }
+
+#[test]
+fn search_sessions_filters_by_branch() {
+    let db = TempDatabase::new();
+    db.seed();
+    db.connection
+        .execute_batch(
+            "UPDATE sessions SET git_branch = 'feature-x' WHERE id IN ('session-a', 'session-c');
+             UPDATE sessions SET git_branch = 'main' WHERE id = 'session-b';",
+        )
+        .expect("Failed to set branches");
+
+    let all_tools = [
+        Tool::ClaudeCode,
+        Tool::OpenCode,
+        Tool::Codex,
+        Tool::MistralVibe,
+    ];
+
+    let sessions =
+        search_sessions(&db.path, &all_tools, "alpha branch:feature-x").expect("Search failed");
+    let ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["session-a"]);
+
+    // A filter on its own lists every matching session, newest first.
+    let sessions =
+        search_sessions(&db.path, &all_tools, "branch:feature-*").expect("Search failed");
+    let ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["session-c", "session-a"]);
+    assert_eq!(sessions[0].git.branch.as_deref(), Some("feature-x"));
+}
+
+#[test]
+fn search_sessions_matches_thinking_only_on_request() {
+    let db = TempDatabase::new();
+    db.seed();
+    db.connection
+        .execute(
+            "INSERT INTO messages (session_id, message_index, role, content, timestamp)
+             VALUES ('session-c', 1, 'thinking', 'ponder delta first', 31)",
+            [],
+        )
+        .expect("Failed to insert thinking message");
+
+    let sessions = search_sessions(&db.path, &[Tool::Codex], "delta").expect("Search failed");
+    assert!(sessions.is_empty());
+
+    let sessions =
+        search_sessions(&db.path, &[Tool::Codex], "delta thinking:include").expect("Search failed");
+    let ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["session-c"]);
+}
+
+#[test]
+fn file_filter_and_lookup_match_path_suffixes() {
+    let db = TempDatabase::new();
+    db.seed();
+    db.connection
+        .execute_batch(
+            "INSERT INTO session_files (session_id, path, operation, message_index, timestamp) VALUES
+               ('session-a', '/projects/alpha/src/database/indexer.rs', 'read', 1, 100),
+               ('session-a', '/projects/alpha/src/database/indexer.rs', 'edit', 2, 110),
+               ('session-b', '/projects/beta/src/database/indexer.rs', 'shell', 1, 200),
+               ('session-c', '/projects/gamma/src/database/mod.rs', 'edit', 1, 300);",
+        )
+        .expect("Failed to insert file accesses");
+
+    let all_tools = [
+        Tool::ClaudeCode,
+        Tool::OpenCode,
+        Tool::Codex,
+        Tool::MistralVibe,
+    ];
+
+    let sessions =
+        search_sessions(&db.path, &all_tools, "file:database/indexer.rs").expect("Search failed");
+    let ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["session-b", "session-a"]);
+
+    // `dexer.rs` is not a whole path component.
+    let sessions = search_sessions(&db.path, &all_tools, "file:dexer.rs").expect("Search failed");
+    assert!(sessions.is_empty());
+
+    let activity = sessions_touching_file(&db.path, "/projects/alpha/src/database/indexer.rs")
+        .expect("Lookup failed");
+    assert_eq!(activity.len(), 1);
+    assert_eq!(activity[0].session.id, "session-a");
+    assert_eq!(activity[0].last_touched.timestamp(), 110);
+    let mut operations = activity[0].operations.clone();
+    operations.sort_by_key(|operation| operation.to_storage());
+    assert_eq!(operations, [FileOperation::Edit, FileOperation::Read]);
+
+    let activity = sessions_touching_file(&db.path, "src/database/*.rs").expect("Lookup failed");
+    let ids: Vec<&str> = activity.iter().map(|a| a.session.id.as_str()).collect();
+    assert_eq!(ids, vec!["session-c", "session-b", "session-a"]);
+}
+
+#[test]
+fn subagent_sessions_are_hidden_and_surface_their_parent() {
+    let db = TempDatabase::new();
+    db.seed();
+    db.connection
+        .execute_batch(
+            "INSERT INTO sessions (id, tool, project_path, start_time, message_count, file_path,
+                                   last_updated, parent_session_id)
+             VALUES ('agent-1', 'claude_code', '/projects/alpha', 12, 2,
+                     '/tmp/agent-1.jsonl', 60, 'session-a');
+             INSERT INTO messages (session_id, message_index, role, content, timestamp)
+             VALUES ('agent-1', 0, 'assistant', 'epsilon found in a subagent', 12);",
+        )
+        .expect("Failed to insert subagent");
+
+    let sessions = search_sessions(&db.path, &[Tool::ClaudeCode], "").expect("Search failed");
+    let ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["session-a"]);
+
+    let sessions =
+        search_sessions(&db.path, &[Tool::ClaudeCode], "epsilon").expect("Search failed");
+    let ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["session-a"]);
+
+    let children = load_child_sessions(&db.path, "session-a").expect("Load failed");
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].parent_session_id.as_deref(), Some("session-a"));
+}
+
+#[test]
+fn tags_and_notes_are_searchable() {
+    let db = TempDatabase::new();
+    db.seed();
+    let all_tools = [
+        Tool::ClaudeCode,
+        Tool::OpenCode,
+        Tool::Codex,
+        Tool::MistralVibe,
+    ];
+
+    set_session_tags(&db.path, "session-b", &["design".to_string()]).expect("Tagging failed");
+    set_session_tags(
+        &db.path,
+        "session-c",
+        &["design".to_string(), "incident-42".to_string()],
+    )
+    .expect("Tagging failed");
+    set_session_note(&db.path, "session-c", "Root cause: *zeta* cache").expect("Note failed");
+
+    let sessions = search_sessions(&db.path, &all_tools, "tag:#design").expect("Search failed");
+    let ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["session-c", "session-b"]);
+    assert_eq!(sessions[0].tags, ["design", "incident-42"]);
+
+    let sessions = search_sessions(&db.path, &all_tools, "zeta").expect("Search failed");
+    let ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["session-c"]);
+
+    assert_eq!(
+        load_tag_counts(&db.path).expect("Tag counts failed"),
+        [("design".to_string(), 2), ("incident-42".to_string(), 1)]
+    );
+
+    set_session_note(&db.path, "session-c", "  ").expect("Note failed");
+    assert_eq!(load_session_note(&db.path, "session-c").unwrap(), None);
+}
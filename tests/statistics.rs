@@ -0,0 +1,168 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use rusqlite::Connection;
+use tempfile::NamedTempFile;
+
+use sessions_chronicle::database::schema::initialize_database;
+use sessions_chronicle::database::stats::{StatsFilter, load_statistics};
+use sessions_chronicle::models::Tool;
+
+// 2026-01-05 is a Monday.
+const MONDAY: i64 = 1_767_571_200;
+const DAY: i64 = 86_400;
+
+/// id, tool, project, start, duration, messages, input tokens, cost
+type SeedRow<'a> = (
+    &'a str,
+    &'a str,
+    Option<&'a str>,
+    i64,
+    i64,
+    i64,
+    i64,
+    Option<f64>,
+);
+
+fn seed(path: &std::path::Path) {
+    let conn = Connection::open(path).unwrap();
+    initialize_database(&conn).unwrap();
+
+    let rows: &[SeedRow] = &[
+        (
+            "a",
+            "claude_code",
+            Some("/p/alpha"),
+            MONDAY,
+            600,
+            10,
+            1000,
+            Some(0.5),
+        ),
+        (
+            "b",
+            "claude_code",
+            Some("/p/alpha"),
+            MONDAY + 3600,
+            1200,
+            20,
+            2000,
+            Some(1.0),
+        ),
+        (
+            "c",
+            "codex",
+            Some("/p/beta"),
+            MONDAY + DAY,
+            1800,
+            6,
+            500,
+            None,
+        ),
+        ("d", "opencode", None, MONDAY + 8 * DAY, 0, 4, 0, None),
+    ];
+
+    for (id, tool, project, start, duration, messages, input, cost) in rows {
+        conn.execute(
+            "INSERT INTO sessions (id, tool, project_path, start_time, message_count, file_path,
+                                   last_updated, input_tokens, cost_usd)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            rusqlite::params![
+                id,
+                tool,
+                project,
+                start,
+                messages,
+                format!("/tmp/{id}.jsonl"),
+                start + duration,
+                input,
+                cost
+            ],
+        )
+        .unwrap();
+    }
+}
+
+fn monday() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 1, 5).unwrap()
+}
+
+#[test]
+fn statistics_aggregate_all_sessions() {
+    let db = NamedTempFile::new().unwrap();
+    seed(db.path());
+
+    let stats = load_statistics(
+        db.path(),
+        &StatsFilter {
+            tools: Tool::ALL.to_vec(),
+            since: None,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(stats.totals.sessions, 4);
+    assert_eq!(stats.totals.messages, 40);
+    assert_eq!(stats.totals.avg_messages, 10.0);
+    assert_eq!(stats.totals.avg_duration_secs, 900.0);
+    assert_eq!(stats.totals.tokens.input, 3500);
+    assert_eq!(stats.totals.cost_usd, Some(1.5));
+
+    let first_day: usize = stats
+        .per_day
+        .iter()
+        .filter(|count| count.bucket == monday())
+        .map(|count| count.sessions)
+        .sum();
+    assert_eq!(first_day, 2);
+
+    assert_eq!(stats.per_week.len(), 3);
+    assert!(stats.per_week.iter().all(|count| {
+        count.bucket == monday() || count.bucket == monday() + chrono::Duration::weeks(1)
+    }));
+
+    assert_eq!(stats.busiest_projects[0].project_path, "/p/alpha");
+    assert_eq!(stats.busiest_projects[0].sessions, 2);
+    assert_eq!(stats.busiest_projects[0].messages, 30);
+    assert_eq!(stats.busiest_projects.len(), 2);
+}
+
+#[test]
+fn statistics_respect_tool_and_date_filters() {
+    let db = NamedTempFile::new().unwrap();
+    seed(db.path());
+
+    let stats = load_statistics(
+        db.path(),
+        &StatsFilter {
+            tools: vec![Tool::ClaudeCode, Tool::Codex],
+            since: Some(Utc.timestamp_opt(MONDAY + 3600, 0).unwrap()),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(stats.totals.sessions, 2);
+    assert_eq!(stats.totals.cost_usd, Some(1.0));
+    assert!(
+        stats
+            .per_day
+            .iter()
+            .all(|count| count.tool != Tool::OpenCode)
+    );
+}
+
+#[test]
+fn statistics_are_empty_without_tools() {
+    let db = NamedTempFile::new().unwrap();
+    seed(db.path());
+
+    let stats = load_statistics(
+        db.path(),
+        &StatsFilter {
+            tools: Vec::new(),
+            since: None,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(stats.totals.sessions, 0);
+    assert!(stats.per_day.is_empty());
+}